@@ -5,13 +5,15 @@ pub struct AnsiSqlDialect {}
 
 impl Dialect for AnsiSqlDialect {
     fn is_identifier_start(&self, ch: char) -> bool {
-        (ch >= 'a' && ch <= 'z') || (ch >= 'A' && ch <= 'Z')
+        ch.is_ascii_lowercase() || ch.is_ascii_uppercase()
     }
 
     fn is_identifier_part(&self, ch: char) -> bool {
-        (ch >= 'a' && ch <= 'z')
-            || (ch >= 'A' && ch <= 'Z')
-            || (ch >= '0' && ch <= '9')
-            || ch == '_'
+        ch.is_ascii_lowercase() || ch.is_ascii_uppercase() || ch.is_ascii_digit() || ch == '_'
+    }
+
+    fn is_placeholder_start(&self, ch: char) -> bool {
+        // A <dynamic parameter specification> or an <embedded variable name>
+        ch == '?' || ch == ':'
     }
 }
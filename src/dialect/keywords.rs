@@ -1,16 +1,19 @@
-///! This module defines
-/// 1) a list of constants for every keyword that
-/// can appear in SQLWord::keyword:
-///    pub const KEYWORD = "KEYWORD"
-/// 2) an `ALL_KEYWORDS` array with every keyword in it
-///     This is not a list of *reserved* keywords: some of these can be
-///     parsed as identifiers if the parser decides so. This means that
-///     new keywords can be added here without affecting the parse result.
-///
-///     As a matter of fact, most of these keywords are not used at all
-///     and could be removed.
-/// 3) a `RESERVED_FOR_TABLE_ALIAS` array with keywords reserved in a
-/// "table alias" context.
+//! This module defines
+//! 1) a list of constants for every keyword that
+//!    can appear in SQLWord::keyword:
+//!    pub const KEYWORD = "KEYWORD"
+//! 2) an `ALL_KEYWORDS` array with every keyword in it
+//!    This is not a list of *reserved* keywords: some of these can be
+//!    parsed as identifiers if the parser decides so. This means that
+//!    new keywords can be added here without affecting the parse result.
+//!
+//!    As a matter of fact, most of these keywords are not used at all
+//!    and could be removed.
+//! 3) a `RESERVED_FOR_TABLE_ALIAS` array with keywords reserved in a
+//!    "table alias" context.
+//!
+//! These are the defaults of the corresponding `Dialect` methods, which
+//! dialects can override to supply their own sets.
 
 /// Defines a string constant for a single keyword: `kw_def!(SELECT);`
 /// expands to `pub const SELECT = "SELECT";`
@@ -38,12 +41,17 @@ macro_rules! define_keywords {
 }
 
 define_keywords!(
+    ABORT,
     ABS,
+    ACTION,
     ADD,
+    ANALYSE,
+    ANALYZE,
     ASC,
     ALL,
     ALLOCATE,
     ALTER,
+    ALWAYS,
     AND,
     ANY,
     ARE,
@@ -55,7 +63,10 @@ define_keywords!(
     ASYMMETRIC,
     AT,
     ATOMIC,
+    ATTACH,
     AUTHORIZATION,
+    AUTOINCREMENT,
+    AUTO_INCREMENT,
     AVG,
     BEGIN,
     BEGIN_FRAME,
@@ -67,6 +78,7 @@ define_keywords!(
     BOOLEAN,
     BOTH,
     BY,
+    CACHE,
     BYTEA,
     CALL,
     CALLED,
@@ -75,9 +87,12 @@ define_keywords!(
     CASCADED,
     CASE,
     CAST,
+    CATCH,
     CEIL,
     CEILING,
+    CHAIN,
     CHAR,
+    CHARSET,
     CHAR_LENGTH,
     CHARACTER,
     CHARACTER_LENGTH,
@@ -89,7 +104,10 @@ define_keywords!(
     COLLECT,
     COLUMN,
     COMMIT,
+    COMMITTED,
+    CONCURRENTLY,
     CONDITION,
+    CONFLICT,
     CONNECT,
     CONSTRAINT,
     CONTAINS,
@@ -97,6 +115,7 @@ define_keywords!(
     COPY,
     CORR,
     CORRESPONDING,
+    COST,
     COUNT,
     COVAR_POP,
     COVAR_SAMP,
@@ -119,6 +138,8 @@ define_keywords!(
     CURRENT_USER,
     CURSOR,
     CYCLE,
+    DATA,
+    DATABASE,
     DATE,
     DAY,
     DEALLOCATE,
@@ -126,6 +147,8 @@ define_keywords!(
     DECIMAL,
     DECLARE,
     DEFAULT,
+    DEFERRABLE,
+    DEFINER,
     DELETE,
     DENSE_RANK,
     DEREF,
@@ -134,8 +157,11 @@ define_keywords!(
     DETERMINISTIC,
     DISCONNECT,
     DISTINCT,
+    DIV,
+    DO,
     DOUBLE,
     DROP,
+    DUPLICATE,
     DYNAMIC,
     EACH,
     ELEMENT,
@@ -143,6 +169,7 @@ define_keywords!(
     END,
     END_FRAME,
     END_PARTITION,
+    ENGINE,
     EQUALS,
     ESCAPE,
     EVERY,
@@ -153,6 +180,7 @@ define_keywords!(
     EXP,
     EXTERNAL,
     EXTRACT,
+    FAIL,
     FALSE,
     FETCH,
     FIRST,
@@ -169,6 +197,7 @@ define_keywords!(
     FULL,
     FUNCTION,
     FUSION,
+    GENERATED,
     GET,
     GLOBAL,
     GRANT,
@@ -181,10 +210,17 @@ define_keywords!(
     HOUR,
     IDENTITY,
     IF,
+    IGNORE,
+    IMMUTABLE,
     IN,
+    INCLUDE,
+    INCREMENT,
+    INDEX,
     INDICATOR,
+    INITIALLY,
     INNER,
     INOUT,
+    INPUT,
     INSENSITIVE,
     INSERT,
     INT,
@@ -193,17 +229,21 @@ define_keywords!(
     INTERSECTION,
     INTERVAL,
     INTO,
+    INVOKER,
     IS,
+    ISOLATION,
     JOIN,
     KEY,
     LAG,
     LANGUAGE,
     LARGE,
+    LAST,
     LAST_VALUE,
     LATERAL,
     LEAD,
     LEADING,
     LEFT,
+    LEVEL,
     LIKE,
     LIKE_REGEX,
     LIMIT,
@@ -215,11 +255,14 @@ define_keywords!(
     LOWER,
     MATCH,
     MATERIALIZED,
+    MATCHED,
     MAX,
+    MAXVALUE,
     MEMBER,
     MERGE,
     METHOD,
     MIN,
+    MINVALUE,
     MINUTE,
     MOD,
     MODIFIES,
@@ -236,10 +279,12 @@ define_keywords!(
     NONE,
     NORMALIZE,
     NOT,
+    NOTHING,
     NTH_VALUE,
     NTILE,
     NULL,
     NULLIF,
+    NULLS,
     NUMERIC,
     OBJECT,
     OCTET_LENGTH,
@@ -254,9 +299,12 @@ define_keywords!(
     ORDER,
     OUT,
     OUTER,
+    OUTPUT,
     OVER,
     OVERLAPS,
     OVERLAY,
+    OWNED,
+    PARALLEL,
     PARAMETER,
     PARTITION,
     PARQUET,
@@ -265,18 +313,22 @@ define_keywords!(
     PERCENTILE_CONT,
     PERCENTILE_DISC,
     PERIOD,
+    PLACING,
     PORTION,
     POSITION,
     POSITION_REGEX,
     POWER,
+    PRAGMA,
     PRECEDES,
     PRECEDING,
     PRECISION,
     PREPARE,
     PRIMARY,
+    PROC,
     PROCEDURE,
     RANGE,
     RANK,
+    READ,
     READS,
     REAL,
     RECURSIVE,
@@ -294,29 +346,43 @@ define_keywords!(
     REGR_SXY,
     REGR_SYY,
     RELEASE,
+    RENAME,
+    REPEATABLE,
+    REPLACE,
     RESTRICT,
+    RESTART,
+    RESTRICTED,
     RESULT,
     RETURN,
+    RETURNING,
     RETURNS,
     REVOKE,
     RIGHT,
     ROLLBACK,
     ROLLUP,
     ROW,
+    ROWID,
     ROW_NUMBER,
     ROWS,
+    SAFE,
     SAVEPOINT,
+    SCHEMA,
     SCOPE,
     SCROLL,
     SEARCH,
     SECOND,
+    SECURITY,
     SELECT,
     SENSITIVE,
+    SEQUENCE,
+    SERIALIZABLE,
     SESSION_USER,
     SET,
+    SETOF,
     SIMILAR,
     SMALLINT,
     SOME,
+    SOURCE,
     SPECIFIC,
     SPECIFICTYPE,
     SQL,
@@ -324,12 +390,14 @@ define_keywords!(
     SQLSTATE,
     SQLWARNING,
     SQRT,
+    STABLE,
     START,
     STATIC,
     STDDEV_POP,
     STDDEV_SAMP,
     STDIN,
     STORED,
+    STRICT,
     SUBMULTISET,
     SUBSTRING,
     SUBSTRING_REGEX,
@@ -341,6 +409,9 @@ define_keywords!(
     SYSTEM_USER,
     TABLE,
     TABLESAMPLE,
+    TARGET,
+    TEMP,
+    TEMPORARY,
     TEXT,
     THEN,
     TIES,
@@ -349,7 +420,10 @@ define_keywords!(
     TIMEZONE_HOUR,
     TIMEZONE_MINUTE,
     TO,
+    TOP,
     TRAILING,
+    TRAN,
+    TRANSACTION,
     TRANSLATE,
     TRANSLATE_REGEX,
     TRANSLATION,
@@ -359,12 +433,17 @@ define_keywords!(
     TRIM,
     TRIM_ARRAY,
     TRUE,
+    TRY,
+    TYPE,
     UESCAPE,
     UNBOUNDED,
+    UNCOMMITTED,
     UNION,
     UNIQUE,
     UNKNOWN,
+    UNLOGGED,
     UNNEST,
+    UNSAFE,
     UPDATE,
     UPPER,
     USER,
@@ -373,6 +452,7 @@ define_keywords!(
     VALUE,
     VALUES,
     VALUE_OF,
+    VARIADIC,
     VAR_POP,
     VAR_SAMP,
     VARBINARY,
@@ -380,6 +460,7 @@ define_keywords!(
     VARYING,
     VERSIONING,
     VIEW,
+    VOLATILE,
     WHEN,
     WHENEVER,
     WHERE,
@@ -388,6 +469,9 @@ define_keywords!(
     WITH,
     WITHIN,
     WITHOUT,
+    WORK,
+    WRITE,
+    XOR,
     YEAR,
     ZONE,
     END_EXEC = "END-EXEC"
@@ -397,16 +481,54 @@ define_keywords!(
 /// can be parsed unambiguously without looking ahead.
 pub const RESERVED_FOR_TABLE_ALIAS: &[&str] = &[
     // Reserved as both a table and a column alias:
-    WITH, SELECT, WHERE, GROUP, ORDER, UNION, EXCEPT, INTERSECT,
+    WITH, SELECT, WHERE, GROUP, ORDER, UNION, EXCEPT, INTERSECT, RETURNING,
     // Reserved only as a table alias in the `FROM`/`JOIN` clauses:
     ON, JOIN, INNER, CROSS, FULL, LEFT, RIGHT, NATURAL, USING, LIMIT, OFFSET, FETCH,
+    // Reserved only as a table alias after `UPDATE <table>`:
+    SET,
 ];
 
 /// Can't be used as a column alias, so that `SELECT <expr> alias`
 /// can be parsed unambiguously without looking ahead.
 pub const RESERVED_FOR_COLUMN_ALIAS: &[&str] = &[
     // Reserved as both a table and a column alias:
-    WITH, SELECT, WHERE, GROUP, ORDER, UNION, EXCEPT, INTERSECT,
+    WITH, SELECT, WHERE, GROUP, ORDER, UNION, EXCEPT, INTERSECT, RETURNING,
     // Reserved only as a column alias in the `SELECT` clause:
     FROM,
 ];
+
+/// Keywords that can't be used as unquoted identifiers, so the printer
+/// quotes identifiers matching them (see `Dialect::reserved_keywords`).
+/// This is the subset of the standard's reserved words that this parser
+/// would misinterpret.
+pub const RESERVED_KEYWORDS: &[&str] = &[
+    ALL, AND, AS, ASC, BETWEEN, BY, CASE, CAST, CHECK, COLLATE, COLUMN, CONSTRAINT, CREATE, CROSS,
+    DEFAULT, DELETE, DESC, DISTINCT, DROP, ELSE, END, EXCEPT, EXISTS, FALSE, FETCH, FOR, FOREIGN,
+    FROM, FULL, GROUP, HAVING, IN, INNER, INSERT, INTERSECT, INTO, IS, JOIN, LEFT, LIKE, LIMIT,
+    NATURAL, NOT, NULL, OFFSET, ON, OR, ORDER, OUTER, PRIMARY, REFERENCES, RIGHT, SELECT, SET,
+    TABLE, THEN, TO, TRUE, UNION, UNIQUE, UPDATE, USING, VALUES, WHEN, WHERE, WITH,
+];
+
+/// Keywords that begin a column constraint or option, and so end the data
+/// type in a column definition (see `Parser::parse_column_data_type`).
+pub const COLUMN_OPTION_KEYWORDS: &[&str] = &[
+    CONSTRAINT,
+    PRIMARY,
+    NOT,
+    NULL,
+    UNIQUE,
+    CHECK,
+    DEFAULT,
+    COLLATE,
+    REFERENCES,
+    GENERATED,
+    AS,
+    AUTO_INCREMENT,
+    AUTOINCREMENT,
+];
+
+/// Keywords that begin a new clause of a query. When recovering from an
+/// error, the parser skips to one of these (see `Parser::parse_clause`).
+pub const QUERY_CLAUSE_KEYWORDS: &[&str] = &[
+    FROM, WHERE, GROUP, HAVING, ORDER, LIMIT, OFFSET, FETCH, UNION, EXCEPT, INTERSECT,
+];
@@ -2,14 +2,21 @@ mod ansi_sql;
 mod generic_sql;
 pub mod keywords;
 mod mssql;
+mod mysql;
 mod postgresql;
+mod sqlite;
 
 use std::fmt::Debug;
 
+use crate::sqlast::{ASTNode, SQLStatement, SQLType};
+use crate::sqlparser::{Parser, ParserError};
+
 pub use self::ansi_sql::AnsiSqlDialect;
 pub use self::generic_sql::GenericSqlDialect;
 pub use self::mssql::MsSqlDialect;
+pub use self::mysql::MySqlDialect;
 pub use self::postgresql::PostgreSqlDialect;
+pub use self::sqlite::SQLiteDialect;
 
 pub trait Dialect: Debug {
     /// Determine if a character starts a quoted identifier. The default
@@ -24,4 +31,123 @@ pub trait Dialect: Debug {
     fn is_identifier_start(&self, ch: char) -> bool;
     /// Determine if a character is a valid unquoted identifier character
     fn is_identifier_part(&self, ch: char) -> bool;
+    /// Determine if `"` quotes a string literal rather than an identifier,
+    /// as it does in MySQL. Such strings are tokenized as
+    /// `Token::DoubleQuotedString`.
+    fn supports_double_quoted_strings(&self) -> bool {
+        false
+    }
+    /// Determine if `#` starts a single-line comment, like `--` does
+    fn supports_hash_comments(&self) -> bool {
+        false
+    }
+    /// Determine if a character starts a query parameter (`Token::Placeholder`):
+    /// a positional `?`, or the prefix of a numbered (`$1`, `?1`) or named
+    /// (`:name`, `@name`) parameter
+    fn is_placeholder_start(&self, _ch: char) -> bool {
+        false
+    }
+    /// Determine if `$` starts a dollar-quoted string (`$$...$$` or
+    /// `$tag$...$tag$`), as it does in PostgreSQL. Such strings are tokenized
+    /// as `Token::DollarQuotedString`.
+    fn supports_dollar_quoted_strings(&self) -> bool {
+        false
+    }
+    /// Determine if a backslash in a string literal escapes the following
+    /// character (as in `'It\'s'` or `'\n'`) instead of standing for itself.
+    /// The escape sequences are kept in the string's value as written, except
+    /// for escaped quotes, so that it prints back the same.
+    fn supports_backslash_escapes(&self) -> bool {
+        false
+    }
+    /// Determine if `LIMIT <offset>, <count>` is accepted as a shorthand for
+    /// `LIMIT <count> OFFSET <offset>`, as in MySQL
+    fn supports_limit_comma(&self) -> bool {
+        false
+    }
+    /// Determine if the data type of a column definition can be omitted, as
+    /// in SQLite's `CREATE TABLE t (a, b)`. Such columns get the type
+    /// `SQLType::Unspecified`.
+    fn supports_untyped_columns(&self) -> bool {
+        false
+    }
+    /// Determine if a column definition's data type can be any sequence of
+    /// names, optionally followed by numeric arguments, as in SQLite's
+    /// `UNSIGNED BIG INT` or `NVARCHAR(100)`. Types not otherwise known get
+    /// the type `SQLType::FreeForm`.
+    fn supports_free_form_types(&self) -> bool {
+        false
+    }
+    /// The words the tokenizer recognizes as keywords (see `SQLWord::keyword`),
+    /// other unquoted words are only ever parsed as identifiers. The parser
+    /// only looks for `keywords::ALL_KEYWORDS`, so this should be a subset of it.
+    fn keywords(&self) -> &[&str] {
+        keywords::ALL_KEYWORDS
+    }
+    /// Keywords that can't be used as a table alias without `AS`, as in
+    /// `FROM t alias`
+    fn reserved_for_table_alias(&self) -> &[&str] {
+        keywords::RESERVED_FOR_TABLE_ALIAS
+    }
+    /// Keywords that can't be used as a column alias without `AS`, as in
+    /// `SELECT expr alias`
+    fn reserved_for_column_alias(&self) -> &[&str] {
+        keywords::RESERVED_FOR_COLUMN_ALIAS
+    }
+    /// Keywords that can't be used as unquoted identifiers
+    fn reserved_keywords(&self) -> &[&str] {
+        keywords::RESERVED_KEYWORDS
+    }
+    /// Determine if an identifier (without quotes) has to be quoted to be
+    /// read back as the same identifier, because it is a reserved keyword or
+    /// contains characters not allowed in an unquoted identifier
+    fn identifier_requires_quoting(&self, ident: &str) -> bool {
+        let mut chars = ident.chars();
+        match chars.next() {
+            Some(ch) if self.is_identifier_start(ch) => {
+                !chars.all(|ch| self.is_identifier_part(ch))
+                    || self
+                        .reserved_keywords()
+                        .contains(&ident.to_uppercase().as_str())
+            }
+            _ => true,
+        }
+    }
+    /// Quote an identifier, escaping the quotes in it if necessary
+    fn quote_identifier(&self, ident: &str) -> String {
+        format!("\"{}\"", ident.replace('"', "\"\""))
+    }
+
+    // The following hooks let a dialect extend or override the grammar. Each
+    // is called before the corresponding `Parser` method runs its default
+    // logic: returning `None` (without consuming any tokens) falls back to
+    // the default, while `Some` is returned from the `Parser` method as is.
+
+    /// Parse a statement, see `Parser::parse_statement`
+    fn parse_statement(&self, _parser: &mut Parser) -> Option<Result<SQLStatement, ParserError>> {
+        None
+    }
+    /// Parse an expression prefix, see `Parser::parse_prefix`
+    fn parse_prefix(&self, _parser: &mut Parser) -> Option<Result<ASTNode, ParserError>> {
+        None
+    }
+    /// Parse the operator following `expr` and its right-hand side, see
+    /// `Parser::parse_infix`. Only called for tokens that `get_next_precedence`
+    /// assigned a non-zero precedence to.
+    fn parse_infix(
+        &self,
+        _parser: &mut Parser,
+        _expr: &ASTNode,
+        _precedence: u8,
+    ) -> Option<Result<ASTNode, ParserError>> {
+        None
+    }
+    /// Get the precedence of the next token, see `Parser::get_next_precedence`
+    fn get_next_precedence(&self, _parser: &Parser) -> Option<Result<u8, ParserError>> {
+        None
+    }
+    /// Parse a data type, see `Parser::parse_data_type`
+    fn parse_data_type(&self, _parser: &mut Parser) -> Option<Result<SQLType, ParserError>> {
+        None
+    }
 }
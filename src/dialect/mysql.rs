@@ -0,0 +1,116 @@
+use crate::dialect::keywords::*;
+use crate::dialect::Dialect;
+use crate::sqlast::{ASTNode, SQLOperator, Spanned};
+use crate::sqlparser::{Parser, ParserError};
+use crate::sqltokenizer::Token;
+
+/// `keywords::RESERVED_FOR_TABLE_ALIAS`, except for OFFSET (which isn't
+/// reserved in MySQL, and can only follow a LIMIT)
+const RESERVED_FOR_TABLE_ALIAS: &[&str] = &[
+    WITH, SELECT, WHERE, GROUP, ORDER, UNION, EXCEPT, INTERSECT, RETURNING, ON, JOIN, INNER, CROSS,
+    FULL, LEFT, RIGHT, NATURAL, USING, LIMIT, FETCH, SET,
+];
+
+/// See https://dev.mysql.com/doc/refman/8.0/en/keywords.html (the keywords
+/// marked "R" that this parser knows about)
+const RESERVED_KEYWORDS: &[&str] = &[
+    ALL, AND, AS, ASC, BETWEEN, BY, CASE, CHECK, COLLATE, COLUMN, CONSTRAINT, CREATE, CROSS,
+    DEFAULT, DELETE, DESC, DISTINCT, DIV, DROP, ELSE, EXCEPT, EXISTS, FALSE, FETCH, FOR, FOREIGN,
+    FROM, GROUP, HAVING, IN, INDEX, INNER, INSERT, INTERSECT, INTERVAL, INTO, IS, JOIN, KEY, LEFT,
+    LIKE, LIMIT, NATURAL, NOT, NULL, ON, OR, ORDER, OUTER, PRIMARY, RANGE, REFERENCES, REPLACE,
+    RIGHT, ROWS, SELECT, SET, TABLE, THEN, TO, TRUE, UNION, UNIQUE, UPDATE, USING, VALUES, WHEN,
+    WHERE, WITH, XOR,
+];
+
+#[derive(Debug)]
+pub struct MySqlDialect {}
+
+impl Dialect for MySqlDialect {
+    fn is_delimited_identifier_start(&self, ch: char) -> bool {
+        ch == '`'
+    }
+
+    fn is_identifier_start(&self, ch: char) -> bool {
+        // See https://dev.mysql.com/doc/refman/8.0/en/identifiers.html
+        // We don't yet support identifiers beginning with a digit or with
+        // non-ASCII characters.
+        ch.is_ascii_lowercase() || ch.is_ascii_uppercase() || ch == '_' || ch == '$'
+    }
+
+    fn is_identifier_part(&self, ch: char) -> bool {
+        self.is_identifier_start(ch) || ch.is_ascii_digit()
+    }
+
+    fn is_placeholder_start(&self, ch: char) -> bool {
+        // Prepared statements only support positional parameters
+        ch == '?'
+    }
+
+    fn supports_double_quoted_strings(&self) -> bool {
+        // Unless the ANSI_QUOTES SQL mode is enabled
+        true
+    }
+
+    fn supports_hash_comments(&self) -> bool {
+        true
+    }
+
+    fn supports_backslash_escapes(&self) -> bool {
+        // Unless the NO_BACKSLASH_ESCAPES SQL mode is enabled
+        true
+    }
+
+    fn supports_limit_comma(&self) -> bool {
+        true
+    }
+
+    fn reserved_for_table_alias(&self) -> &[&str] {
+        RESERVED_FOR_TABLE_ALIAS
+    }
+
+    fn reserved_keywords(&self) -> &[&str] {
+        RESERVED_KEYWORDS
+    }
+
+    fn quote_identifier(&self, ident: &str) -> String {
+        format!("`{}`", ident.replace('`', "``"))
+    }
+
+    fn parse_infix(
+        &self,
+        parser: &mut Parser,
+        expr: &ASTNode,
+        precedence: u8,
+    ) -> Option<Result<ASTNode, ParserError>> {
+        let op = mysql_operator(parser)?;
+        parser.next_token();
+        Some(parser.parse_subexpr(precedence).map(|right| {
+            let span = expr.span().union(&right.span());
+            ASTNode::SQLBinaryExpr {
+                left: Box::new(expr.clone()),
+                op,
+                right: Box::new(right),
+                span,
+            }
+        }))
+    }
+
+    fn get_next_precedence(&self, parser: &Parser) -> Option<Result<u8, ParserError>> {
+        let precedence = match mysql_operator(parser)? {
+            SQLOperator::Xor => 7,
+            SQLOperator::Spaceship => 20,
+            _ => 40,
+        };
+        Some(Ok(precedence))
+    }
+}
+
+/// The MySQL-specific binary operator at the current position, if any
+fn mysql_operator(parser: &Parser) -> Option<SQLOperator> {
+    match parser.peek_token()? {
+        Token::Spaceship => Some(SQLOperator::Spaceship),
+        Token::SQLWord(ref w) if w.keyword == XOR => Some(SQLOperator::Xor),
+        Token::SQLWord(ref w) if w.keyword == DIV => Some(SQLOperator::IntegerDivide),
+        _ => None,
+    }
+}
@@ -1,5 +1,89 @@
+use crate::dialect::keywords::*;
 use crate::dialect::Dialect;
 
+/// See https://www.postgresql.org/docs/11/sql-keywords-appendix.html
+/// (the "reserved" keywords, excluding the ones that can be function or
+/// type names)
+const RESERVED_KEYWORDS: &[&str] = &[
+    ALL,
+    ANALYSE,
+    ANALYZE,
+    AND,
+    ANY,
+    ARRAY,
+    AS,
+    ASC,
+    ASYMMETRIC,
+    BOTH,
+    CASE,
+    CAST,
+    CHECK,
+    COLLATE,
+    COLUMN,
+    CONSTRAINT,
+    CREATE,
+    CURRENT_CATALOG,
+    CURRENT_DATE,
+    CURRENT_ROLE,
+    CURRENT_TIME,
+    CURRENT_TIMESTAMP,
+    CURRENT_USER,
+    DEFAULT,
+    DEFERRABLE,
+    DESC,
+    DISTINCT,
+    DO,
+    ELSE,
+    END,
+    EXCEPT,
+    FALSE,
+    FETCH,
+    FOR,
+    FOREIGN,
+    FROM,
+    GRANT,
+    GROUP,
+    HAVING,
+    IN,
+    INITIALLY,
+    INTERSECT,
+    INTO,
+    LATERAL,
+    LEADING,
+    LIMIT,
+    LOCALTIME,
+    LOCALTIMESTAMP,
+    NOT,
+    NULL,
+    OFFSET,
+    ON,
+    ONLY,
+    OR,
+    ORDER,
+    PLACING,
+    PRIMARY,
+    REFERENCES,
+    RETURNING,
+    SELECT,
+    SESSION_USER,
+    SOME,
+    SYMMETRIC,
+    TABLE,
+    THEN,
+    TO,
+    TRAILING,
+    TRUE,
+    UNION,
+    UNIQUE,
+    USER,
+    USING,
+    VARIADIC,
+    WHEN,
+    WHERE,
+    WINDOW,
+    WITH,
+];
+
 #[derive(Debug)]
 pub struct PostgreSqlDialect {}
 
@@ -8,14 +92,27 @@ impl Dialect for PostgreSqlDialect {
         // See https://www.postgresql.org/docs/11/sql-syntax-lexical.html#SQL-SYNTAX-IDENTIFIERS
         // We don't yet support identifiers beginning with "letters with
         // diacritical marks and non-Latin letters"
-        (ch >= 'a' && ch <= 'z') || (ch >= 'A' && ch <= 'Z') || ch == '_'
+        ch.is_ascii_lowercase() || ch.is_ascii_uppercase() || ch == '_'
     }
 
     fn is_identifier_part(&self, ch: char) -> bool {
-        (ch >= 'a' && ch <= 'z')
-            || (ch >= 'A' && ch <= 'Z')
-            || (ch >= '0' && ch <= '9')
+        ch.is_ascii_lowercase()
+            || ch.is_ascii_uppercase()
+            || ch.is_ascii_digit()
             || ch == '$'
             || ch == '_'
     }
+
+    fn is_placeholder_start(&self, ch: char) -> bool {
+        // `$1`, see https://www.postgresql.org/docs/11/sql-expressions.html#SQL-EXPRESSIONS-PARAMETERS-POSITIONAL
+        ch == '$'
+    }
+
+    fn supports_dollar_quoted_strings(&self) -> bool {
+        true
+    }
+
+    fn reserved_keywords(&self) -> &[&str] {
+        RESERVED_KEYWORDS
+    }
 }
@@ -0,0 +1,44 @@
+use crate::dialect::Dialect;
+
+#[derive(Debug)]
+pub struct SQLiteDialect {}
+
+impl Dialect for SQLiteDialect {
+    // See https://www.sqlite.org/lang_keywords.html: besides the standard
+    // "double quotes", SQLite accepts MySQL `backticks` and MS SQL [brackets]
+    fn is_delimited_identifier_start(&self, ch: char) -> bool {
+        ch == '`' || ch == '"' || ch == '['
+    }
+
+    fn is_identifier_start(&self, ch: char) -> bool {
+        // See https://www.sqlite.org/draft/tokenreq.html
+        ch.is_ascii_lowercase() || ch.is_ascii_uppercase() || ch == '_'
+    }
+
+    fn is_identifier_part(&self, ch: char) -> bool {
+        self.is_identifier_start(ch) || ch.is_ascii_digit() || ch == '$'
+    }
+
+    fn is_placeholder_start(&self, ch: char) -> bool {
+        // `?NNN`, `:AAAA`, `@AAAA` and `$AAAA`, see
+        // https://www.sqlite.org/lang_expr.html#varparam
+        ch == '?' || ch == ':' || ch == '@' || ch == '$'
+    }
+
+    fn supports_limit_comma(&self) -> bool {
+        // See https://www.sqlite.org/lang_select.html#limitoffset
+        true
+    }
+
+    fn supports_untyped_columns(&self) -> bool {
+        // A column's type affinity defaults to BLOB, see
+        // https://www.sqlite.org/datatype3.html#determination_of_column_affinity
+        true
+    }
+
+    fn supports_free_form_types(&self) -> bool {
+        // Any type name is accepted, and mapped to an affinity by the words
+        // it contains, see the same page
+        true
+    }
+}
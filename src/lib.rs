@@ -38,6 +38,7 @@
 
 pub mod dialect;
 pub mod sqlast;
+pub mod sqlformat;
 pub mod sqlparser;
 pub mod sqltokenizer;
 
@@ -1,21 +1,190 @@
 //! AST types specific to CREATE/ALTER variants of `SQLStatement`
 //! (commonly referred to as Data Definition Language, or DDL)
-use super::{ASTNode, SQLIdent, SQLObjectName};
+use super::{
+    display_comma_separated, ASTNode, DollarQuotedString, SQLColumnDef, SQLIdent, SQLObjectName,
+    SQLType, Span, Spanned,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fmt;
 
 /// An `ALTER TABLE` (`SQLStatement::SQLAlterTable`) operation
 #[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum AlterTableOperation {
     /// `ADD <table_constraint>`
     AddConstraint(TableConstraint),
-    /// TODO: implement `DROP CONSTRAINT <name>`
-    DropConstraint { name: SQLIdent },
+    /// `ADD [ COLUMN ] <column_def>`
+    AddColumn {
+        /// Whether the optional `COLUMN` keyword was present
+        column_keyword: bool,
+        column_def: SQLColumnDef,
+        span: Span,
+    },
+    /// `DROP CONSTRAINT <name>`
+    DropConstraint { name: SQLIdent, span: Span },
+    /// `DROP [ COLUMN ] [ IF EXISTS ] <column_name> [ CASCADE ]`
+    DropColumn {
+        column_name: SQLIdent,
+        if_exists: bool,
+        cascade: bool,
+        span: Span,
+    },
+    /// `RENAME [ COLUMN ] <old_column_name> TO <new_column_name>`
+    RenameColumn {
+        old_column_name: SQLIdent,
+        new_column_name: SQLIdent,
+        span: Span,
+    },
+    /// `RENAME TO <table_name>`
+    RenameTable {
+        table_name: SQLObjectName,
+        span: Span,
+    },
+    /// `ALTER [ COLUMN ] <column_name> <op>`
+    AlterColumn {
+        column_name: SQLIdent,
+        op: AlterColumnOperation,
+        span: Span,
+    },
+}
+
+impl fmt::Display for AlterTableOperation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AlterTableOperation::AddConstraint(c) => write!(f, "ADD {}", c),
+            AlterTableOperation::AddColumn {
+                column_keyword,
+                column_def,
+                ..
+            } => {
+                f.write_str(if *column_keyword { "ADD COLUMN" } else { "ADD" })?;
+                write!(f, " {}", column_def)
+            }
+            AlterTableOperation::DropConstraint { name, .. } => {
+                write!(f, "DROP CONSTRAINT {}", name)
+            }
+            AlterTableOperation::DropColumn {
+                column_name,
+                if_exists,
+                cascade,
+                ..
+            } => write!(
+                f,
+                "DROP COLUMN {}{}{}",
+                if *if_exists { "IF EXISTS " } else { "" },
+                column_name,
+                if *cascade { " CASCADE" } else { "" }
+            ),
+            AlterTableOperation::RenameColumn {
+                old_column_name,
+                new_column_name,
+                ..
+            } => write!(
+                f,
+                "RENAME COLUMN {} TO {}",
+                old_column_name, new_column_name
+            ),
+            AlterTableOperation::RenameTable { table_name, .. } => {
+                write!(f, "RENAME TO {}", table_name)
+            }
+            AlterTableOperation::AlterColumn {
+                column_name, op, ..
+            } => write!(f, "ALTER COLUMN {} {}", column_name, op),
+        }
+    }
 }
 
-impl ToString for AlterTableOperation {
-    fn to_string(&self) -> String {
+impl Spanned for AlterTableOperation {
+    fn span(&self) -> Span {
         match self {
-            AlterTableOperation::AddConstraint(c) => format!("ADD {}", c.to_string()),
-            AlterTableOperation::DropConstraint { name } => format!("DROP CONSTRAINT {}", name),
+            AlterTableOperation::AddConstraint(c) => c.span(),
+            AlterTableOperation::AddColumn { span, .. }
+            | AlterTableOperation::DropConstraint { span, .. }
+            | AlterTableOperation::DropColumn { span, .. }
+            | AlterTableOperation::RenameColumn { span, .. }
+            | AlterTableOperation::RenameTable { span, .. }
+            | AlterTableOperation::AlterColumn { span, .. } => *span,
+        }
+    }
+}
+
+/// An `ALTER TABLE ... ALTER COLUMN <name>` operation
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum AlterColumnOperation {
+    /// `SET NOT NULL`
+    SetNotNull,
+    /// `DROP NOT NULL`
+    DropNotNull,
+    /// `SET DEFAULT <expr>`
+    SetDefault { value: ASTNode },
+    /// `DROP DEFAULT`
+    DropDefault,
+    /// `[ SET DATA ] TYPE <data_type>`
+    SetDataType { data_type: SQLType },
+}
+
+impl fmt::Display for AlterColumnOperation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AlterColumnOperation::SetNotNull => f.write_str("SET NOT NULL"),
+            AlterColumnOperation::DropNotNull => f.write_str("DROP NOT NULL"),
+            AlterColumnOperation::SetDefault { value } => write!(f, "SET DEFAULT {}", value),
+            AlterColumnOperation::DropDefault => f.write_str("DROP DEFAULT"),
+            AlterColumnOperation::SetDataType { data_type } => {
+                write!(f, "SET DATA TYPE {}", data_type)
+            }
+        }
+    }
+}
+
+/// An option of a `CREATE SEQUENCE` or `ALTER SEQUENCE` statement
+#[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SequenceOption {
+    /// `AS <data_type>`
+    As { data_type: SQLType },
+    /// `INCREMENT [ BY ] <value>`
+    IncrementBy { value: ASTNode },
+    /// `MINVALUE <value>`, or `NO MINVALUE` if `value` is `None`
+    MinValue { value: Option<ASTNode> },
+    /// `MAXVALUE <value>`, or `NO MAXVALUE` if `value` is `None`
+    MaxValue { value: Option<ASTNode> },
+    /// `START [ WITH ] <value>`
+    StartWith { value: ASTNode },
+    /// `RESTART [ [ WITH ] <value> ]`
+    Restart { value: Option<ASTNode> },
+    /// `CACHE <value>`
+    Cache { value: ASTNode },
+    /// `CYCLE`
+    Cycle,
+    /// `NO CYCLE`
+    NoCycle,
+    /// `OWNED BY <table>.<column>`, or `OWNED BY NONE` if `column` is `None`
+    OwnedBy { column: Option<SQLObjectName> },
+}
+
+impl fmt::Display for SequenceOption {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SequenceOption::As { data_type } => write!(f, "AS {}", data_type),
+            SequenceOption::IncrementBy { value } => write!(f, "INCREMENT BY {}", value),
+            SequenceOption::MinValue { value: Some(value) } => write!(f, "MINVALUE {}", value),
+            SequenceOption::MinValue { value: None } => f.write_str("NO MINVALUE"),
+            SequenceOption::MaxValue { value: Some(value) } => write!(f, "MAXVALUE {}", value),
+            SequenceOption::MaxValue { value: None } => f.write_str("NO MAXVALUE"),
+            SequenceOption::StartWith { value } => write!(f, "START WITH {}", value),
+            SequenceOption::Restart { value: Some(value) } => write!(f, "RESTART WITH {}", value),
+            SequenceOption::Restart { value: None } => f.write_str("RESTART"),
+            SequenceOption::Cache { value } => write!(f, "CACHE {}", value),
+            SequenceOption::Cycle => f.write_str("CYCLE"),
+            SequenceOption::NoCycle => f.write_str("NO CYCLE"),
+            SequenceOption::OwnedBy {
+                column: Some(column),
+            } => write!(f, "OWNED BY {}", column),
+            SequenceOption::OwnedBy { column: None } => f.write_str("OWNED BY NONE"),
         }
     }
 }
@@ -23,6 +192,7 @@ impl ToString for AlterTableOperation {
 /// A table-level constraint, specified in a `CREATE TABLE` or an
 /// `ALTER TABLE ADD <constraint>` statement.
 #[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum TableConstraint {
     /// `[ CONSTRAINT <name> ] { PRIMARY KEY | UNIQUE } (<columns>)`
     Unique {
@@ -30,6 +200,7 @@ pub enum TableConstraint {
         columns: Vec<SQLIdent>,
         /// Whether this is a `PRIMARY KEY` or just a `UNIQUE` constraint
         is_primary: bool,
+        span: Span,
     },
     /// A referential integrity constraint (`[ CONSTRAINT <name> ] FOREIGN KEY (<columns>)
     /// REFERENCES <foreign_table> (<referred_columns>)`)
@@ -38,49 +209,437 @@ pub enum TableConstraint {
         columns: Vec<SQLIdent>,
         foreign_table: SQLObjectName,
         referred_columns: Vec<SQLIdent>,
+        span: Span,
     },
     /// `[ CONSTRAINT <name> ] CHECK (<expr>)`
     Check {
         name: Option<SQLIdent>,
         expr: Box<ASTNode>,
+        span: Span,
     },
 }
 
-impl ToString for TableConstraint {
-    fn to_string(&self) -> String {
-        fn format_constraint_name(name: &Option<SQLIdent>) -> String {
-            name.as_ref()
-                .map(|name| format!("CONSTRAINT {} ", name))
-                .unwrap_or_default()
-        }
+impl fmt::Display for TableConstraint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             TableConstraint::Unique {
                 name,
                 columns,
                 is_primary,
-            } => format!(
+                ..
+            } => write!(
+                f,
                 "{}{} ({})",
-                format_constraint_name(name),
+                display_constraint_name(name),
                 if *is_primary { "PRIMARY KEY" } else { "UNIQUE" },
-                columns.join(", ")
+                display_comma_separated(columns)
             ),
             TableConstraint::ForeignKey {
                 name,
                 columns,
                 foreign_table,
                 referred_columns,
-            } => format!(
+                ..
+            } => write!(
+                f,
                 "{}FOREIGN KEY ({}) REFERENCES {}({})",
-                format_constraint_name(name),
-                columns.join(", "),
-                foreign_table.to_string(),
-                referred_columns.join(", ")
+                display_constraint_name(name),
+                display_comma_separated(columns),
+                foreign_table,
+                display_comma_separated(referred_columns)
             ),
-            TableConstraint::Check { name, expr } => format!(
-                "{}CHECK ({})",
-                format_constraint_name(name),
-                expr.to_string()
+            TableConstraint::Check { name, expr, .. } => {
+                write!(f, "{}CHECK ({})", display_constraint_name(name), expr)
+            }
+        }
+    }
+}
+
+impl Spanned for TableConstraint {
+    fn span(&self) -> Span {
+        match self {
+            TableConstraint::Unique { span, .. }
+            | TableConstraint::ForeignKey { span, .. }
+            | TableConstraint::Check { span, .. } => *span,
+        }
+    }
+}
+
+/// A column option, optionally named: `[ CONSTRAINT <name> ] <option>`
+#[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ColumnOptionDef {
+    pub name: Option<SQLIdent>,
+    pub option: ColumnOption,
+    pub span: Span,
+}
+
+impl fmt::Display for ColumnOptionDef {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", display_constraint_name(&self.name), self.option)
+    }
+}
+
+impl Spanned for ColumnOptionDef {
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+/// A constraint or other option following the data type in a column
+/// definition (`SQLColumnDef`)
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ColumnOption {
+    /// `NULL`
+    Null,
+    /// `NOT NULL`
+    NotNull,
+    /// `DEFAULT <expr>`
+    Default(ASTNode),
+    /// `{ PRIMARY KEY | UNIQUE }`
+    Unique { is_primary: bool },
+    /// A referential integrity constraint (`REFERENCES <foreign_table> [ (<referred_columns>) ]
+    /// [ ON DELETE <action> ] [ ON UPDATE <action> ]`)
+    ForeignKey {
+        foreign_table: SQLObjectName,
+        referred_columns: Vec<SQLIdent>,
+        /// The `ON DELETE` and `ON UPDATE` actions, in the order written
+        actions: Vec<ForeignKeyAction>,
+    },
+    /// `CHECK (<expr>)`
+    Check(ASTNode),
+    /// `COLLATE <collation>`
+    Collate(SQLObjectName),
+    /// `GENERATED { ALWAYS | BY DEFAULT } AS IDENTITY`
+    GeneratedAsIdentity {
+        /// Whether this is `ALWAYS` rather than `BY DEFAULT`
+        always: bool,
+    },
+    /// SQLite's `AUTOINCREMENT`, which may only follow `PRIMARY KEY`
+    SqliteAutoincrement,
+    /// MySQL's `AUTO_INCREMENT`
+    MySqlAutoIncrement,
+}
+
+impl fmt::Display for ColumnOption {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ColumnOption::Null => f.write_str("NULL"),
+            ColumnOption::NotNull => f.write_str("NOT NULL"),
+            ColumnOption::Default(expr) => write!(f, "DEFAULT {}", expr),
+            ColumnOption::Unique { is_primary } => {
+                f.write_str(if *is_primary { "PRIMARY KEY" } else { "UNIQUE" })
+            }
+            ColumnOption::ForeignKey {
+                foreign_table,
+                referred_columns,
+                actions,
+            } => {
+                write!(f, "REFERENCES {}", foreign_table)?;
+                if !referred_columns.is_empty() {
+                    write!(f, "({})", display_comma_separated(referred_columns))?;
+                }
+                for action in actions {
+                    write!(f, " {}", action)?;
+                }
+                Ok(())
+            }
+            ColumnOption::Check(expr) => write!(f, "CHECK ({})", expr),
+            ColumnOption::Collate(collation) => write!(f, "COLLATE {}", collation),
+            ColumnOption::GeneratedAsIdentity { always } => write!(
+                f,
+                "GENERATED {} AS IDENTITY",
+                if *always { "ALWAYS" } else { "BY DEFAULT" }
+            ),
+            ColumnOption::SqliteAutoincrement => f.write_str("AUTOINCREMENT"),
+            ColumnOption::MySqlAutoIncrement => f.write_str("AUTO_INCREMENT"),
+        }
+    }
+}
+
+/// `ON DELETE <action>` or `ON UPDATE <action>` in a `REFERENCES` column option
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ForeignKeyAction {
+    OnDelete(ReferentialAction),
+    OnUpdate(ReferentialAction),
+}
+
+impl fmt::Display for ForeignKeyAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ForeignKeyAction::OnDelete(action) => write!(f, "ON DELETE {}", action),
+            ForeignKeyAction::OnUpdate(action) => write!(f, "ON UPDATE {}", action),
+        }
+    }
+}
+
+/// What to do with referencing rows when the referenced row is deleted or
+/// updated (`ON DELETE <action>`, `ON UPDATE <action>`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ReferentialAction {
+    Restrict,
+    Cascade,
+    SetNull,
+    NoAction,
+    SetDefault,
+}
+
+impl fmt::Display for ReferentialAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            ReferentialAction::Restrict => "RESTRICT",
+            ReferentialAction::Cascade => "CASCADE",
+            ReferentialAction::SetNull => "SET NULL",
+            ReferentialAction::NoAction => "NO ACTION",
+            ReferentialAction::SetDefault => "SET DEFAULT",
+        })
+    }
+}
+
+/// An argument of a `CREATE FUNCTION` or a parameter of a `CREATE PROCEDURE`
+/// statement: `[ <mode> ] [ <name> ] <data_type> [ = <default> ] [ OUTPUT ]`
+#[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FunctionArg {
+    pub mode: Option<ArgMode>,
+    pub name: Option<SQLIdent>,
+    pub data_type: SQLType,
+    /// The default value, after `=` or `DEFAULT`
+    pub default: Option<ASTNode>,
+    /// MSSQL's `OUTPUT` (or `OUT`), following the data type
+    pub output: bool,
+    pub span: Span,
+}
+
+impl fmt::Display for FunctionArg {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(mode) = self.mode {
+            write!(f, "{} ", mode)?;
+        }
+        if let Some(name) = &self.name {
+            write!(f, "{} ", name)?;
+        }
+        write!(f, "{}", self.data_type)?;
+        if let Some(default) = &self.default {
+            write!(f, " = {}", default)?;
+        }
+        if self.output {
+            f.write_str(" OUTPUT")?;
+        }
+        Ok(())
+    }
+}
+
+impl Spanned for FunctionArg {
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+/// The mode of a function argument (Postgres)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ArgMode {
+    In,
+    Out,
+    InOut,
+    Variadic,
+}
+
+impl fmt::Display for ArgMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            ArgMode::In => "IN",
+            ArgMode::Out => "OUT",
+            ArgMode::InOut => "INOUT",
+            ArgMode::Variadic => "VARIADIC",
+        })
+    }
+}
+
+/// The return type of a function (after `RETURNS`)
+#[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum FunctionReturns {
+    /// `RETURNS <data_type>`
+    Type(SQLType),
+    /// `RETURNS SETOF <data_type>` (Postgres)
+    SetOf(SQLType),
+    /// `RETURNS TABLE [ (<columns>) ]`, the columns being omitted by MSSQL's
+    /// inline table-valued functions
+    Table(Vec<SQLColumnDef>),
+}
+
+impl fmt::Display for FunctionReturns {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FunctionReturns::Type(data_type) => write!(f, "{}", data_type),
+            FunctionReturns::SetOf(data_type) => write!(f, "SETOF {}", data_type),
+            FunctionReturns::Table(columns) if columns.is_empty() => f.write_str("TABLE"),
+            FunctionReturns::Table(columns) => {
+                write!(f, "TABLE ({})", display_comma_separated(columns))
+            }
+        }
+    }
+}
+
+/// A characteristic of a function or procedure, following its signature
+#[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum FunctionAttribute {
+    /// `LANGUAGE <name>`
+    Language(SQLIdent),
+    /// `IMMUTABLE`
+    Immutable,
+    /// `STABLE`
+    Stable,
+    /// `VOLATILE`
+    Volatile,
+    /// `STRICT`
+    Strict,
+    /// `CALLED ON NULL INPUT`
+    CalledOnNullInput,
+    /// `RETURNS NULL ON NULL INPUT`
+    ReturnsNullOnNullInput,
+    /// `SECURITY { DEFINER | INVOKER }`
+    Security { definer: bool },
+    /// `PARALLEL { UNSAFE | RESTRICTED | SAFE }`
+    Parallel(FunctionParallel),
+    /// `COST <execution_cost>`
+    Cost(ASTNode),
+    /// `ROWS <result_rows>`
+    Rows(ASTNode),
+    /// MSSQL's `WITH <option>, ...`, e.g. `WITH SCHEMABINDING, RECOMPILE`
+    With(Vec<SQLIdent>),
+}
+
+impl fmt::Display for FunctionAttribute {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FunctionAttribute::Language(name) => write!(f, "LANGUAGE {}", name),
+            FunctionAttribute::Immutable => f.write_str("IMMUTABLE"),
+            FunctionAttribute::Stable => f.write_str("STABLE"),
+            FunctionAttribute::Volatile => f.write_str("VOLATILE"),
+            FunctionAttribute::Strict => f.write_str("STRICT"),
+            FunctionAttribute::CalledOnNullInput => f.write_str("CALLED ON NULL INPUT"),
+            FunctionAttribute::ReturnsNullOnNullInput => f.write_str("RETURNS NULL ON NULL INPUT"),
+            FunctionAttribute::Security { definer } => write!(
+                f,
+                "SECURITY {}",
+                if *definer { "DEFINER" } else { "INVOKER" }
             ),
+            FunctionAttribute::Parallel(parallel) => write!(f, "PARALLEL {}", parallel),
+            FunctionAttribute::Cost(cost) => write!(f, "COST {}", cost),
+            FunctionAttribute::Rows(rows) => write!(f, "ROWS {}", rows),
+            FunctionAttribute::With(options) => {
+                write!(f, "WITH {}", display_comma_separated(options))
+            }
+        }
+    }
+}
+
+/// Whether a function is safe to run in parallel mode (`PARALLEL <...>`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum FunctionParallel {
+    Unsafe,
+    Restricted,
+    Safe,
+}
+
+impl fmt::Display for FunctionParallel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            FunctionParallel::Unsafe => "UNSAFE",
+            FunctionParallel::Restricted => "RESTRICTED",
+            FunctionParallel::Safe => "SAFE",
+        })
+    }
+}
+
+/// The body of a function or procedure (after `AS`), kept as written
+#[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum FunctionBody {
+    /// `AS '<definition>'`
+    SingleQuoted(String),
+    /// `AS $tag$<definition>$tag$` (Postgres)
+    DollarQuoted(DollarQuotedString),
+    /// The source text of MSSQL's `AS BEGIN ... END`, or of a single
+    /// statement following `AS`
+    Raw(String),
+}
+
+impl fmt::Display for FunctionBody {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FunctionBody::SingleQuoted(s) => write!(f, "'{}'", s.replace('\'', "''")),
+            FunctionBody::DollarQuoted(s) => write!(f, "{}", s),
+            FunctionBody::Raw(s) => f.write_str(s),
+        }
+    }
+}
+
+/// The persistence of a table created with `CREATE { TEMP | TEMPORARY | UNLOGGED } TABLE`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TablePersistence {
+    Temp,
+    Temporary,
+    Unlogged,
+}
+
+impl fmt::Display for TablePersistence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            TablePersistence::Temp => "TEMP",
+            TablePersistence::Temporary => "TEMPORARY",
+            TablePersistence::Unlogged => "UNLOGGED",
+        })
+    }
+}
+
+/// A MySQL table option following the column list of a `CREATE TABLE`,
+/// e.g. `ENGINE=InnoDB` or `DEFAULT CHARSET=utf8mb4`
+#[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SQLTableOption {
+    /// Whether the option was introduced by the (meaningless) `DEFAULT`
+    pub default: bool,
+    pub name: SQLIdent,
+    /// An identifier (`InnoDB`) or a literal (`10`, `'comment'`)
+    pub value: ASTNode,
+    pub span: Span,
+}
+
+impl fmt::Display for SQLTableOption {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.default {
+            f.write_str("DEFAULT ")?;
+        }
+        write!(f, "{}={}", self.name, self.value)
+    }
+}
+
+impl Spanned for SQLTableOption {
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+fn display_constraint_name(name: &Option<SQLIdent>) -> impl fmt::Display + '_ {
+    struct ConstraintName<'a>(&'a Option<SQLIdent>);
+    impl<'a> fmt::Display for ConstraintName<'a> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            if let Some(name) = self.0 {
+                write!(f, "CONSTRAINT {} ", name)?;
+            }
+            Ok(())
         }
     }
+    ConstraintName(name)
 }
@@ -13,36 +13,111 @@
 // limitations under the License.
 
 //! SQL Abstract Syntax Tree (AST) types
+//!
+//! # Serialization
+//!
+//! With the `serde` cargo feature enabled, all AST types (and the `Span`s
+//! they carry) implement `serde::Serialize` and `serde::Deserialize`. The
+//! representation is serde's default, which in JSON means:
+//!
+//! - structs and struct-like enum variants are objects keyed by field name,
+//!   e.g. `SQLAssignment` is `{"id": "a", "value": ..., "span": ...}`;
+//! - enums are externally tagged by the variant name: unit variants are
+//!   plain strings (`"Plus"`), newtype variants wrap their content
+//!   (`{"Long": 1}`) and tuple variants wrap an array
+//!   (`{"SQLIdentifier": ["a", <span>]}`);
+//! - `SQLObjectName` is an array of identifiers, `Value::Double` a number;
+//! - a `Span` is `{"start": <location>, "end": <location>}`, where a
+//!   `Location` is `{"offset": 0, "line": 1, "column": 1}`.
+//!
+//! This shape follows the Rust definitions, so renaming a type's variant or
+//! field is a breaking change for serialized ASTs.
 
 mod ddl;
+mod params;
 mod query;
 mod sql_operator;
 mod sqltype;
 mod value;
+mod visitor;
 
-use std::ops::Deref;
+use std::fmt;
 
-pub use self::ddl::{AlterTableOperation, TableConstraint};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+pub use self::ddl::{
+    AlterColumnOperation, AlterTableOperation, ArgMode, ColumnOption, ColumnOptionDef,
+    ForeignKeyAction, FunctionArg, FunctionAttribute, FunctionBody, FunctionParallel,
+    FunctionReturns, ReferentialAction, SQLTableOption, SequenceOption, TableConstraint,
+    TablePersistence,
+};
+pub use self::params::{BindError, BindParams};
 pub use self::query::{
-    Cte, Fetch, Join, JoinConstraint, JoinOperator, SQLOrderByExpr, SQLQuery, SQLSelect,
+    Cte, Fetch, Join, JoinConstraint, JoinOperator, Offset, SQLOrderByExpr, SQLQuery, SQLSelect,
     SQLSelectItem, SQLSetExpr, SQLSetOperator, SQLValues, TableAlias, TableFactor,
 };
 pub use self::sqltype::SQLType;
 pub use self::value::Value;
+pub use self::visitor::{Visit, VisitMut, Visitor, VisitorMut};
 
 pub use self::sql_operator::SQLOperator;
 
-/// Like `vec.join(", ")`, but for any types implementing ToString.
-fn comma_separated_string<I>(iter: I) -> String
+pub use crate::sqltokenizer::{DollarQuotedString, Location, Span};
+
+/// An AST node that knows which part of the source text it was parsed from.
+///
+/// Nodes constructed by hand (rather than by the parser) report an empty
+/// span, see `Span::empty()`.
+///
+/// Identifiers (`SQLIdent`), names (`SQLObjectName`) and literal values
+/// (`Value`) deliberately don't carry spans of their own, as they are also
+/// built by hand and compared as plain data, e.g. when binding parameters.
+/// Use the span of the enclosing node instead: `ASTNode::SQLIdentifier`,
+/// `ASTNode::SQLCompoundIdentifier` and `ASTNode::SQLValue` cover them in
+/// expressions, and definitions such as `SQLColumnDef` or the statement
+/// itself cover them elsewhere.
+pub trait Spanned {
+    fn span(&self) -> Span;
+}
+
+/// Displays the elements of a slice separated by `sep`, like
+/// `slice.join(sep)` but without allocating intermediate strings.
+struct DisplaySeparated<'a, T>
+where
+    T: fmt::Display,
+{
+    slice: &'a [T],
+    sep: &'static str,
+}
+
+impl<'a, T> fmt::Display for DisplaySeparated<'a, T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut delim = "";
+        for t in self.slice {
+            f.write_str(delim)?;
+            delim = self.sep;
+            write!(f, "{}", t)?;
+        }
+        Ok(())
+    }
+}
+
+fn display_separated<'a, T>(slice: &'a [T], sep: &'static str) -> DisplaySeparated<'a, T>
+where
+    T: fmt::Display,
+{
+    DisplaySeparated { slice, sep }
+}
+
+fn display_comma_separated<T>(slice: &[T]) -> DisplaySeparated<'_, T>
 where
-    I: IntoIterator,
-    I::Item: Deref,
-    <I::Item as Deref>::Target: ToString,
+    T: fmt::Display,
 {
-    iter.into_iter()
-        .map(|t| t.deref().to_string())
-        .collect::<Vec<String>>()
-        .join(", ")
+    DisplaySeparated { slice, sep: ", " }
 }
 
 /// Identifier name, in the originally quoted form (e.g. `"id"`)
@@ -54,33 +129,36 @@ pub type SQLIdent = String;
 /// (e.g. boolean vs string), so the caller must handle expressions of
 /// inappropriate type, like `WHERE 1` or `SELECT 1=1`, as necessary.
 #[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ASTNode {
     /// Identifier e.g. table name or column name
-    SQLIdentifier(SQLIdent),
+    SQLIdentifier(SQLIdent, Span),
     /// Unqualified wildcard (`*`). SQL allows this in limited contexts (such as right
     /// after `SELECT` or as part of an aggregate function, e.g. `COUNT(*)`, but we
     /// currently accept it in contexts where it doesn't make sense, such as `* + *`
-    SQLWildcard,
+    SQLWildcard(Span),
     /// Qualified wildcard, e.g. `alias.*` or `schema.table.*`.
     /// (Same caveats apply to SQLQualifiedWildcard as to SQLWildcard.)
-    SQLQualifiedWildcard(Vec<SQLIdent>),
+    SQLQualifiedWildcard(Vec<SQLIdent>, Span),
     /// Multi-part identifier, e.g. `table_alias.column` or `schema.table.col`
-    SQLCompoundIdentifier(Vec<SQLIdent>),
+    SQLCompoundIdentifier(Vec<SQLIdent>, Span),
     /// `IS NULL` expression
-    SQLIsNull(Box<ASTNode>),
+    SQLIsNull(Box<ASTNode>, Span),
     /// `IS NOT NULL` expression
-    SQLIsNotNull(Box<ASTNode>),
+    SQLIsNotNull(Box<ASTNode>, Span),
     /// `[ NOT ] IN (val1, val2, ...)`
     SQLInList {
         expr: Box<ASTNode>,
         list: Vec<ASTNode>,
         negated: bool,
+        span: Span,
     },
     /// `[ NOT ] IN (SELECT ...)`
     SQLInSubquery {
         expr: Box<ASTNode>,
         subquery: Box<SQLQuery>,
         negated: bool,
+        span: Span,
     },
     /// `<expr> [ NOT ] BETWEEN <low> AND <high>`
     SQLBetween {
@@ -88,36 +166,44 @@ pub enum ASTNode {
         negated: bool,
         low: Box<ASTNode>,
         high: Box<ASTNode>,
+        span: Span,
     },
     /// Binary expression e.g. `1 + 1` or `foo > bar`
     SQLBinaryExpr {
         left: Box<ASTNode>,
         op: SQLOperator,
         right: Box<ASTNode>,
+        span: Span,
     },
     /// CAST an expression to a different data type e.g. `CAST(foo AS VARCHAR(123))`
     SQLCast {
         expr: Box<ASTNode>,
         data_type: SQLType,
+        span: Span,
     },
     SQLExtract {
         field: SQLDateTimeField,
         expr: Box<ASTNode>,
+        span: Span,
     },
     /// `expr COLLATE collation`
     SQLCollate {
         expr: Box<ASTNode>,
         collation: SQLObjectName,
+        span: Span,
     },
     /// Nested expression e.g. `(foo > bar)` or `(1)`
-    SQLNested(Box<ASTNode>),
+    SQLNested(Box<ASTNode>, Span),
     /// Unary expression
     SQLUnary {
         operator: SQLOperator,
         expr: Box<ASTNode>,
+        span: Span,
     },
     /// SQLValue
-    SQLValue(Value),
+    SQLValue(Value, Span),
+    /// A query parameter, e.g. `?` or `:name`, including its prefix
+    SQLPlaceholder(String, Span),
     /// Scalar function call e.g. `LEFT(foo, 5)`
     SQLFunction(SQLFunction),
     /// CASE [<operand>] WHEN <condition> THEN <result> ... [ELSE <result>] END
@@ -129,176 +215,217 @@ pub enum ASTNode {
         conditions: Vec<ASTNode>,
         results: Vec<ASTNode>,
         else_result: Option<Box<ASTNode>>,
+        span: Span,
     },
     /// An exists expression `EXISTS(SELECT ...)`, used in expressions like
     /// `WHERE EXISTS (SELECT ...)`.
-    SQLExists(Box<SQLQuery>),
+    SQLExists(Box<SQLQuery>, Span),
     /// A parenthesized subquery `(SELECT ...)`, used in expression like
     /// `SELECT (subquery) AS x` or `WHERE (subquery) = x`
-    SQLSubquery(Box<SQLQuery>),
+    SQLSubquery(Box<SQLQuery>, Span),
 }
 
-impl ToString for ASTNode {
-    fn to_string(&self) -> String {
+impl fmt::Display for ASTNode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            ASTNode::SQLIdentifier(s) => s.to_string(),
-            ASTNode::SQLWildcard => "*".to_string(),
-            ASTNode::SQLQualifiedWildcard(q) => q.join(".") + ".*",
-            ASTNode::SQLCompoundIdentifier(s) => s.join("."),
-            ASTNode::SQLIsNull(ast) => format!("{} IS NULL", ast.as_ref().to_string()),
-            ASTNode::SQLIsNotNull(ast) => format!("{} IS NOT NULL", ast.as_ref().to_string()),
+            ASTNode::SQLIdentifier(s, _) => write!(f, "{}", s),
+            ASTNode::SQLWildcard(_) => f.write_str("*"),
+            ASTNode::SQLQualifiedWildcard(q, _) => {
+                write!(f, "{}.*", display_separated(q, "."))
+            }
+            ASTNode::SQLCompoundIdentifier(s, _) => write!(f, "{}", display_separated(s, ".")),
+            ASTNode::SQLIsNull(ast, _) => write!(f, "{} IS NULL", ast),
+            ASTNode::SQLIsNotNull(ast, _) => write!(f, "{} IS NOT NULL", ast),
             ASTNode::SQLInList {
                 expr,
                 list,
                 negated,
-            } => format!(
+                ..
+            } => write!(
+                f,
                 "{} {}IN ({})",
-                expr.as_ref().to_string(),
+                expr,
                 if *negated { "NOT " } else { "" },
-                comma_separated_string(list)
+                display_comma_separated(list)
             ),
             ASTNode::SQLInSubquery {
                 expr,
                 subquery,
                 negated,
-            } => format!(
+                ..
+            } => write!(
+                f,
                 "{} {}IN ({})",
-                expr.as_ref().to_string(),
+                expr,
                 if *negated { "NOT " } else { "" },
-                subquery.to_string()
+                subquery
             ),
             ASTNode::SQLBetween {
                 expr,
                 negated,
                 low,
                 high,
-            } => format!(
+                ..
+            } => write!(
+                f,
                 "{} {}BETWEEN {} AND {}",
-                expr.to_string(),
+                expr,
                 if *negated { "NOT " } else { "" },
-                low.to_string(),
-                high.to_string()
-            ),
-            ASTNode::SQLBinaryExpr { left, op, right } => format!(
-                "{} {} {}",
-                left.as_ref().to_string(),
-                op.to_string(),
-                right.as_ref().to_string()
-            ),
-            ASTNode::SQLCast { expr, data_type } => format!(
-                "CAST({} AS {})",
-                expr.as_ref().to_string(),
-                data_type.to_string()
-            ),
-            ASTNode::SQLExtract { field, expr } => {
-                format!("EXTRACT({} FROM {})", field.to_string(), expr.to_string())
-            }
-            ASTNode::SQLCollate { expr, collation } => format!(
-                "{} COLLATE {}",
-                expr.as_ref().to_string(),
-                collation.to_string()
+                low,
+                high
             ),
-            ASTNode::SQLNested(ast) => format!("({})", ast.as_ref().to_string()),
-            ASTNode::SQLUnary { operator, expr } => {
-                format!("{} {}", operator.to_string(), expr.as_ref().to_string())
+            ASTNode::SQLBinaryExpr {
+                left, op, right, ..
+            } => write!(f, "{} {} {}", left, op, right),
+            ASTNode::SQLCast {
+                expr, data_type, ..
+            } => write!(f, "CAST({} AS {})", expr, data_type),
+            ASTNode::SQLExtract { field, expr, .. } => {
+                write!(f, "EXTRACT({} FROM {})", field, expr)
             }
-            ASTNode::SQLValue(v) => v.to_string(),
-            ASTNode::SQLFunction(f) => f.to_string(),
+            ASTNode::SQLCollate {
+                expr, collation, ..
+            } => write!(f, "{} COLLATE {}", expr, collation),
+            ASTNode::SQLNested(ast, _) => write!(f, "({})", ast),
+            ASTNode::SQLUnary { operator, expr, .. } => write!(f, "{} {}", operator, expr),
+            ASTNode::SQLValue(v, _) => write!(f, "{}", v),
+            ASTNode::SQLPlaceholder(s, _) => f.write_str(s),
+            ASTNode::SQLFunction(fun) => write!(f, "{}", fun),
             ASTNode::SQLCase {
                 operand,
                 conditions,
                 results,
                 else_result,
+                ..
             } => {
-                let mut s = "CASE".to_string();
+                f.write_str("CASE")?;
                 if let Some(operand) = operand {
-                    s += &format!(" {}", operand.to_string());
-                }
-                s += &conditions
-                    .iter()
-                    .zip(results)
-                    .map(|(c, r)| format!(" WHEN {} THEN {}", c.to_string(), r.to_string()))
-                    .collect::<Vec<String>>()
-                    .join("");
+                    write!(f, " {}", operand)?;
+                }
+                for (c, r) in conditions.iter().zip(results) {
+                    write!(f, " WHEN {} THEN {}", c, r)?;
+                }
                 if let Some(else_result) = else_result {
-                    s += &format!(" ELSE {}", else_result.to_string())
+                    write!(f, " ELSE {}", else_result)?;
                 }
-                s + " END"
+                f.write_str(" END")
             }
-            ASTNode::SQLExists(s) => format!("EXISTS ({})", s.to_string()),
-            ASTNode::SQLSubquery(s) => format!("({})", s.to_string()),
+            ASTNode::SQLExists(s, _) => write!(f, "EXISTS ({})", s),
+            ASTNode::SQLSubquery(s, _) => write!(f, "({})", s),
+        }
+    }
+}
+
+impl Spanned for ASTNode {
+    fn span(&self) -> Span {
+        match self {
+            ASTNode::SQLIdentifier(_, span)
+            | ASTNode::SQLWildcard(span)
+            | ASTNode::SQLQualifiedWildcard(_, span)
+            | ASTNode::SQLCompoundIdentifier(_, span)
+            | ASTNode::SQLIsNull(_, span)
+            | ASTNode::SQLIsNotNull(_, span)
+            | ASTNode::SQLInList { span, .. }
+            | ASTNode::SQLInSubquery { span, .. }
+            | ASTNode::SQLBetween { span, .. }
+            | ASTNode::SQLBinaryExpr { span, .. }
+            | ASTNode::SQLCast { span, .. }
+            | ASTNode::SQLExtract { span, .. }
+            | ASTNode::SQLCollate { span, .. }
+            | ASTNode::SQLNested(_, span)
+            | ASTNode::SQLUnary { span, .. }
+            | ASTNode::SQLValue(_, span)
+            | ASTNode::SQLPlaceholder(_, span)
+            | ASTNode::SQLCase { span, .. }
+            | ASTNode::SQLExists(_, span)
+            | ASTNode::SQLSubquery(_, span) => *span,
+            ASTNode::SQLFunction(f) => f.span(),
         }
     }
 }
 
 /// A window specification (i.e. `OVER (PARTITION BY .. ORDER BY .. etc.)`)
 #[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SQLWindowSpec {
     pub partition_by: Vec<ASTNode>,
     pub order_by: Vec<SQLOrderByExpr>,
     pub window_frame: Option<SQLWindowFrame>,
+    pub span: Span,
 }
 
-impl ToString for SQLWindowSpec {
-    fn to_string(&self) -> String {
-        let mut clauses = vec![];
+impl fmt::Display for SQLWindowSpec {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut delim = "";
         if !self.partition_by.is_empty() {
-            clauses.push(format!(
+            delim = " ";
+            write!(
+                f,
                 "PARTITION BY {}",
-                comma_separated_string(&self.partition_by)
-            ))
-        };
+                display_comma_separated(&self.partition_by)
+            )?;
+        }
         if !self.order_by.is_empty() {
-            clauses.push(format!(
-                "ORDER BY {}",
-                comma_separated_string(&self.order_by)
-            ))
-        };
+            f.write_str(delim)?;
+            delim = " ";
+            write!(f, "ORDER BY {}", display_comma_separated(&self.order_by))?;
+        }
         if let Some(window_frame) = &self.window_frame {
+            f.write_str(delim)?;
             if let Some(end_bound) = &window_frame.end_bound {
-                clauses.push(format!(
+                write!(
+                    f,
                     "{} BETWEEN {} AND {}",
-                    window_frame.units.to_string(),
-                    window_frame.start_bound.to_string(),
-                    end_bound.to_string()
-                ));
+                    window_frame.units, window_frame.start_bound, end_bound
+                )?;
             } else {
-                clauses.push(format!(
-                    "{} {}",
-                    window_frame.units.to_string(),
-                    window_frame.start_bound.to_string()
-                ));
+                write!(f, "{} {}", window_frame.units, window_frame.start_bound)?;
             }
         }
-        clauses.join(" ")
+        Ok(())
+    }
+}
+
+impl Spanned for SQLWindowSpec {
+    fn span(&self) -> Span {
+        self.span
     }
 }
 
 /// Specifies the data processed by a window function, e.g.
 /// `RANGE UNBOUNDED PRECEDING` or `ROWS BETWEEN 5 PRECEDING AND CURRENT ROW`.
 #[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SQLWindowFrame {
     pub units: SQLWindowFrameUnits,
     pub start_bound: SQLWindowFrameBound,
     /// The right bound of the `BETWEEN .. AND` clause.
     pub end_bound: Option<SQLWindowFrameBound>,
     // TBD: EXCLUDE
+    pub span: Span,
+}
+
+impl Spanned for SQLWindowFrame {
+    fn span(&self) -> Span {
+        self.span
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum SQLWindowFrameUnits {
     Rows,
     Range,
     Groups,
 }
 
-impl ToString for SQLWindowFrameUnits {
-    fn to_string(&self) -> String {
-        match self {
-            SQLWindowFrameUnits::Rows => "ROWS".to_string(),
-            SQLWindowFrameUnits::Range => "RANGE".to_string(),
-            SQLWindowFrameUnits::Groups => "GROUPS".to_string(),
-        }
+impl fmt::Display for SQLWindowFrameUnits {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            SQLWindowFrameUnits::Rows => "ROWS",
+            SQLWindowFrameUnits::Range => "RANGE",
+            SQLWindowFrameUnits::Groups => "GROUPS",
+        })
     }
 }
 
@@ -310,15 +437,20 @@ impl FromStr for SQLWindowFrameUnits {
             "ROWS" => Ok(SQLWindowFrameUnits::Rows),
             "RANGE" => Ok(SQLWindowFrameUnits::Range),
             "GROUPS" => Ok(SQLWindowFrameUnits::Groups),
-            _ => Err(ParserError::ParserError(format!(
-                "Expected ROWS, RANGE, or GROUPS, found: {}",
-                s
-            ))),
+            _ => Err(ParserError {
+                expected: vec!["ROWS".into(), "RANGE".into(), "GROUPS".into()],
+                ..ParserError::new(
+                    ParserErrorKind::UnexpectedToken,
+                    format!("Expected ROWS, RANGE, or GROUPS, found: {}", s),
+                    Span::empty(),
+                )
+            }),
         }
     }
 }
 
 #[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum SQLWindowFrameBound {
     /// "CURRENT ROW"
     CurrentRow,
@@ -329,14 +461,14 @@ pub enum SQLWindowFrameBound {
     Following(Option<u64>),
 }
 
-impl ToString for SQLWindowFrameBound {
-    fn to_string(&self) -> String {
+impl fmt::Display for SQLWindowFrameBound {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            SQLWindowFrameBound::CurrentRow => "CURRENT ROW".to_string(),
-            SQLWindowFrameBound::Preceding(None) => "UNBOUNDED PRECEDING".to_string(),
-            SQLWindowFrameBound::Following(None) => "UNBOUNDED FOLLOWING".to_string(),
-            SQLWindowFrameBound::Preceding(Some(n)) => format!("{} PRECEDING", n),
-            SQLWindowFrameBound::Following(Some(n)) => format!("{} FOLLOWING", n),
+            SQLWindowFrameBound::CurrentRow => f.write_str("CURRENT ROW"),
+            SQLWindowFrameBound::Preceding(None) => f.write_str("UNBOUNDED PRECEDING"),
+            SQLWindowFrameBound::Following(None) => f.write_str("UNBOUNDED FOLLOWING"),
+            SQLWindowFrameBound::Preceding(Some(n)) => write!(f, "{} PRECEDING", n),
+            SQLWindowFrameBound::Following(Some(n)) => write!(f, "{} FOLLOWING", n),
         }
     }
 }
@@ -344,17 +476,27 @@ impl ToString for SQLWindowFrameBound {
 /// A top-level statement (SELECT, INSERT, CREATE, etc.)
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum SQLStatement {
     /// SELECT
     SQLQuery(Box<SQLQuery>),
     /// INSERT
     SQLInsert {
+        /// Whether this is MySQL's `REPLACE INTO` rather than `INSERT INTO`
+        replace_into: bool,
+        /// SQLite's `INSERT OR <conflict resolution> INTO`
+        or: Option<SQLiteOnConflict>,
         /// TABLE
         table_name: SQLObjectName,
         /// COLUMNS
         columns: Vec<SQLIdent>,
         /// A SQL query that specifies what to insert
         source: Box<SQLQuery>,
+        /// What to do when a row being inserted conflicts with an existing one
+        on: Option<OnInsert>,
+        /// PostgreSQL's `RETURNING <select items>`
+        returning: Vec<SQLSelectItem>,
+        span: Span,
     },
     SQLCopy {
         /// TABLE
@@ -363,49 +505,158 @@ pub enum SQLStatement {
         columns: Vec<SQLIdent>,
         /// VALUES a vector of values to be copied
         values: Vec<Option<String>>,
+        span: Span,
     },
     /// UPDATE
     SQLUpdate {
         /// TABLE
         table_name: SQLObjectName,
+        /// The alias of the table being updated
+        alias: Option<SQLIdent>,
         /// Column assignments
         assignments: Vec<SQLAssignment>,
+        /// The first table of the `FROM` clause (Postgres, MSSQL)
+        from: Option<TableFactor>,
+        /// The rest of the `FROM` clause
+        joins: Vec<Join>,
         /// WHERE
         selection: Option<ASTNode>,
+        /// RETURNING
+        returning: Vec<SQLSelectItem>,
+        span: Span,
     },
     /// DELETE
     SQLDelete {
         /// FROM
         table_name: SQLObjectName,
+        /// The alias of the table being deleted from
+        alias: Option<SQLIdent>,
+        /// The first table of Postgres's `USING` clause
+        using: Option<TableFactor>,
+        /// The rest of the `USING` clause
+        joins: Vec<Join>,
         /// WHERE
         selection: Option<ASTNode>,
+        /// RETURNING
+        returning: Vec<SQLSelectItem>,
+        span: Span,
+    },
+    /// MERGE
+    SQLMerge {
+        /// Whether the optional `INTO` keyword was present
+        into: bool,
+        /// The target table
+        table: TableFactor,
+        /// The table or subquery to merge into the target (after `USING`)
+        source: TableFactor,
+        /// The condition matching source rows to target rows
+        on: Box<ASTNode>,
+        /// The `WHEN ... THEN ...` clauses, in order
+        clauses: Vec<MergeClause>,
+        span: Span,
     },
     /// CREATE VIEW
     SQLCreateView {
+        /// `OR REPLACE`
+        or_replace: bool,
         /// View name
         name: SQLObjectName,
         columns: Vec<SQLIdent>,
         query: Box<SQLQuery>,
         materialized: bool,
         with_options: Vec<SQLOption>,
+        span: Span,
     },
     /// CREATE TABLE
     SQLCreateTable {
+        /// `TEMP`, `TEMPORARY` or `UNLOGGED`
+        persistence: Option<TablePersistence>,
+        if_not_exists: bool,
         /// Table name
         name: SQLObjectName,
         /// Optional schema
         columns: Vec<SQLColumnDef>,
         constraints: Vec<TableConstraint>,
         with_options: Vec<SQLOption>,
+        /// MySQL table options, e.g. `ENGINE=InnoDB`
+        table_options: Vec<SQLTableOption>,
+        /// SQLite's `WITHOUT ROWID`
+        without_rowid: bool,
         external: bool,
         file_format: Option<FileFormat>,
         location: Option<String>,
+        /// The query of a `CREATE TABLE ... AS <query>`
+        query: Option<Box<SQLQuery>>,
+        span: Span,
+    },
+    /// CREATE INDEX
+    SQLCreateIndex {
+        /// Index name
+        name: SQLObjectName,
+        /// The table the index is created on (after `ON`)
+        table_name: SQLObjectName,
+        unique: bool,
+        /// Postgres's `CONCURRENTLY`
+        concurrently: bool,
+        if_not_exists: bool,
+        /// The index method, e.g. `btree` or `gin` (after `USING`)
+        using: Option<SQLIdent>,
+        /// The indexed columns or expressions, with their sort order
+        columns: Vec<SQLOrderByExpr>,
+        /// The non-key columns of an `INCLUDE (...)` clause
+        include: Vec<SQLIdent>,
+        /// The `WHERE` clause of a partial index
+        predicate: Option<ASTNode>,
+        span: Span,
+    },
+    /// CREATE SCHEMA
+    SQLCreateSchema {
+        schema_name: SQLObjectName,
+        if_not_exists: bool,
+        span: Span,
+    },
+    /// CREATE DATABASE
+    SQLCreateDatabase {
+        db_name: SQLObjectName,
+        if_not_exists: bool,
+        span: Span,
+    },
+    /// CREATE SEQUENCE
+    SQLCreateSequence {
+        name: SQLObjectName,
+        if_not_exists: bool,
+        /// The sequence options, in the order written
+        options: Vec<SequenceOption>,
+        span: Span,
+    },
+    /// CREATE FUNCTION / CREATE PROCEDURE
+    SQLCreateFunction {
+        /// `OR REPLACE`
+        or_replace: bool,
+        /// Whether this is a `PROCEDURE` rather than a `FUNCTION`
+        procedure: bool,
+        name: SQLObjectName,
+        /// The arguments, `None` if there was no (parenthesized) argument list
+        args: Option<Vec<FunctionArg>>,
+        returns: Option<FunctionReturns>,
+        attributes: Vec<FunctionAttribute>,
+        body: FunctionBody,
+        span: Span,
+    },
+    /// ALTER SEQUENCE
+    SQLAlterSequence {
+        name: SQLObjectName,
+        if_exists: bool,
+        options: Vec<SequenceOption>,
+        span: Span,
     },
     /// ALTER TABLE
     SQLAlterTable {
         /// Table name
         name: SQLObjectName,
-        operation: AlterTableOperation,
+        /// The comma-separated operations, in order
+        operations: Vec<AlterTableOperation>,
+        span: Span,
     },
     /// DROP TABLE
     SQLDrop {
@@ -413,234 +664,898 @@ pub enum SQLStatement {
         if_exists: bool,
         names: Vec<SQLObjectName>,
         cascade: bool,
+        span: Span,
+    },
+    /// `{ BEGIN [ TRANSACTION | WORK ] | START TRANSACTION } [ <mode>, ... ]`
+    SQLStartTransaction {
+        /// Whether the statement was spelled `BEGIN` rather than `START TRANSACTION`
+        begin: bool,
+        modes: Vec<TransactionMode>,
+        span: Span,
+    },
+    /// `SET TRANSACTION <mode>, ...`
+    SQLSetTransaction {
+        modes: Vec<TransactionMode>,
+        span: Span,
+    },
+    /// `COMMIT [ TRANSACTION | WORK ] [ AND [ NO ] CHAIN ]`
+    SQLCommit {
+        /// Whether a new transaction is started immediately (`AND CHAIN`)
+        chain: bool,
+        span: Span,
+    },
+    /// `ROLLBACK [ TRANSACTION | WORK ] [ AND [ NO ] CHAIN ] [ TO [ SAVEPOINT ] <name> ]`
+    SQLRollback {
+        /// Whether a new transaction is started immediately (`AND CHAIN`)
+        chain: bool,
+        /// The savepoint to roll back to, instead of aborting the transaction
+        savepoint: Option<SQLIdent>,
+        span: Span,
+    },
+    /// `SAVEPOINT <name>`
+    SQLSavepoint { name: SQLIdent, span: Span },
+    /// `RELEASE [ SAVEPOINT ] <name>`
+    SQLReleaseSavepoint { name: SQLIdent, span: Span },
+    /// SQLite's `PRAGMA <name> [ = <value> | (<value>) ]`
+    SQLPragma {
+        name: SQLObjectName,
+        value: Option<ASTNode>,
+        /// Whether the value is given as `= <value>` rather than `(<value>)`
+        is_eq: bool,
+        span: Span,
+    },
+    /// SQLite's `ATTACH [ DATABASE ] <file name> AS <schema name>`
+    SQLAttachDatabase {
+        /// Whether the optional `DATABASE` keyword was present
+        database: bool,
+        database_file_name: ASTNode,
+        schema_name: SQLIdent,
+        span: Span,
     },
-    SQLTransaction(Vec<Box<SQLStatement>>),
 }
 
-impl ToString for SQLStatement {
-    fn to_string(&self) -> String {
+impl fmt::Display for SQLStatement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            SQLStatement::SQLQuery(s) => s.to_string(),
+            SQLStatement::SQLQuery(s) => write!(f, "{}", s),
             SQLStatement::SQLInsert {
+                replace_into,
+                or,
                 table_name,
                 columns,
                 source,
+                on,
+                returning,
+                ..
             } => {
-                let mut s = format!("INSERT INTO {} ", table_name.to_string());
+                f.write_str(if *replace_into { "REPLACE" } else { "INSERT" })?;
+                if let Some(or) = or {
+                    write!(f, " OR {}", or)?;
+                }
+                write!(f, " INTO {} ", table_name)?;
                 if !columns.is_empty() {
-                    s += &format!("({}) ", columns.join(", "));
+                    write!(f, "({}) ", display_comma_separated(columns))?;
+                }
+                write!(f, "{}", source)?;
+                if let Some(on) = on {
+                    write!(f, " {}", on)?;
                 }
-                s += &source.to_string();
-                s
+                display_returning(f, returning)
             }
             SQLStatement::SQLCopy {
                 table_name,
                 columns,
                 values,
+                ..
             } => {
-                let mut s = format!("COPY {}", table_name.to_string());
+                write!(f, "COPY {}", table_name)?;
                 if !columns.is_empty() {
-                    s += &format!(" ({})", comma_separated_string(columns));
+                    write!(f, " ({})", display_comma_separated(columns))?;
                 }
-                s += " FROM stdin; ";
+                f.write_str(" FROM stdin; ")?;
                 if !values.is_empty() {
-                    s += &format!(
-                        "\n{}",
-                        values
-                            .iter()
-                            .map(|v| v.clone().unwrap_or_else(|| "\\N".to_string()))
-                            .collect::<Vec<String>>()
-                            .join("\t")
-                    );
-                }
-                s += "\n\\.";
-                s
+                    f.write_str("\n")?;
+                    let mut delim = "";
+                    for v in values {
+                        f.write_str(delim)?;
+                        delim = "\t";
+                        f.write_str(v.as_ref().map_or("\\N", String::as_str))?;
+                    }
+                }
+                f.write_str("\n\\.")
             }
             SQLStatement::SQLUpdate {
                 table_name,
+                alias,
                 assignments,
+                from,
+                joins,
                 selection,
+                returning,
+                ..
             } => {
-                let mut s = format!("UPDATE {}", table_name.to_string());
+                write!(f, "UPDATE {}", table_name)?;
+                if let Some(alias) = alias {
+                    write!(f, " AS {}", alias)?;
+                }
                 if !assignments.is_empty() {
-                    s += " SET ";
-                    s += &comma_separated_string(assignments);
+                    write!(f, " SET {}", display_comma_separated(assignments))?;
+                }
+                if let Some(from) = from {
+                    write!(f, " FROM {}", from)?;
+                }
+                for join in joins {
+                    write!(f, "{}", join)?;
                 }
                 if let Some(selection) = selection {
-                    s += &format!(" WHERE {}", selection.to_string());
+                    write!(f, " WHERE {}", selection)?;
                 }
-                s
+                display_returning(f, returning)
             }
             SQLStatement::SQLDelete {
                 table_name,
+                alias,
+                using,
+                joins,
                 selection,
+                returning,
+                ..
             } => {
-                let mut s = format!("DELETE FROM {}", table_name.to_string());
+                write!(f, "DELETE FROM {}", table_name)?;
+                if let Some(alias) = alias {
+                    write!(f, " AS {}", alias)?;
+                }
+                if let Some(using) = using {
+                    write!(f, " USING {}", using)?;
+                }
+                for join in joins {
+                    write!(f, "{}", join)?;
+                }
                 if let Some(selection) = selection {
-                    s += &format!(" WHERE {}", selection.to_string());
+                    write!(f, " WHERE {}", selection)?;
+                }
+                display_returning(f, returning)
+            }
+            SQLStatement::SQLMerge {
+                into,
+                table,
+                source,
+                on,
+                clauses,
+                ..
+            } => {
+                f.write_str(if *into { "MERGE INTO" } else { "MERGE" })?;
+                write!(f, " {} USING {} ON {}", table, source, on)?;
+                for clause in clauses {
+                    write!(f, " {}", clause)?;
                 }
-                s
+                Ok(())
             }
             SQLStatement::SQLCreateView {
+                or_replace,
                 name,
                 columns,
                 query,
                 materialized,
                 with_options,
+                ..
             } => {
-                let modifier = if *materialized { " MATERIALIZED" } else { "" };
-                let with_options = if !with_options.is_empty() {
-                    format!(" WITH ({})", comma_separated_string(with_options))
-                } else {
-                    "".into()
-                };
-                let columns = if !columns.is_empty() {
-                    format!(" ({})", comma_separated_string(columns))
-                } else {
-                    "".into()
-                };
-                format!(
-                    "CREATE{} VIEW {}{}{} AS {}",
-                    modifier,
-                    name.to_string(),
-                    with_options,
-                    columns,
-                    query.to_string(),
-                )
+                f.write_str("CREATE")?;
+                if *or_replace {
+                    f.write_str(" OR REPLACE")?;
+                }
+                if *materialized {
+                    f.write_str(" MATERIALIZED")?;
+                }
+                write!(f, " VIEW {}", name)?;
+                if !with_options.is_empty() {
+                    write!(f, " WITH ({})", display_comma_separated(with_options))?;
+                }
+                if !columns.is_empty() {
+                    write!(f, " ({})", display_comma_separated(columns))?;
+                }
+                write!(f, " AS {}", query)
             }
             SQLStatement::SQLCreateTable {
+                persistence,
+                if_not_exists,
                 name,
                 columns,
                 constraints,
                 with_options,
+                table_options,
+                without_rowid,
                 external,
                 file_format,
                 location,
+                query,
+                ..
             } => {
-                let mut s = format!(
-                    "CREATE {}TABLE {} ({}",
+                f.write_str("CREATE ")?;
+                if let Some(persistence) = persistence {
+                    write!(f, "{} ", persistence)?;
+                }
+                write!(
+                    f,
+                    "{}TABLE {}{}",
                     if *external { "EXTERNAL " } else { "" },
-                    name.to_string(),
-                    comma_separated_string(columns)
-                );
-                if !constraints.is_empty() {
-                    s += &format!(", {}", comma_separated_string(constraints));
+                    if *if_not_exists { "IF NOT EXISTS " } else { "" },
+                    name,
+                )?;
+                if query.is_none() || !columns.is_empty() || !constraints.is_empty() {
+                    write!(f, " ({}", display_comma_separated(columns))?;
+                    if !constraints.is_empty() {
+                        write!(f, ", {}", display_comma_separated(constraints))?;
+                    }
+                    f.write_str(")")?;
+                }
+                if *without_rowid {
+                    f.write_str(" WITHOUT ROWID")?;
                 }
-                s += ")";
                 if *external {
-                    s += &format!(
+                    write!(
+                        f,
                         " STORED AS {} LOCATION '{}'",
-                        file_format.as_ref().unwrap().to_string(),
+                        file_format.as_ref().unwrap(),
                         location.as_ref().unwrap()
-                    );
+                    )?;
                 }
                 if !with_options.is_empty() {
-                    s += &format!(" WITH ({})", comma_separated_string(with_options));
+                    write!(f, " WITH ({})", display_comma_separated(with_options))?;
+                }
+                if !table_options.is_empty() {
+                    write!(f, " {}", display_separated(table_options, " "))?;
+                }
+                if let Some(query) = query {
+                    write!(f, " AS {}", query)?;
+                }
+                Ok(())
+            }
+            SQLStatement::SQLCreateIndex {
+                name,
+                table_name,
+                unique,
+                concurrently,
+                if_not_exists,
+                using,
+                columns,
+                include,
+                predicate,
+                ..
+            } => {
+                write!(
+                    f,
+                    "CREATE {}INDEX {}{}{} ON {}",
+                    if *unique { "UNIQUE " } else { "" },
+                    if *concurrently { "CONCURRENTLY " } else { "" },
+                    if *if_not_exists { "IF NOT EXISTS " } else { "" },
+                    name,
+                    table_name
+                )?;
+                if let Some(using) = using {
+                    write!(f, " USING {}", using)?;
                 }
-                s
+                write!(f, " ({})", display_comma_separated(columns))?;
+                if !include.is_empty() {
+                    write!(f, " INCLUDE ({})", display_comma_separated(include))?;
+                }
+                if let Some(predicate) = predicate {
+                    write!(f, " WHERE {}", predicate)?;
+                }
+                Ok(())
             }
-            SQLStatement::SQLAlterTable { name, operation } => {
-                format!("ALTER TABLE {} {}", name.to_string(), operation.to_string())
+            SQLStatement::SQLCreateSchema {
+                schema_name,
+                if_not_exists,
+                ..
+            } => write!(
+                f,
+                "CREATE SCHEMA {}{}",
+                if *if_not_exists { "IF NOT EXISTS " } else { "" },
+                schema_name
+            ),
+            SQLStatement::SQLCreateDatabase {
+                db_name,
+                if_not_exists,
+                ..
+            } => write!(
+                f,
+                "CREATE DATABASE {}{}",
+                if *if_not_exists { "IF NOT EXISTS " } else { "" },
+                db_name
+            ),
+            SQLStatement::SQLCreateSequence {
+                name,
+                if_not_exists,
+                options,
+                ..
+            } => {
+                write!(
+                    f,
+                    "CREATE SEQUENCE {}{}",
+                    if *if_not_exists { "IF NOT EXISTS " } else { "" },
+                    name
+                )?;
+                for option in options {
+                    write!(f, " {}", option)?;
+                }
+                Ok(())
             }
+            SQLStatement::SQLCreateFunction {
+                or_replace,
+                procedure,
+                name,
+                args,
+                returns,
+                attributes,
+                body,
+                ..
+            } => {
+                write!(
+                    f,
+                    "CREATE {}{} {}",
+                    if *or_replace { "OR REPLACE " } else { "" },
+                    if *procedure { "PROCEDURE" } else { "FUNCTION" },
+                    name
+                )?;
+                if let Some(args) = args {
+                    write!(f, "({})", display_comma_separated(args))?;
+                }
+                if let Some(returns) = returns {
+                    write!(f, " RETURNS {}", returns)?;
+                }
+                for attribute in attributes {
+                    write!(f, " {}", attribute)?;
+                }
+                write!(f, " AS {}", body)
+            }
+            SQLStatement::SQLAlterSequence {
+                name,
+                if_exists,
+                options,
+                ..
+            } => {
+                write!(
+                    f,
+                    "ALTER SEQUENCE {}{}",
+                    if *if_exists { "IF EXISTS " } else { "" },
+                    name
+                )?;
+                for option in options {
+                    write!(f, " {}", option)?;
+                }
+                Ok(())
+            }
+            SQLStatement::SQLAlterTable {
+                name, operations, ..
+            } => write!(
+                f,
+                "ALTER TABLE {} {}",
+                name,
+                display_comma_separated(operations)
+            ),
             SQLStatement::SQLDrop {
                 object_type,
                 if_exists,
                 names,
                 cascade,
-            } => format!(
+                ..
+            } => write!(
+                f,
                 "DROP {}{} {}{}",
-                object_type.to_string(),
+                object_type,
                 if *if_exists { " IF EXISTS" } else { "" },
-                comma_separated_string(names),
+                display_comma_separated(names),
                 if *cascade { " CASCADE" } else { "" },
             ),
-            SQLStatement::SQLTransaction(stmts) => format!(
-                "BEGIN;\n{}\nCOMMIT;",
-                stmts.iter().map(|s| s.to_string()).collect::<Vec<String>>().join("\n"),
+            SQLStatement::SQLStartTransaction { begin, modes, .. } => {
+                f.write_str(if *begin { "BEGIN" } else { "START TRANSACTION" })?;
+                if !modes.is_empty() {
+                    write!(f, " {}", display_comma_separated(modes))?;
+                }
+                Ok(())
+            }
+            SQLStatement::SQLSetTransaction { modes, .. } => {
+                write!(f, "SET TRANSACTION {}", display_comma_separated(modes))
+            }
+            SQLStatement::SQLCommit { chain, .. } => {
+                write!(f, "COMMIT{}", if *chain { " AND CHAIN" } else { "" })
+            }
+            SQLStatement::SQLRollback {
+                chain, savepoint, ..
+            } => {
+                write!(f, "ROLLBACK{}", if *chain { " AND CHAIN" } else { "" })?;
+                if let Some(savepoint) = savepoint {
+                    write!(f, " TO SAVEPOINT {}", savepoint)?;
+                }
+                Ok(())
+            }
+            SQLStatement::SQLSavepoint { name, .. } => write!(f, "SAVEPOINT {}", name),
+            SQLStatement::SQLReleaseSavepoint { name, .. } => {
+                write!(f, "RELEASE SAVEPOINT {}", name)
+            }
+            SQLStatement::SQLPragma {
+                name, value, is_eq, ..
+            } => {
+                write!(f, "PRAGMA {}", name)?;
+                match value {
+                    Some(value) if *is_eq => write!(f, " = {}", value),
+                    Some(value) => write!(f, "({})", value),
+                    None => Ok(()),
+                }
+            }
+            SQLStatement::SQLAttachDatabase {
+                database,
+                database_file_name,
+                schema_name,
+                ..
+            } => write!(
+                f,
+                "ATTACH {}{} AS {}",
+                if *database { "DATABASE " } else { "" },
+                database_file_name,
+                schema_name
             ),
         }
     }
 }
 
+impl Spanned for SQLStatement {
+    fn span(&self) -> Span {
+        match self {
+            SQLStatement::SQLQuery(query) => query.span,
+            SQLStatement::SQLInsert { span, .. }
+            | SQLStatement::SQLCopy { span, .. }
+            | SQLStatement::SQLUpdate { span, .. }
+            | SQLStatement::SQLDelete { span, .. }
+            | SQLStatement::SQLMerge { span, .. }
+            | SQLStatement::SQLCreateView { span, .. }
+            | SQLStatement::SQLCreateTable { span, .. }
+            | SQLStatement::SQLCreateIndex { span, .. }
+            | SQLStatement::SQLCreateSchema { span, .. }
+            | SQLStatement::SQLCreateDatabase { span, .. }
+            | SQLStatement::SQLCreateSequence { span, .. }
+            | SQLStatement::SQLCreateFunction { span, .. }
+            | SQLStatement::SQLAlterSequence { span, .. }
+            | SQLStatement::SQLAlterTable { span, .. }
+            | SQLStatement::SQLDrop { span, .. }
+            | SQLStatement::SQLStartTransaction { span, .. }
+            | SQLStatement::SQLSetTransaction { span, .. }
+            | SQLStatement::SQLCommit { span, .. }
+            | SQLStatement::SQLRollback { span, .. }
+            | SQLStatement::SQLSavepoint { span, .. }
+            | SQLStatement::SQLReleaseSavepoint { span, .. }
+            | SQLStatement::SQLPragma { span, .. }
+            | SQLStatement::SQLAttachDatabase { span, .. } => *span,
+        }
+    }
+}
+
+impl SQLStatement {
+    /// The span of a statement includes its leading keyword(s), which are
+    /// consumed by `Parser::parse_statement` before the statement-specific
+    /// parsing function is invoked, so the span is fixed up afterwards.
+    pub(crate) fn span_mut(&mut self) -> &mut Span {
+        match self {
+            SQLStatement::SQLQuery(query) => &mut query.span,
+            SQLStatement::SQLInsert { span, .. }
+            | SQLStatement::SQLCopy { span, .. }
+            | SQLStatement::SQLUpdate { span, .. }
+            | SQLStatement::SQLDelete { span, .. }
+            | SQLStatement::SQLMerge { span, .. }
+            | SQLStatement::SQLCreateView { span, .. }
+            | SQLStatement::SQLCreateTable { span, .. }
+            | SQLStatement::SQLCreateIndex { span, .. }
+            | SQLStatement::SQLCreateSchema { span, .. }
+            | SQLStatement::SQLCreateDatabase { span, .. }
+            | SQLStatement::SQLCreateSequence { span, .. }
+            | SQLStatement::SQLCreateFunction { span, .. }
+            | SQLStatement::SQLAlterSequence { span, .. }
+            | SQLStatement::SQLAlterTable { span, .. }
+            | SQLStatement::SQLDrop { span, .. }
+            | SQLStatement::SQLStartTransaction { span, .. }
+            | SQLStatement::SQLSetTransaction { span, .. }
+            | SQLStatement::SQLCommit { span, .. }
+            | SQLStatement::SQLRollback { span, .. }
+            | SQLStatement::SQLSavepoint { span, .. }
+            | SQLStatement::SQLReleaseSavepoint { span, .. }
+            | SQLStatement::SQLPragma { span, .. }
+            | SQLStatement::SQLAttachDatabase { span, .. } => span,
+        }
+    }
+}
+
 /// A name of a table, view, custom type, etc., possibly multi-part, i.e. db.schema.obj
 #[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SQLObjectName(pub Vec<SQLIdent>);
 
-impl ToString for SQLObjectName {
-    fn to_string(&self) -> String {
-        self.0.join(".")
+impl fmt::Display for SQLObjectName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", display_separated(&self.0, "."))
     }
 }
 
 /// SQL assignment `foo = expr` as used in SQLUpdate
 #[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SQLAssignment {
-    pub id: SQLIdent,
+    /// The column, possibly qualified with a table name or alias (`t.foo`)
+    pub id: Vec<SQLIdent>,
     pub value: ASTNode,
+    pub span: Span,
 }
 
-impl ToString for SQLAssignment {
-    fn to_string(&self) -> String {
-        format!("{} = {}", self.id, self.value.to_string())
+impl fmt::Display for SQLAssignment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} = {}", display_separated(&self.id, "."), self.value)
+    }
+}
+
+impl Spanned for SQLAssignment {
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+/// The conflict handling clause of an `INSERT`
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum OnInsert {
+    /// MySQL's `ON DUPLICATE KEY UPDATE <assignments>`
+    DuplicateKeyUpdate(Vec<SQLAssignment>, Span),
+    /// PostgreSQL's `ON CONFLICT [ <target> ] DO { NOTHING | UPDATE ... }`
+    OnConflict(OnConflict),
+}
+
+impl fmt::Display for OnInsert {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OnInsert::DuplicateKeyUpdate(assignments, _) => write!(
+                f,
+                "ON DUPLICATE KEY UPDATE {}",
+                display_comma_separated(assignments)
+            ),
+            OnInsert::OnConflict(on_conflict) => write!(f, "{}", on_conflict),
+        }
+    }
+}
+
+impl Spanned for OnInsert {
+    fn span(&self) -> Span {
+        match self {
+            OnInsert::DuplicateKeyUpdate(_, span) => *span,
+            OnInsert::OnConflict(on_conflict) => on_conflict.span,
+        }
+    }
+}
+
+/// `ON CONFLICT [ <conflict_target> ] <action>`
+#[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct OnConflict {
+    pub conflict_target: Option<ConflictTarget>,
+    pub action: OnConflictAction,
+    pub span: Span,
+}
+
+impl fmt::Display for OnConflict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("ON CONFLICT")?;
+        if let Some(target) = &self.conflict_target {
+            write!(f, " {}", target)?;
+        }
+        write!(f, " {}", self.action)
+    }
+}
+
+impl Spanned for OnConflict {
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+/// The constraint whose violation an `ON CONFLICT` clause handles
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ConflictTarget {
+    /// `(<columns>) [ WHERE <selection> ]`, matching a unique index on these
+    /// columns, or a partial one whose predicate is implied by `selection`
+    Columns {
+        columns: Vec<SQLIdent>,
+        selection: Option<ASTNode>,
+    },
+    /// `ON CONSTRAINT <constraint name>`
+    OnConstraint(SQLObjectName),
+}
+
+impl fmt::Display for ConflictTarget {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConflictTarget::Columns { columns, selection } => {
+                write!(f, "({})", display_comma_separated(columns))?;
+                if let Some(selection) = selection {
+                    write!(f, " WHERE {}", selection)?;
+                }
+                Ok(())
+            }
+            ConflictTarget::OnConstraint(name) => write!(f, "ON CONSTRAINT {}", name),
+        }
+    }
+}
+
+/// What an `ON CONFLICT` clause does with a conflicting row
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum OnConflictAction {
+    /// `DO NOTHING`
+    DoNothing,
+    /// `DO UPDATE SET <assignments> [ WHERE <selection> ]`
+    DoUpdate {
+        assignments: Vec<SQLAssignment>,
+        selection: Option<ASTNode>,
+    },
+}
+
+impl fmt::Display for OnConflictAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OnConflictAction::DoNothing => f.write_str("DO NOTHING"),
+            OnConflictAction::DoUpdate {
+                assignments,
+                selection,
+            } => {
+                write!(f, "DO UPDATE SET {}", display_comma_separated(assignments))?;
+                if let Some(selection) = selection {
+                    write!(f, " WHERE {}", selection)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// A `WHEN [ NOT ] MATCHED [ AND <predicate> ] THEN <action>` clause of a `MERGE`
+#[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MergeClause {
+    pub kind: MergeClauseKind,
+    /// The `AND <predicate>` further restricting the rows this clause applies to
+    pub predicate: Option<ASTNode>,
+    pub action: MergeAction,
+    pub span: Span,
+}
+
+impl fmt::Display for MergeClause {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "WHEN {}", self.kind)?;
+        if let Some(predicate) = &self.predicate {
+            write!(f, " AND {}", predicate)?;
+        }
+        write!(f, " THEN {}", self.action)
+    }
+}
+
+impl Spanned for MergeClause {
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+/// Which rows a `MERGE` clause applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum MergeClauseKind {
+    /// `MATCHED`: target rows matched by a source row
+    Matched,
+    /// `NOT MATCHED`: source rows matching no target row
+    NotMatched,
+    /// MSSQL's `NOT MATCHED BY TARGET`, a synonym for `NOT MATCHED`
+    NotMatchedByTarget,
+    /// MSSQL's `NOT MATCHED BY SOURCE`: target rows matching no source row
+    NotMatchedBySource,
+}
+
+impl fmt::Display for MergeClauseKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            MergeClauseKind::Matched => "MATCHED",
+            MergeClauseKind::NotMatched => "NOT MATCHED",
+            MergeClauseKind::NotMatchedByTarget => "NOT MATCHED BY TARGET",
+            MergeClauseKind::NotMatchedBySource => "NOT MATCHED BY SOURCE",
+        })
+    }
+}
+
+/// What a `MERGE` clause does with the rows it applies to
+#[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum MergeAction {
+    /// `UPDATE SET <assignments>`
+    Update { assignments: Vec<SQLAssignment> },
+    /// `DELETE`
+    Delete,
+    /// `INSERT [ (<columns>) ] VALUES (<values>)`
+    Insert {
+        columns: Vec<SQLIdent>,
+        values: Vec<ASTNode>,
+    },
+}
+
+impl fmt::Display for MergeAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MergeAction::Update { assignments } => {
+                write!(f, "UPDATE SET {}", display_comma_separated(assignments))
+            }
+            MergeAction::Delete => f.write_str("DELETE"),
+            MergeAction::Insert { columns, values } => {
+                f.write_str("INSERT ")?;
+                if !columns.is_empty() {
+                    write!(f, "({}) ", display_comma_separated(columns))?;
+                }
+                write!(f, "VALUES ({})", display_comma_separated(values))
+            }
+        }
+    }
+}
+
+/// Write the ` RETURNING <items>` clause of a statement, if any
+fn display_returning(f: &mut fmt::Formatter, returning: &[SQLSelectItem]) -> fmt::Result {
+    if !returning.is_empty() {
+        write!(f, " RETURNING {}", display_comma_separated(returning))?;
+    }
+    Ok(())
+}
+
+/// The conflict resolution algorithm of SQLite's `INSERT OR ...`
+#[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SQLiteOnConflict {
+    Rollback,
+    Abort,
+    Fail,
+    Ignore,
+    Replace,
+}
+
+impl fmt::Display for SQLiteOnConflict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            SQLiteOnConflict::Rollback => "ROLLBACK",
+            SQLiteOnConflict::Abort => "ABORT",
+            SQLiteOnConflict::Fail => "FAIL",
+            SQLiteOnConflict::Ignore => "IGNORE",
+            SQLiteOnConflict::Replace => "REPLACE",
+        })
+    }
+}
+
+/// A characteristic of a transaction, as set by `START TRANSACTION` or
+/// `SET TRANSACTION`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TransactionMode {
+    AccessMode(TransactionAccessMode),
+    IsolationLevel(TransactionIsolationLevel),
+}
+
+impl fmt::Display for TransactionMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TransactionMode::AccessMode(access_mode) => write!(f, "{}", access_mode),
+            TransactionMode::IsolationLevel(level) => write!(f, "ISOLATION LEVEL {}", level),
+        }
+    }
+}
+
+/// `READ ONLY` or `READ WRITE`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TransactionAccessMode {
+    ReadOnly,
+    ReadWrite,
+}
+
+impl fmt::Display for TransactionAccessMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            TransactionAccessMode::ReadOnly => "READ ONLY",
+            TransactionAccessMode::ReadWrite => "READ WRITE",
+        })
+    }
+}
+
+/// The argument of `ISOLATION LEVEL`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TransactionIsolationLevel {
+    ReadUncommitted,
+    ReadCommitted,
+    RepeatableRead,
+    Serializable,
+}
+
+impl fmt::Display for TransactionIsolationLevel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            TransactionIsolationLevel::ReadUncommitted => "READ UNCOMMITTED",
+            TransactionIsolationLevel::ReadCommitted => "READ COMMITTED",
+            TransactionIsolationLevel::RepeatableRead => "REPEATABLE READ",
+            TransactionIsolationLevel::Serializable => "SERIALIZABLE",
+        })
     }
 }
 
 /// SQL column definition
 #[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SQLColumnDef {
     pub name: SQLIdent,
     pub data_type: SQLType,
-    pub is_primary: bool,
-    pub is_unique: bool,
-    pub default: Option<ASTNode>,
-    pub allow_null: bool,
+    /// The column constraints and other options, in the order written
+    pub options: Vec<ColumnOptionDef>,
+    pub span: Span,
 }
 
-impl ToString for SQLColumnDef {
-    fn to_string(&self) -> String {
-        let mut s = format!("{} {}", self.name, self.data_type.to_string());
-        if self.is_primary {
-            s += " PRIMARY KEY";
-        }
-        if self.is_unique {
-            s += " UNIQUE";
-        }
-        if let Some(ref default) = self.default {
-            s += &format!(" DEFAULT {}", default.to_string());
+impl fmt::Display for SQLColumnDef {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+        if self.data_type != SQLType::Unspecified {
+            write!(f, " {}", self.data_type)?;
         }
-        if !self.allow_null {
-            s += " NOT NULL";
+        for option in &self.options {
+            write!(f, " {}", option)?;
         }
-        s
+        Ok(())
+    }
+}
+
+impl Spanned for SQLColumnDef {
+    fn span(&self) -> Span {
+        self.span
     }
 }
 
 /// SQL function
 #[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SQLFunction {
     pub name: SQLObjectName,
     pub args: Vec<ASTNode>,
     pub over: Option<SQLWindowSpec>,
     // aggregate functions may specify eg `COUNT(DISTINCT x)`
     pub distinct: bool,
+    pub span: Span,
 }
 
-impl ToString for SQLFunction {
-    fn to_string(&self) -> String {
-        let mut s = format!(
+impl fmt::Display for SQLFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
             "{}({}{})",
-            self.name.to_string(),
+            self.name,
             if self.distinct { "DISTINCT " } else { "" },
-            comma_separated_string(&self.args),
-        );
+            display_comma_separated(&self.args),
+        )?;
         if let Some(o) = &self.over {
-            s += &format!(" OVER ({})", o.to_string())
+            write!(f, " OVER ({})", o)?;
         }
-        s
+        Ok(())
+    }
+}
+
+impl Spanned for SQLFunction {
+    fn span(&self) -> Span {
+        self.span
     }
 }
 
 #[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum SQLDateTimeField {
     Year,
     Month,
@@ -650,21 +1565,22 @@ pub enum SQLDateTimeField {
     Second,
 }
 
-impl ToString for SQLDateTimeField {
-    fn to_string(&self) -> String {
-        match self {
-            SQLDateTimeField::Year => "YEAR".to_string(),
-            SQLDateTimeField::Month => "MONTH".to_string(),
-            SQLDateTimeField::Day => "DAY".to_string(),
-            SQLDateTimeField::Hour => "HOUR".to_string(),
-            SQLDateTimeField::Minute => "MINUTE".to_string(),
-            SQLDateTimeField::Second => "SECOND".to_string(),
-        }
+impl fmt::Display for SQLDateTimeField {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            SQLDateTimeField::Year => "YEAR",
+            SQLDateTimeField::Month => "MONTH",
+            SQLDateTimeField::Day => "DAY",
+            SQLDateTimeField::Hour => "HOUR",
+            SQLDateTimeField::Minute => "MINUTE",
+            SQLDateTimeField::Second => "SECOND",
+        })
     }
 }
 
 /// External table's available file format
 #[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum FileFormat {
     TEXTFILE,
     SEQUENCEFILE,
@@ -675,22 +1591,22 @@ pub enum FileFormat {
     JSONFILE,
 }
 
-impl ToString for FileFormat {
-    fn to_string(&self) -> String {
+impl fmt::Display for FileFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         use self::FileFormat::*;
-        match self {
-            TEXTFILE => "TEXTFILE".to_string(),
-            SEQUENCEFILE => "SEQUENCEFILE".to_string(),
-            ORC => "ORC".to_string(),
-            PARQUET => "PARQUET".to_string(),
-            AVRO => "AVRO".to_string(),
-            RCFILE => "RCFILE".to_string(),
-            JSONFILE => "TEXTFILE".to_string(),
-        }
+        f.write_str(match self {
+            TEXTFILE => "TEXTFILE",
+            SEQUENCEFILE => "SEQUENCEFILE",
+            ORC => "ORC",
+            PARQUET => "PARQUET",
+            AVRO => "AVRO",
+            RCFILE => "RCFILE",
+            JSONFILE => "TEXTFILE",
+        })
     }
 }
 
-use crate::sqlparser::ParserError;
+use crate::sqlparser::{ParserError, ParserErrorKind};
 use std::str::FromStr;
 impl FromStr for FileFormat {
     type Err = ParserError;
@@ -705,37 +1621,97 @@ impl FromStr for FileFormat {
             "AVRO" => Ok(AVRO),
             "RCFILE" => Ok(RCFILE),
             "JSONFILE" => Ok(JSONFILE),
-            _ => Err(ParserError::ParserError(format!(
-                "Unexpected file format: {}",
-                s
-            ))),
+            _ => Err(ParserError {
+                expected: [
+                    "TEXTFILE",
+                    "SEQUENCEFILE",
+                    "ORC",
+                    "PARQUET",
+                    "AVRO",
+                    "RCFILE",
+                    "JSONFILE",
+                ]
+                .iter()
+                .map(|f| f.to_string())
+                .collect(),
+                ..ParserError::new(
+                    ParserErrorKind::UnexpectedToken,
+                    format!("Unexpected file format: {}", s),
+                    Span::empty(),
+                )
+            }),
         }
     }
 }
 
 #[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum SQLObjectType {
     Table,
     View,
+    Index,
+    Schema,
+    Database,
+    Sequence,
 }
 
-impl SQLObjectType {
-    fn to_string(&self) -> String {
-        match self {
-            SQLObjectType::Table => "TABLE".into(),
-            SQLObjectType::View => "VIEW".into(),
-        }
+impl fmt::Display for SQLObjectType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            SQLObjectType::Table => "TABLE",
+            SQLObjectType::View => "VIEW",
+            SQLObjectType::Index => "INDEX",
+            SQLObjectType::Schema => "SCHEMA",
+            SQLObjectType::Database => "DATABASE",
+            SQLObjectType::Sequence => "SEQUENCE",
+        })
     }
 }
 
 #[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SQLOption {
     pub name: SQLIdent,
     pub value: Value,
+    pub span: Span,
+}
+
+impl fmt::Display for SQLOption {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} = {}", self.name, self.value)
+    }
+}
+
+impl Spanned for SQLOption {
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+/// A comment in the source text, as returned by `Parser::parse_sql_with_comments`
+#[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SQLComment {
+    /// The text of the comment, without the `--` or `/*` and `*/` markers
+    /// (and without the newline ending a single-line comment)
+    pub text: String,
+    /// Whether this is a `/* ... */` comment rather than a `-- ...` one
+    pub multiline: bool,
+    pub span: Span,
+}
+
+impl fmt::Display for SQLComment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.multiline {
+            write!(f, "/*{}*/", self.text)
+        } else {
+            write!(f, "--{}", self.text)
+        }
+    }
 }
 
-impl ToString for SQLOption {
-    fn to_string(&self) -> String {
-        format!("{} = {}", self.name.to_string(), self.value.to_string())
+impl Spanned for SQLComment {
+    fn span(&self) -> Span {
+        self.span
     }
 }
@@ -0,0 +1,486 @@
+//! Query parameters (`ASTNode::SQLPlaceholder`, e.g. `?`, `$1` or `:name`)
+//! and client-side binding of values to them.
+//!
+//! ```
+//! use sqlparser::dialect::PostgreSqlDialect;
+//! use sqlparser::sqlast::{BindParams, Value};
+//! use sqlparser::sqlparser::Parser;
+//!
+//! let sql = "SELECT * FROM t WHERE a = $1 AND b = $2".to_string();
+//! let statements = Parser::parse_sql(&PostgreSqlDialect {}, sql).unwrap();
+//! assert_eq!(vec!["$1", "$2"], statements[0].placeholders());
+//! let params = BindParams::Positional(vec![
+//!     Value::Long(1),
+//!     Value::SingleQuotedString("it's".to_string()),
+//! ]);
+//! let bound = statements[0].bind(&PostgreSqlDialect {}, &params).unwrap();
+//! assert_eq!("SELECT * FROM t WHERE a = 1 AND b = 'it''s'", bound.to_string());
+//! ```
+use std::collections::HashMap;
+use std::error::Error;
+use std::ops::ControlFlow;
+
+use super::*;
+use crate::dialect::Dialect;
+use crate::sqltokenizer::{Token, Tokenizer};
+
+/// The values to substitute for the query parameters of a statement, see
+/// `SQLStatement::bind`
+#[derive(Debug, Clone, PartialEq)]
+pub enum BindParams {
+    /// Values for positional (`?`) and numbered (`$1`, `?1`) parameters.
+    /// `$n` and `?n` take the n-th value (counting from 1), while `?`
+    /// takes the value after the highest-numbered one taken so far.
+    Positional(Vec<Value>),
+    /// Values for named parameters (`:name`, `@name`, `$name`), keyed by
+    /// the name without its prefix. Values for names that don't appear in
+    /// the statement are ignored.
+    Named(HashMap<String, Value>),
+}
+
+/// An error binding values to the parameters of a statement
+#[derive(Debug, Clone, PartialEq)]
+pub struct BindError {
+    /// A human-readable description, e.g. "No value for parameter $3"
+    pub message: String,
+    /// The location of the offending parameter (empty if unknown, or if the
+    /// error is not about a single parameter)
+    pub span: Span,
+}
+
+impl BindError {
+    fn new(message: String, span: Span) -> Self {
+        BindError { message, span }
+    }
+}
+
+impl fmt::Display for BindError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "sql bind error: {}", self.message)
+    }
+}
+
+impl Error for BindError {}
+
+/// How a placeholder refers to its value
+enum Param<'a> {
+    /// `?`
+    Next,
+    /// `$1`, `?1` (and `:1`)
+    Number(usize),
+    /// `:name`, `@name`, `$name`
+    Name(&'a str),
+}
+
+impl<'a> Param<'a> {
+    fn from_placeholder(placeholder: &'a str) -> Self {
+        let mut chars = placeholder.chars();
+        chars.next(); // the prefix
+        let name = chars.as_str();
+        if name.is_empty() {
+            Param::Next
+        } else if name.chars().all(|ch| ch.is_ascii_digit()) {
+            // an out of range number is as invalid as 0
+            Param::Number(name.parse().unwrap_or(0))
+        } else {
+            Param::Name(name)
+        }
+    }
+}
+
+/// Collects the placeholders in traversal order
+struct Placeholders(Vec<(String, Span)>);
+
+impl Visitor for Placeholders {
+    type Break = ();
+
+    fn pre_visit_expr(&mut self, expr: &ASTNode) -> ControlFlow<()> {
+        if let ASTNode::SQLPlaceholder(s, span) = expr {
+            self.0.push((s.clone(), *span));
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+/// Replaces the placeholders, visited in the same order as by
+/// `Placeholders`, with the given values
+struct Binder<I: Iterator<Item = Value>>(I);
+
+impl<I: Iterator<Item = Value>> VisitorMut for Binder<I> {
+    type Break = ();
+
+    fn pre_visit_expr(&mut self, expr: &mut ASTNode) -> ControlFlow<()> {
+        if let ASTNode::SQLPlaceholder(_, span) = expr {
+            // `bind` checked that there is a value for each placeholder
+            *expr = ASTNode::SQLValue(self.0.next().unwrap(), *span);
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+impl SQLStatement {
+    /// The query parameters (`ASTNode::SQLPlaceholder`) of the statement,
+    /// in the order they appear in the SQL text, e.g. `["$1", "?", ":name"]`.
+    /// A parameter used more than once is listed each time.
+    pub fn placeholders(&self) -> Vec<String> {
+        let mut placeholders = Placeholders(vec![]);
+        let _ = self.visit(&mut placeholders);
+        // The traversal order mostly matches the text, except for e.g.
+        // MySQL's `LIMIT <offset>, <count>`
+        placeholders.0.sort_by_key(|(_, span)| span.start);
+        placeholders.0.into_iter().map(|(s, _)| s).collect()
+    }
+
+    /// Return a copy of the statement with each query parameter replaced by
+    /// the literal value bound to it, so that its `to_string()` is SQL with
+    /// the values correctly escaped.
+    ///
+    /// Fails if a parameter has no value, if positional values are given
+    /// for named parameters (or vice versa), if more positional values are
+    /// given than there are parameters, or if a value can't be written as a
+    /// literal safely: a non-finite number, a hexadecimal string with other
+    /// characters than hex digits, or a dollar-quoted string that doesn't
+    /// read back as the same string. For dialects that support backslash
+    /// escapes (see `Dialect::supports_backslash_escapes`), backslashes in
+    /// bound strings are escaped.
+    pub fn bind(&self, dialect: &dyn Dialect, params: &BindParams) -> Result<Self, BindError> {
+        let mut placeholders = Placeholders(vec![]);
+        let _ = self.visit(&mut placeholders);
+        let placeholders = placeholders.0;
+
+        // Number the `?`s in the order they appear in the SQL text
+        let mut text_order: Vec<usize> = (0..placeholders.len()).collect();
+        text_order.sort_by_key(|&i| placeholders[i].1.start);
+
+        let mut values: Vec<Option<&Value>> = vec![None; placeholders.len()];
+        match params {
+            BindParams::Positional(positional) => {
+                let mut next = 0;
+                for i in text_order {
+                    let (placeholder, span) = &placeholders[i];
+                    let index = match Param::from_placeholder(placeholder) {
+                        Param::Next => next,
+                        Param::Number(n) if n > 0 => n - 1,
+                        Param::Number(_) => {
+                            return Err(BindError::new(
+                                format!("Invalid parameter number: {}", placeholder),
+                                *span,
+                            ))
+                        }
+                        Param::Name(_) => {
+                            return Err(BindError::new(
+                                format!("Named parameter {} requires named values", placeholder),
+                                *span,
+                            ))
+                        }
+                    };
+                    next = next.max(index + 1);
+                    values[i] = Some(positional.get(index).ok_or_else(|| {
+                        BindError::new(
+                            format!(
+                                "No value for parameter {} ({} values given)",
+                                placeholder,
+                                positional.len()
+                            ),
+                            *span,
+                        )
+                    })?);
+                }
+                if positional.len() > next {
+                    return Err(BindError::new(
+                        format!(
+                            "Expected {} parameter values, found: {}",
+                            next,
+                            positional.len()
+                        ),
+                        Span::empty(),
+                    ));
+                }
+            }
+            BindParams::Named(named) => {
+                for (i, (placeholder, span)) in placeholders.iter().enumerate() {
+                    let name = match Param::from_placeholder(placeholder) {
+                        Param::Name(name) => name,
+                        _ => {
+                            return Err(BindError::new(
+                                format!(
+                                    "Positional parameter {} requires positional values",
+                                    placeholder
+                                ),
+                                *span,
+                            ))
+                        }
+                    };
+                    values[i] = Some(named.get(name).ok_or_else(|| {
+                        BindError::new(format!("No value for parameter {}", placeholder), *span)
+                    })?);
+                }
+            }
+        }
+
+        let values: Vec<&Value> = values.into_iter().map(Option::unwrap).collect();
+        for (value, (_, span)) in values.iter().zip(&placeholders) {
+            check_bound_value(dialect, value, *span)?;
+        }
+        let values = values
+            .into_iter()
+            .map(|value| escape_backslashes(dialect, value));
+        let mut bound = self.clone();
+        let _ = bound.visit_mut(&mut Binder(values.into_iter()));
+        Ok(bound)
+    }
+}
+
+/// Check that `value` reads back as the same value when printed as a literal
+fn check_bound_value(dialect: &dyn Dialect, value: &Value, span: Span) -> Result<(), BindError> {
+    let message = match value {
+        Value::Double(v) if !v.is_finite() => format!("Can't bind a non-finite number: {}", v),
+        Value::HexStringLiteral(s) if !s.chars().all(|ch| ch.is_ascii_hexdigit()) => {
+            format!("Invalid hexadecimal string: {}", s)
+        }
+        Value::DollarQuotedString(s) if !reads_back_as_dollar_quoted(dialect, s) => {
+            format!("Invalid dollar-quoted string for {:?}: {}", dialect, value)
+        }
+        _ => return Ok(()),
+    };
+    Err(BindError::new(message, span))
+}
+
+/// Escape the backslashes in a string `value` if `dialect` treats them as
+/// escape characters, as such strings are kept as written when parsed
+fn escape_backslashes(dialect: &dyn Dialect, value: &Value) -> Value {
+    if !dialect.supports_backslash_escapes() {
+        return value.clone();
+    }
+    let escape = |s: &String| s.replace('\\', "\\\\");
+    match value {
+        Value::SingleQuotedString(s) => Value::SingleQuotedString(escape(s)),
+        Value::NationalStringLiteral(s) => Value::NationalStringLiteral(escape(s)),
+        Value::Date(s) => Value::Date(escape(s)),
+        Value::Time(s) => Value::Time(escape(s)),
+        Value::Timestamp(s) => Value::Timestamp(escape(s)),
+        _ => value.clone(),
+    }
+}
+
+/// Whether `s` is tokenized as the same single dollar-quoted string by
+/// `dialect`, i.e. its tag is valid and its value doesn't end it early
+fn reads_back_as_dollar_quoted(dialect: &dyn Dialect, s: &DollarQuotedString) -> bool {
+    let sql = s.to_string();
+    match Tokenizer::new(dialect, &sql).tokenize() {
+        Ok(tokens) => tokens == vec![Token::DollarQuotedString(s.clone())],
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialect::{GenericSqlDialect, MySqlDialect, PostgreSqlDialect, SQLiteDialect};
+    use crate::sqlparser::Parser;
+    use crate::test_utils::TestedDialects;
+
+    fn sqlite() -> TestedDialects {
+        TestedDialects {
+            dialects: vec![Box::new(SQLiteDialect {})],
+        }
+    }
+
+    fn bind(sql: &str, params: &BindParams) -> Result<String, String> {
+        sqlite()
+            .verified_stmt(sql)
+            .bind(&SQLiteDialect {}, params)
+            .map(|statement| statement.to_string())
+            .map_err(|e| e.message)
+    }
+
+    fn named(params: &[(&str, Value)]) -> BindParams {
+        BindParams::Named(
+            params
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.clone()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn bind_positional() {
+        let params = BindParams::Positional(vec![
+            Value::Long(1),
+            Value::SingleQuotedString("O'Reilly".to_string()),
+            Value::Null,
+        ]);
+        assert_eq!(
+            bind("UPDATE t SET a = ?, b = ? WHERE c = ?", &params),
+            Ok("UPDATE t SET a = 1, b = 'O''Reilly' WHERE c = NULL".to_string())
+        );
+        // `?` continues after the highest number so far
+        assert_eq!(
+            bind("SELECT ?2, ?, ?1, ?3", &params),
+            Ok("SELECT 'O''Reilly', NULL, 1, NULL".to_string())
+        );
+        // MySQL's `LIMIT <offset>, <count>` binds in text order
+        let statement =
+            Parser::parse_sql(&MySqlDialect {}, "SELECT a FROM t LIMIT ?, ?".to_string())
+                .unwrap()
+                .remove(0);
+        let bound = statement.bind(
+            &MySqlDialect {},
+            &BindParams::Positional(vec![Value::Long(20), Value::Long(10)]),
+        );
+        assert_eq!(
+            "SELECT a FROM t LIMIT 10 OFFSET 20",
+            bound.unwrap().to_string()
+        );
+
+        assert_eq!(
+            bind("SELECT ?, ?4", &params),
+            Err("No value for parameter ?4 (3 values given)".to_string())
+        );
+        assert_eq!(
+            bind("SELECT ?, ?", &params),
+            Err("Expected 2 parameter values, found: 3".to_string())
+        );
+        assert_eq!(
+            bind("SELECT ?0", &params),
+            Err("Invalid parameter number: ?0".to_string())
+        );
+        assert_eq!(
+            bind("SELECT ?, :a", &params),
+            Err("Named parameter :a requires named values".to_string())
+        );
+    }
+
+    #[test]
+    fn bind_named() {
+        let params = named(&[
+            ("id", Value::Long(7)),
+            ("name", Value::NationalStringLiteral("a'b".to_string())),
+            ("unused", Value::Boolean(true)),
+        ]);
+        assert_eq!(
+            bind(
+                "SELECT * FROM t WHERE id = :id OR parent = @id AND name = $name",
+                &params
+            ),
+            Ok("SELECT * FROM t WHERE id = 7 OR parent = 7 AND name = N'a''b'".to_string())
+        );
+        assert_eq!(
+            bind("SELECT :id, :other", &params),
+            Err("No value for parameter :other".to_string())
+        );
+        assert_eq!(
+            bind("SELECT :id, ?", &params),
+            Err("Positional parameter ? requires positional values".to_string())
+        );
+    }
+
+    #[test]
+    fn bind_on_conflict_target() {
+        // The predicate of a partial unique index's conflict target
+        let statement = Parser::parse_sql(
+            &PostgreSqlDialect {},
+            "INSERT INTO t (a) VALUES ($1) ON CONFLICT (a) WHERE b = $2 DO NOTHING".to_string(),
+        )
+        .unwrap()
+        .remove(0);
+        assert_eq!(vec!["$1", "$2"], statement.placeholders());
+        let bound = statement.bind(
+            &PostgreSqlDialect {},
+            &BindParams::Positional(vec![Value::Long(1), Value::Long(2)]),
+        );
+        assert_eq!(
+            "INSERT INTO t (a) VALUES (1) ON CONFLICT (a) WHERE b = 2 DO NOTHING",
+            bound.unwrap().to_string()
+        );
+        let bound = statement.bind(
+            &PostgreSqlDialect {},
+            &BindParams::Positional(vec![Value::Long(1)]),
+        );
+        assert_eq!(
+            "No value for parameter $2 (1 values given)",
+            bound.unwrap_err().message
+        );
+    }
+
+    #[test]
+    fn bind_unsafe_values() {
+        let bind_one = |dialect: &dyn Dialect, value: Value| {
+            let statement = Parser::parse_sql(dialect, "SELECT ?".to_string())
+                .unwrap()
+                .remove(0);
+            statement
+                .bind(dialect, &BindParams::Positional(vec![value]))
+                .map(|statement| statement.to_string())
+                .map_err(|e| e.message)
+        };
+        assert_eq!(
+            bind_one(&GenericSqlDialect {}, Value::Double(f64::NAN.into())),
+            Err("Can't bind a non-finite number: NaN".to_string())
+        );
+        assert_eq!(
+            bind_one(
+                &GenericSqlDialect {},
+                Value::HexStringLiteral("ab'".to_string())
+            ),
+            Err("Invalid hexadecimal string: ab'".to_string())
+        );
+        assert_eq!(
+            bind_one(
+                &GenericSqlDialect {},
+                Value::HexStringLiteral("ab01".to_string())
+            ),
+            Ok("SELECT X'ab01'".to_string())
+        );
+        let backslash = Value::SingleQuotedString("a\\' OR 1 = 1 --".to_string());
+        assert_eq!(
+            bind_one(&GenericSqlDialect {}, backslash.clone()),
+            Ok("SELECT 'a\\'' OR 1 = 1 --'".to_string())
+        );
+        // MySQL reads the escaped backslash and the doubled quote back as
+        // the string's characters
+        assert_eq!(
+            bind_one(&MySqlDialect {}, backslash),
+            Ok("SELECT 'a\\\\'' OR 1 = 1 --'".to_string())
+        );
+        // PostgreSQL has no `?` parameters
+        let bind_pg = |value: Value| {
+            let statement = Parser::parse_sql(&PostgreSqlDialect {}, "SELECT $1".to_string())
+                .unwrap()
+                .remove(0);
+            statement
+                .bind(&PostgreSqlDialect {}, &BindParams::Positional(vec![value]))
+                .map(|statement| statement.to_string())
+                .map_err(|e| e.message)
+        };
+        let dollar_quoted = |value: &str, tag: Option<&str>| {
+            Value::DollarQuotedString(DollarQuotedString {
+                value: value.to_string(),
+                tag: tag.map(|t| t.to_string()),
+            })
+        };
+        assert_eq!(
+            bind_pg(dollar_quoted("it's $$", Some("x"))),
+            Ok("SELECT $x$it's $$$x$".to_string())
+        );
+        assert_eq!(
+            bind_pg(dollar_quoted("a$$; DROP TABLE t", None)),
+            Err(
+                "Invalid dollar-quoted string for PostgreSqlDialect: $$a$$; DROP TABLE t$$"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            bind_pg(dollar_quoted("a$", None)),
+            Err("Invalid dollar-quoted string for PostgreSqlDialect: $$a$$$".to_string())
+        );
+        assert_eq!(
+            bind_pg(dollar_quoted("a", Some("1"))),
+            Err("Invalid dollar-quoted string for PostgreSqlDialect: $1$a$1$".to_string())
+        );
+        assert_eq!(
+            bind_one(&GenericSqlDialect {}, dollar_quoted("a", None)),
+            Err("Invalid dollar-quoted string for GenericSqlDialect: $$a$$".to_string())
+        );
+    }
+}
@@ -1,8 +1,12 @@
 use super::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fmt;
 
 /// The most complete variant of a `SELECT` query expression, optionally
 /// including `WITH`, `UNION` / other set operations, and `ORDER BY`.
 #[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SQLQuery {
     /// WITH (common table expressions, or CTEs)
     pub ctes: Vec<Cte>,
@@ -12,39 +16,73 @@ pub struct SQLQuery {
     pub order_by: Vec<SQLOrderByExpr>,
     /// LIMIT { <N> | ALL }
     pub limit: Option<ASTNode>,
-    /// OFFSET <N> { ROW | ROWS }
-    pub offset: Option<ASTNode>,
+    /// OFFSET <N> [ { ROW | ROWS } ]
+    pub offset: Option<Offset>,
     /// FETCH { FIRST | NEXT } <N> [ PERCENT ] { ROW | ROWS } | { ONLY | WITH TIES }
     pub fetch: Option<Fetch>,
+    pub span: Span,
 }
 
-impl ToString for SQLQuery {
-    fn to_string(&self) -> String {
-        let mut s = String::new();
+impl fmt::Display for SQLQuery {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         if !self.ctes.is_empty() {
-            s += &format!("WITH {} ", comma_separated_string(&self.ctes))
+            write!(f, "WITH {} ", display_comma_separated(&self.ctes))?;
         }
-        s += &self.body.to_string();
+        write!(f, "{}", self.body)?;
         if !self.order_by.is_empty() {
-            s += &format!(" ORDER BY {}", comma_separated_string(&self.order_by));
+            write!(f, " ORDER BY {}", display_comma_separated(&self.order_by))?;
         }
         if let Some(ref limit) = self.limit {
-            s += &format!(" LIMIT {}", limit.to_string());
+            write!(f, " LIMIT {}", limit)?;
         }
         if let Some(ref offset) = self.offset {
-            s += &format!(" OFFSET {} ROWS", offset.to_string());
+            write!(f, " {}", offset)?;
         }
         if let Some(ref fetch) = self.fetch {
-            s.push(' ');
-            s += &fetch.to_string();
+            write!(f, " {}", fetch)?;
         }
-        s
+        Ok(())
+    }
+}
+
+impl Spanned for SQLQuery {
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+/// `OFFSET <N> [ { ROW | ROWS } ]`, or the offset part of MySQL's
+/// `LIMIT <offset>, <count>`
+#[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Offset {
+    pub value: ASTNode,
+    /// Whether `ROW`/`ROWS` follows the value. The SQL standard requires
+    /// it, but MySQL doesn't accept it.
+    pub rows: bool,
+    pub span: Span,
+}
+
+impl fmt::Display for Offset {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "OFFSET {}", self.value)?;
+        if self.rows {
+            f.write_str(" ROWS")?;
+        }
+        Ok(())
+    }
+}
+
+impl Spanned for Offset {
+    fn span(&self) -> Span {
+        self.span
     }
 }
 
 /// A node in a tree, representing a "query body" expression, roughly:
 /// `SELECT ... [ {UNION|EXCEPT|INTERSECT} SELECT ...]`
 #[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum SQLSetExpr {
     /// Restricted SELECT .. FROM .. HAVING (no ORDER BY or set operations)
     Select(Box<SQLSelect>),
@@ -57,50 +95,58 @@ pub enum SQLSetExpr {
         all: bool,
         left: Box<SQLSetExpr>,
         right: Box<SQLSetExpr>,
+        span: Span,
     },
     Values(SQLValues),
     // TODO: ANSI SQL supports `TABLE` here.
 }
 
-impl ToString for SQLSetExpr {
-    fn to_string(&self) -> String {
+impl fmt::Display for SQLSetExpr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            SQLSetExpr::Select(s) => s.to_string(),
-            SQLSetExpr::Query(q) => format!("({})", q.to_string()),
-            SQLSetExpr::Values(v) => v.to_string(),
+            SQLSetExpr::Select(s) => write!(f, "{}", s),
+            SQLSetExpr::Query(q) => write!(f, "({})", q),
+            SQLSetExpr::Values(v) => write!(f, "{}", v),
             SQLSetExpr::SetOperation {
                 left,
                 right,
                 op,
                 all,
+                ..
             } => {
                 let all_str = if *all { " ALL" } else { "" };
-                format!(
-                    "{} {}{} {}",
-                    left.to_string(),
-                    op.to_string(),
-                    all_str,
-                    right.to_string()
-                )
+                write!(f, "{} {}{} {}", left, op, all_str, right)
             }
         }
     }
 }
 
+impl Spanned for SQLSetExpr {
+    fn span(&self) -> Span {
+        match self {
+            SQLSetExpr::Select(s) => s.span,
+            SQLSetExpr::Query(q) => q.span,
+            SQLSetExpr::SetOperation { span, .. } => *span,
+            SQLSetExpr::Values(v) => v.1,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum SQLSetOperator {
     Union,
     Except,
     Intersect,
 }
 
-impl ToString for SQLSetOperator {
-    fn to_string(&self) -> String {
-        match self {
-            SQLSetOperator::Union => "UNION".to_string(),
-            SQLSetOperator::Except => "EXCEPT".to_string(),
-            SQLSetOperator::Intersect => "INTERSECT".to_string(),
-        }
+impl fmt::Display for SQLSetOperator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            SQLSetOperator::Union => "UNION",
+            SQLSetOperator::Except => "EXCEPT",
+            SQLSetOperator::Intersect => "INTERSECT",
+        })
     }
 }
 
@@ -108,6 +154,7 @@ impl ToString for SQLSetOperator {
 /// appear either as the only body item of an `SQLQuery`, or as an operand
 /// to a set operation like `UNION`.
 #[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SQLSelect {
     pub distinct: bool,
     /// projection expressions
@@ -122,31 +169,39 @@ pub struct SQLSelect {
     pub group_by: Vec<ASTNode>,
     /// HAVING
     pub having: Option<ASTNode>,
+    pub span: Span,
 }
 
-impl ToString for SQLSelect {
-    fn to_string(&self) -> String {
-        let mut s = format!(
+impl fmt::Display for SQLSelect {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
             "SELECT{} {}",
             if self.distinct { " DISTINCT" } else { "" },
-            comma_separated_string(&self.projection)
-        );
+            display_comma_separated(&self.projection)
+        )?;
         if let Some(ref relation) = self.relation {
-            s += &format!(" FROM {}", relation.to_string());
+            write!(f, " FROM {}", relation)?;
         }
         for join in &self.joins {
-            s += &join.to_string();
+            write!(f, "{}", join)?;
         }
         if let Some(ref selection) = self.selection {
-            s += &format!(" WHERE {}", selection.to_string());
+            write!(f, " WHERE {}", selection)?;
         }
         if !self.group_by.is_empty() {
-            s += &format!(" GROUP BY {}", comma_separated_string(&self.group_by));
+            write!(f, " GROUP BY {}", display_comma_separated(&self.group_by))?;
         }
         if let Some(ref having) = self.having {
-            s += &format!(" HAVING {}", having.to_string());
+            write!(f, " HAVING {}", having)?;
         }
-        s
+        Ok(())
+    }
+}
+
+impl Spanned for SQLSelect {
+    fn span(&self) -> Span {
+        self.span
     }
 }
 
@@ -155,50 +210,75 @@ impl ToString for SQLSelect {
 /// of the columns returned by the query. The parser does not validate that the
 /// number of columns in the query matches the number of columns in the query.
 #[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Cte {
     pub alias: SQLIdent,
     pub query: SQLQuery,
     pub renamed_columns: Vec<SQLIdent>,
+    pub span: Span,
 }
 
-impl ToString for Cte {
-    fn to_string(&self) -> String {
-        let mut s = self.alias.clone();
+impl fmt::Display for Cte {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.alias)?;
         if !self.renamed_columns.is_empty() {
-            s += &format!(" ({})", comma_separated_string(&self.renamed_columns));
+            write!(f, " ({})", display_comma_separated(&self.renamed_columns))?;
         }
-        s + &format!(" AS ({})", self.query.to_string())
+        write!(f, " AS ({})", self.query)
+    }
+}
+
+impl Spanned for Cte {
+    fn span(&self) -> Span {
+        self.span
     }
 }
 
 /// One item of the comma-separated list following `SELECT`
 #[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum SQLSelectItem {
     /// Any expression, not followed by `[ AS ] alias`
     UnnamedExpression(ASTNode),
     /// An expression, followed by `[ AS ] alias`
-    ExpressionWithAlias { expr: ASTNode, alias: SQLIdent },
+    ExpressionWithAlias {
+        expr: ASTNode,
+        alias: SQLIdent,
+        span: Span,
+    },
     /// `alias.*` or even `schema.table.*`
-    QualifiedWildcard(SQLObjectName),
+    QualifiedWildcard(SQLObjectName, Span),
     /// An unqualified `*`
-    Wildcard,
+    Wildcard(Span),
 }
 
-impl ToString for SQLSelectItem {
-    fn to_string(&self) -> String {
+impl fmt::Display for SQLSelectItem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match &self {
-            SQLSelectItem::UnnamedExpression(expr) => expr.to_string(),
-            SQLSelectItem::ExpressionWithAlias { expr, alias } => {
-                format!("{} AS {}", expr.to_string(), alias)
+            SQLSelectItem::UnnamedExpression(expr) => write!(f, "{}", expr),
+            SQLSelectItem::ExpressionWithAlias { expr, alias, .. } => {
+                write!(f, "{} AS {}", expr, alias)
             }
-            SQLSelectItem::QualifiedWildcard(prefix) => format!("{}.*", prefix.to_string()),
-            SQLSelectItem::Wildcard => "*".to_string(),
+            SQLSelectItem::QualifiedWildcard(prefix, _) => write!(f, "{}.*", prefix),
+            SQLSelectItem::Wildcard(_) => f.write_str("*"),
+        }
+    }
+}
+
+impl Spanned for SQLSelectItem {
+    fn span(&self) -> Span {
+        match self {
+            SQLSelectItem::UnnamedExpression(expr) => expr.span(),
+            SQLSelectItem::ExpressionWithAlias { span, .. }
+            | SQLSelectItem::QualifiedWildcard(_, span)
+            | SQLSelectItem::Wildcard(span) => *span,
         }
     }
 }
 
 /// A table name or a parenthesized subquery with an optional alias
 #[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum TableFactor {
     Table {
         name: SQLObjectName,
@@ -209,123 +289,160 @@ pub enum TableFactor {
         args: Vec<ASTNode>,
         /// MSSQL-specific `WITH (...)` hints such as NOLOCK.
         with_hints: Vec<ASTNode>,
+        span: Span,
     },
     Derived {
         lateral: bool,
         subquery: Box<SQLQuery>,
         alias: Option<TableAlias>,
+        span: Span,
     },
 }
 
-impl ToString for TableFactor {
-    fn to_string(&self) -> String {
+impl fmt::Display for TableFactor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             TableFactor::Table {
                 name,
                 alias,
                 args,
                 with_hints,
+                ..
             } => {
-                let mut s = name.to_string();
+                write!(f, "{}", name)?;
                 if !args.is_empty() {
-                    s += &format!("({})", comma_separated_string(args))
-                };
+                    write!(f, "({})", display_comma_separated(args))?;
+                }
                 if let Some(alias) = alias {
-                    s += &format!(" AS {}", alias.to_string());
+                    write!(f, " AS {}", alias)?;
                 }
                 if !with_hints.is_empty() {
-                    s += &format!(" WITH ({})", comma_separated_string(with_hints));
+                    write!(f, " WITH ({})", display_comma_separated(with_hints))?;
                 }
-                s
+                Ok(())
             }
             TableFactor::Derived {
                 lateral,
                 subquery,
                 alias,
+                ..
             } => {
-                let mut s = String::new();
                 if *lateral {
-                    s += "LATERAL ";
+                    f.write_str("LATERAL ")?;
                 }
-                s += &format!("({})", subquery.to_string());
+                write!(f, "({})", subquery)?;
                 if let Some(alias) = alias {
-                    s += &format!(" AS {}", alias.to_string());
+                    write!(f, " AS {}", alias)?;
                 }
-                s
+                Ok(())
             }
         }
     }
 }
 
+impl Spanned for TableFactor {
+    fn span(&self) -> Span {
+        match self {
+            TableFactor::Table { span, .. } | TableFactor::Derived { span, .. } => *span,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct TableAlias {
     pub name: SQLIdent,
     pub columns: Vec<SQLIdent>,
+    pub span: Span,
 }
 
-impl ToString for TableAlias {
-    fn to_string(&self) -> String {
-        let mut s = self.name.clone();
+impl fmt::Display for TableAlias {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name)?;
         if !self.columns.is_empty() {
-            s += &format!(" ({})", comma_separated_string(&self.columns));
+            write!(f, " ({})", display_comma_separated(&self.columns))?;
         }
-        s
+        Ok(())
+    }
+}
+
+impl Spanned for TableAlias {
+    fn span(&self) -> Span {
+        self.span
     }
 }
 
 #[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Join {
     pub relation: TableFactor,
     pub join_operator: JoinOperator,
+    pub span: Span,
 }
 
-impl ToString for Join {
-    fn to_string(&self) -> String {
-        fn prefix(constraint: &JoinConstraint) -> String {
+impl fmt::Display for Join {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fn prefix(constraint: &JoinConstraint) -> &'static str {
             match constraint {
-                JoinConstraint::Natural => "NATURAL ".to_string(),
-                _ => "".to_string(),
+                JoinConstraint::Natural => "NATURAL ",
+                _ => "",
             }
         }
-        fn suffix(constraint: &JoinConstraint) -> String {
-            match constraint {
-                JoinConstraint::On(expr) => format!(" ON {}", expr.to_string()),
-                JoinConstraint::Using(attrs) => format!(" USING({})", attrs.join(", ")),
-                _ => "".to_string(),
+        struct Suffix<'a>(&'a JoinConstraint);
+        impl<'a> fmt::Display for Suffix<'a> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                match self.0 {
+                    JoinConstraint::On(expr) => write!(f, " ON {}", expr),
+                    JoinConstraint::Using(attrs) => {
+                        write!(f, " USING({})", display_comma_separated(attrs))
+                    }
+                    _ => Ok(()),
+                }
             }
         }
         match &self.join_operator {
-            JoinOperator::Inner(constraint) => format!(
+            JoinOperator::Inner(constraint) => write!(
+                f,
                 " {}JOIN {}{}",
                 prefix(constraint),
-                self.relation.to_string(),
-                suffix(constraint)
+                self.relation,
+                Suffix(constraint)
             ),
-            JoinOperator::Cross => format!(" CROSS JOIN {}", self.relation.to_string()),
-            JoinOperator::Implicit => format!(", {}", self.relation.to_string()),
-            JoinOperator::LeftOuter(constraint) => format!(
+            JoinOperator::Cross => write!(f, " CROSS JOIN {}", self.relation),
+            JoinOperator::Implicit => write!(f, ", {}", self.relation),
+            JoinOperator::LeftOuter(constraint) => write!(
+                f,
                 " {}LEFT JOIN {}{}",
                 prefix(constraint),
-                self.relation.to_string(),
-                suffix(constraint)
+                self.relation,
+                Suffix(constraint)
             ),
-            JoinOperator::RightOuter(constraint) => format!(
+            JoinOperator::RightOuter(constraint) => write!(
+                f,
                 " {}RIGHT JOIN {}{}",
                 prefix(constraint),
-                self.relation.to_string(),
-                suffix(constraint)
+                self.relation,
+                Suffix(constraint)
             ),
-            JoinOperator::FullOuter(constraint) => format!(
+            JoinOperator::FullOuter(constraint) => write!(
+                f,
                 " {}FULL JOIN {}{}",
                 prefix(constraint),
-                self.relation.to_string(),
-                suffix(constraint)
+                self.relation,
+                Suffix(constraint)
             ),
         }
     }
 }
 
+impl Spanned for Join {
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum JoinOperator {
     Inner(JoinConstraint),
     LeftOuter(JoinConstraint),
@@ -335,7 +452,9 @@ pub enum JoinOperator {
     Cross,
 }
 
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum JoinConstraint {
     On(ASTNode),
     Using(Vec<SQLIdent>),
@@ -344,54 +463,82 @@ pub enum JoinConstraint {
 
 /// SQL ORDER BY expression
 #[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SQLOrderByExpr {
     pub expr: ASTNode,
     pub asc: Option<bool>,
+    /// `NULLS FIRST` (`Some(true)`) or `NULLS LAST` (`Some(false)`)
+    pub nulls_first: Option<bool>,
+    pub span: Span,
 }
 
-impl ToString for SQLOrderByExpr {
-    fn to_string(&self) -> String {
+impl fmt::Display for SQLOrderByExpr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self.asc {
-            Some(true) => format!("{} ASC", self.expr.to_string()),
-            Some(false) => format!("{} DESC", self.expr.to_string()),
-            None => self.expr.to_string(),
+            Some(true) => write!(f, "{} ASC", self.expr)?,
+            Some(false) => write!(f, "{} DESC", self.expr)?,
+            None => write!(f, "{}", self.expr)?,
+        }
+        match self.nulls_first {
+            Some(true) => f.write_str(" NULLS FIRST"),
+            Some(false) => f.write_str(" NULLS LAST"),
+            None => Ok(()),
         }
     }
 }
 
+impl Spanned for SQLOrderByExpr {
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Fetch {
     pub with_ties: bool,
     pub percent: bool,
     pub quantity: Option<ASTNode>,
+    pub span: Span,
 }
 
-impl ToString for Fetch {
-    fn to_string(&self) -> String {
+impl fmt::Display for Fetch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let extension = if self.with_ties { "WITH TIES" } else { "ONLY" };
         if let Some(ref quantity) = self.quantity {
             let percent = if self.percent { " PERCENT" } else { "" };
-            format!(
-                "FETCH FIRST {}{} ROWS {}",
-                quantity.to_string(),
-                percent,
-                extension
-            )
+            write!(f, "FETCH FIRST {}{} ROWS {}", quantity, percent, extension)
         } else {
-            format!("FETCH FIRST ROWS {}", extension)
+            write!(f, "FETCH FIRST ROWS {}", extension)
         }
     }
 }
 
+impl Spanned for Fetch {
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+/// `VALUES (...), (...)`: the rows, followed by the span of the whole list
 #[derive(Debug, Clone, PartialEq, Hash)]
-pub struct SQLValues(pub Vec<Vec<ASTNode>>);
-
-impl ToString for SQLValues {
-    fn to_string(&self) -> String {
-        let rows = self
-            .0
-            .iter()
-            .map(|row| format!("({})", comma_separated_string(row)));
-        format!("VALUES {}", comma_separated_string(rows))
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SQLValues(pub Vec<Vec<ASTNode>>, pub Span);
+
+impl fmt::Display for SQLValues {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("VALUES ")?;
+        let mut delim = "";
+        for row in &self.0 {
+            write!(f, "{}({})", delim, display_comma_separated(row))?;
+            delim = ", ";
+        }
+        Ok(())
+    }
+}
+
+impl Spanned for SQLValues {
+    fn span(&self) -> Span {
+        self.1
     }
 }
@@ -1,10 +1,17 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
 /// SQL Operator
 #[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum SQLOperator {
     Plus,
     Minus,
     Multiply,
     Divide,
+    /// MySQL's integer division, `DIV`
+    IntegerDivide,
     Modulus,
     Gt,
     Lt,
@@ -12,32 +19,38 @@ pub enum SQLOperator {
     LtEq,
     Eq,
     NotEq,
+    /// MySQL's NULL-safe equality, `<=>`
+    Spaceship,
     And,
     Or,
+    Xor,
     Not,
     Like,
     NotLike,
 }
 
-impl ToString for SQLOperator {
-    fn to_string(&self) -> String {
-        match self {
-            SQLOperator::Plus => "+".to_string(),
-            SQLOperator::Minus => "-".to_string(),
-            SQLOperator::Multiply => "*".to_string(),
-            SQLOperator::Divide => "/".to_string(),
-            SQLOperator::Modulus => "%".to_string(),
-            SQLOperator::Gt => ">".to_string(),
-            SQLOperator::Lt => "<".to_string(),
-            SQLOperator::GtEq => ">=".to_string(),
-            SQLOperator::LtEq => "<=".to_string(),
-            SQLOperator::Eq => "=".to_string(),
-            SQLOperator::NotEq => "<>".to_string(),
-            SQLOperator::And => "AND".to_string(),
-            SQLOperator::Or => "OR".to_string(),
-            SQLOperator::Not => "NOT".to_string(),
-            SQLOperator::Like => "LIKE".to_string(),
-            SQLOperator::NotLike => "NOT LIKE".to_string(),
-        }
+impl fmt::Display for SQLOperator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            SQLOperator::Plus => "+",
+            SQLOperator::Minus => "-",
+            SQLOperator::Multiply => "*",
+            SQLOperator::Divide => "/",
+            SQLOperator::IntegerDivide => "DIV",
+            SQLOperator::Modulus => "%",
+            SQLOperator::Gt => ">",
+            SQLOperator::Lt => "<",
+            SQLOperator::GtEq => ">=",
+            SQLOperator::LtEq => "<=",
+            SQLOperator::Eq => "=",
+            SQLOperator::NotEq => "<>",
+            SQLOperator::Spaceship => "<=>",
+            SQLOperator::And => "AND",
+            SQLOperator::Or => "OR",
+            SQLOperator::Xor => "XOR",
+            SQLOperator::Not => "NOT",
+            SQLOperator::Like => "LIKE",
+            SQLOperator::NotLike => "NOT LIKE",
+        })
     }
 }
@@ -1,7 +1,11 @@
-use super::SQLObjectName;
+use super::{display_comma_separated, display_separated, SQLIdent, SQLObjectName};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fmt;
 
 /// SQL data types
 #[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum SQLType {
     /// Fixed-length character type e.g. CHAR(10)
     Char(Option<u64>),
@@ -47,50 +51,71 @@ pub enum SQLType {
     Bytea,
     /// Custom type such as enums
     Custom(SQLObjectName),
+    /// A free-form type name with optional numeric arguments, which SQLite
+    /// derives the column's type affinity from, e.g. `UNSIGNED BIG INT` or
+    /// `NVARCHAR(100)`
+    FreeForm(Vec<SQLIdent>, Vec<u64>),
     /// Arrays
     Array(Box<SQLType>),
+    /// No type at all, in the column definitions of dialects that allow
+    /// omitting it (see `Dialect::supports_untyped_columns`)
+    Unspecified,
 }
 
-impl ToString for SQLType {
-    fn to_string(&self) -> String {
+impl fmt::Display for SQLType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            SQLType::Char(size) => format_type_with_optional_length("char", size),
-            SQLType::Varchar(size) => format_type_with_optional_length("character varying", size),
-            SQLType::Uuid => "uuid".to_string(),
-            SQLType::Clob(size) => format!("clob({})", size),
-            SQLType::Binary(size) => format!("binary({})", size),
-            SQLType::Varbinary(size) => format!("varbinary({})", size),
-            SQLType::Blob(size) => format!("blob({})", size),
+            SQLType::Char(size) => format_type_with_optional_length(f, "char", size),
+            SQLType::Varchar(size) => {
+                format_type_with_optional_length(f, "character varying", size)
+            }
+            SQLType::Uuid => f.write_str("uuid"),
+            SQLType::Clob(size) => write!(f, "clob({})", size),
+            SQLType::Binary(size) => write!(f, "binary({})", size),
+            SQLType::Varbinary(size) => write!(f, "varbinary({})", size),
+            SQLType::Blob(size) => write!(f, "blob({})", size),
             SQLType::Decimal(precision, scale) => {
                 if let Some(scale) = scale {
-                    format!("numeric({},{})", precision.unwrap(), scale)
+                    write!(f, "numeric({},{})", precision.unwrap(), scale)
                 } else {
-                    format_type_with_optional_length("numeric", precision)
+                    format_type_with_optional_length(f, "numeric", precision)
+                }
+            }
+            SQLType::Float(size) => format_type_with_optional_length(f, "float", size),
+            SQLType::SmallInt => f.write_str("smallint"),
+            SQLType::Int => f.write_str("int"),
+            SQLType::BigInt => f.write_str("bigint"),
+            SQLType::Real => f.write_str("real"),
+            SQLType::Double => f.write_str("double"),
+            SQLType::Boolean => f.write_str("boolean"),
+            SQLType::Date => f.write_str("date"),
+            SQLType::Time => f.write_str("time"),
+            SQLType::Timestamp => f.write_str("timestamp"),
+            SQLType::Regclass => f.write_str("regclass"),
+            SQLType::Text => f.write_str("text"),
+            SQLType::Bytea => f.write_str("bytea"),
+            SQLType::Array(ty) => write!(f, "{}[]", ty),
+            SQLType::Unspecified => Ok(()),
+            SQLType::Custom(ty) => write!(f, "{}", ty),
+            SQLType::FreeForm(words, args) => {
+                write!(f, "{}", display_separated(words, " "))?;
+                if !args.is_empty() {
+                    write!(f, "({})", display_comma_separated(args))?;
                 }
+                Ok(())
             }
-            SQLType::Float(size) => format_type_with_optional_length("float", size),
-            SQLType::SmallInt => "smallint".to_string(),
-            SQLType::Int => "int".to_string(),
-            SQLType::BigInt => "bigint".to_string(),
-            SQLType::Real => "real".to_string(),
-            SQLType::Double => "double".to_string(),
-            SQLType::Boolean => "boolean".to_string(),
-            SQLType::Date => "date".to_string(),
-            SQLType::Time => "time".to_string(),
-            SQLType::Timestamp => "timestamp".to_string(),
-            SQLType::Regclass => "regclass".to_string(),
-            SQLType::Text => "text".to_string(),
-            SQLType::Bytea => "bytea".to_string(),
-            SQLType::Array(ty) => format!("{}[]", ty.to_string()),
-            SQLType::Custom(ty) => ty.to_string(),
         }
     }
 }
 
-fn format_type_with_optional_length(sql_type: &str, len: &Option<u64>) -> String {
-    let mut s = sql_type.to_string();
+fn format_type_with_optional_length(
+    f: &mut fmt::Formatter,
+    sql_type: &'static str,
+    len: &Option<u64>,
+) -> fmt::Result {
+    f.write_str(sql_type)?;
     if let Some(len) = len {
-        s += &format!("({})", len);
+        write!(f, "({})", len)?;
     }
-    s
+    Ok(())
 }
@@ -1,7 +1,12 @@
+use crate::sqltokenizer::DollarQuotedString;
 use ordered_float::OrderedFloat;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fmt;
 
 /// Primitive SQL values such as number and string
 #[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Value {
     /// Unsigned integer value
     Long(u64),
@@ -13,6 +18,8 @@ pub enum Value {
     NationalStringLiteral(String),
     /// X'hex value'
     HexStringLiteral(String),
+    /// $$string value$$ or $tag$string value$tag$ (PostgreSQL)
+    DollarQuotedString(DollarQuotedString),
     /// Boolean value true or false
     Boolean(bool),
     /// Date literals
@@ -25,31 +32,39 @@ pub enum Value {
     Null,
 }
 
-impl ToString for Value {
-    fn to_string(&self) -> String {
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            Value::Long(v) => v.to_string(),
-            Value::Double(v) => v.to_string(),
-            Value::SingleQuotedString(v) => format!("'{}'", escape_single_quote_string(v)),
-            Value::NationalStringLiteral(v) => format!("N'{}'", v),
-            Value::HexStringLiteral(v) => format!("X'{}'", v),
-            Value::Boolean(v) => v.to_string(),
-            Value::Date(v) => format!("DATE '{}'", escape_single_quote_string(v)),
-            Value::Time(v) => format!("TIME '{}'", escape_single_quote_string(v)),
-            Value::Timestamp(v) => format!("TIMESTAMP '{}'", escape_single_quote_string(v)),
-            Value::Null => "NULL".to_string(),
+            Value::Long(v) => write!(f, "{}", v),
+            Value::Double(v) => write!(f, "{}", v),
+            Value::SingleQuotedString(v) => write!(f, "'{}'", escape_single_quote_string(v)),
+            Value::NationalStringLiteral(v) => write!(f, "N'{}'", escape_single_quote_string(v)),
+            Value::HexStringLiteral(v) => write!(f, "X'{}'", v),
+            Value::DollarQuotedString(v) => write!(f, "{}", v),
+            Value::Boolean(v) => write!(f, "{}", v),
+            Value::Date(v) => write!(f, "DATE '{}'", escape_single_quote_string(v)),
+            Value::Time(v) => write!(f, "TIME '{}'", escape_single_quote_string(v)),
+            Value::Timestamp(v) => write!(f, "TIMESTAMP '{}'", escape_single_quote_string(v)),
+            Value::Null => f.write_str("NULL"),
         }
     }
 }
 
-fn escape_single_quote_string(s: &str) -> String {
-    let mut escaped = String::new();
-    for c in s.chars() {
-        if c == '\'' {
-            escaped.push_str("\'\'");
-        } else {
-            escaped.push(c);
+struct EscapeSingleQuoteString<'a>(&'a str);
+
+impl<'a> fmt::Display for EscapeSingleQuoteString<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for c in self.0.chars() {
+            if c == '\'' {
+                f.write_str("\'\'")?;
+            } else {
+                write!(f, "{}", c)?;
+            }
         }
+        Ok(())
     }
-    escaped
+}
+
+fn escape_single_quote_string(s: &str) -> EscapeSingleQuoteString<'_> {
+    EscapeSingleQuoteString(s)
 }
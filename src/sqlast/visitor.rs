@@ -0,0 +1,820 @@
+//! Traversal of the AST: the `Visitor` and `VisitorMut` traits let callers
+//! hook into the nodes they are interested in, while the `Visit` and
+//! `VisitMut` traits (implemented for every AST type) take care of walking
+//! the rest of the tree.
+//!
+//! Each hook is called twice for a node: `pre_visit_*` before its children
+//! are visited and `post_visit_*` after. Returning `ControlFlow::Break`
+//! from any hook stops the traversal immediately, and the `Break` value is
+//! returned from the `visit` call.
+//!
+//! ```
+//! # use sqlparser::dialect::GenericSqlDialect;
+//! # use sqlparser::sqlparser::Parser;
+//! use sqlparser::sqlast::*;
+//! use std::ops::ControlFlow;
+//!
+//! /// Collects the names of all tables in FROM / JOIN clauses
+//! struct Tables(Vec<String>);
+//!
+//! impl Visitor for Tables {
+//!     type Break = ();
+//!
+//!     fn pre_visit_table_factor(&mut self, table: &TableFactor) -> ControlFlow<()> {
+//!         if let TableFactor::Table { name, .. } = table {
+//!             self.0.push(name.to_string());
+//!         }
+//!         ControlFlow::Continue(())
+//!     }
+//! }
+//!
+//! let sql = "SELECT * FROM a JOIN (SELECT * FROM b) AS c ON a.x = c.x".to_string();
+//! let statements = Parser::parse_sql(&GenericSqlDialect {}, sql).unwrap();
+//! let mut tables = Tables(vec![]);
+//! let _ = statements.visit(&mut tables);
+//! assert_eq!(vec!["a", "b"], tables.0);
+//! ```
+use std::ops::ControlFlow;
+
+use super::*;
+
+macro_rules! visitor_hooks {
+    ($($ref:tt)+) => {
+        fn pre_visit_statement(
+            &mut self,
+            _statement: $($ref)+ SQLStatement,
+        ) -> ControlFlow<Self::Break> {
+            ControlFlow::Continue(())
+        }
+
+        fn post_visit_statement(
+            &mut self,
+            _statement: $($ref)+ SQLStatement,
+        ) -> ControlFlow<Self::Break> {
+            ControlFlow::Continue(())
+        }
+
+        fn pre_visit_query(&mut self, _query: $($ref)+ SQLQuery) -> ControlFlow<Self::Break> {
+            ControlFlow::Continue(())
+        }
+
+        fn post_visit_query(&mut self, _query: $($ref)+ SQLQuery) -> ControlFlow<Self::Break> {
+            ControlFlow::Continue(())
+        }
+
+        fn pre_visit_set_expr(
+            &mut self,
+            _set_expr: $($ref)+ SQLSetExpr,
+        ) -> ControlFlow<Self::Break> {
+            ControlFlow::Continue(())
+        }
+
+        fn post_visit_set_expr(
+            &mut self,
+            _set_expr: $($ref)+ SQLSetExpr,
+        ) -> ControlFlow<Self::Break> {
+            ControlFlow::Continue(())
+        }
+
+        fn pre_visit_select(&mut self, _select: $($ref)+ SQLSelect) -> ControlFlow<Self::Break> {
+            ControlFlow::Continue(())
+        }
+
+        fn post_visit_select(&mut self, _select: $($ref)+ SQLSelect) -> ControlFlow<Self::Break> {
+            ControlFlow::Continue(())
+        }
+
+        fn pre_visit_table_factor(
+            &mut self,
+            _table_factor: $($ref)+ TableFactor,
+        ) -> ControlFlow<Self::Break> {
+            ControlFlow::Continue(())
+        }
+
+        fn post_visit_table_factor(
+            &mut self,
+            _table_factor: $($ref)+ TableFactor,
+        ) -> ControlFlow<Self::Break> {
+            ControlFlow::Continue(())
+        }
+
+        fn pre_visit_join(&mut self, _join: $($ref)+ Join) -> ControlFlow<Self::Break> {
+            ControlFlow::Continue(())
+        }
+
+        fn post_visit_join(&mut self, _join: $($ref)+ Join) -> ControlFlow<Self::Break> {
+            ControlFlow::Continue(())
+        }
+
+        fn pre_visit_expr(&mut self, _expr: $($ref)+ ASTNode) -> ControlFlow<Self::Break> {
+            ControlFlow::Continue(())
+        }
+
+        fn post_visit_expr(&mut self, _expr: $($ref)+ ASTNode) -> ControlFlow<Self::Break> {
+            ControlFlow::Continue(())
+        }
+
+        fn pre_visit_data_type(
+            &mut self,
+            _data_type: $($ref)+ SQLType,
+        ) -> ControlFlow<Self::Break> {
+            ControlFlow::Continue(())
+        }
+
+        fn post_visit_data_type(
+            &mut self,
+            _data_type: $($ref)+ SQLType,
+        ) -> ControlFlow<Self::Break> {
+            ControlFlow::Continue(())
+        }
+
+        fn pre_visit_window_spec(
+            &mut self,
+            _window_spec: $($ref)+ SQLWindowSpec,
+        ) -> ControlFlow<Self::Break> {
+            ControlFlow::Continue(())
+        }
+
+        fn post_visit_window_spec(
+            &mut self,
+            _window_spec: $($ref)+ SQLWindowSpec,
+        ) -> ControlFlow<Self::Break> {
+            ControlFlow::Continue(())
+        }
+    };
+}
+
+/// Read-only hooks into a traversal of the AST started by `Visit::visit`.
+/// All hooks default to doing nothing, so implementors only need to
+/// override the ones for the nodes they care about.
+pub trait Visitor {
+    /// The value a hook can stop the traversal with
+    type Break;
+
+    visitor_hooks!(&);
+}
+
+/// Like `Visitor`, but the hooks can modify the nodes they are called for.
+/// The changes made in a `pre_visit_*` hook are seen by the traversal of
+/// the node's children.
+pub trait VisitorMut {
+    /// The value a hook can stop the traversal with
+    type Break;
+
+    visitor_hooks!(&mut);
+}
+
+/// An AST node that can be traversed by a `Visitor`
+pub trait Visit {
+    fn visit<V: Visitor>(&self, visitor: &mut V) -> ControlFlow<V::Break>;
+}
+
+/// An AST node that can be traversed (and modified) by a `VisitorMut`
+pub trait VisitMut {
+    fn visit_mut<V: VisitorMut>(&mut self, visitor: &mut V) -> ControlFlow<V::Break>;
+}
+
+/// Implements `$Visit` for all AST types. The same code serves for `Visit`
+/// and `VisitMut`: matching on `self` binds children by reference or by
+/// mutable reference, depending on the type of `self`.
+macro_rules! impl_visit {
+    ($Visit:ident, $visit:ident, $Visitor:ident, $($ref:tt)+) => {
+        impl<T: $Visit> $Visit for Box<T> {
+            fn $visit<V: $Visitor>($($ref)+ self, visitor: &mut V) -> ControlFlow<V::Break> {
+                (**self).$visit(visitor)
+            }
+        }
+
+        impl<T: $Visit> $Visit for Option<T> {
+            fn $visit<V: $Visitor>($($ref)+ self, visitor: &mut V) -> ControlFlow<V::Break> {
+                if let Some(node) = self {
+                    node.$visit(visitor)?;
+                }
+                ControlFlow::Continue(())
+            }
+        }
+
+        impl<T: $Visit> $Visit for Vec<T> {
+            fn $visit<V: $Visitor>($($ref)+ self, visitor: &mut V) -> ControlFlow<V::Break> {
+                for node in self {
+                    node.$visit(visitor)?;
+                }
+                ControlFlow::Continue(())
+            }
+        }
+
+        impl $Visit for SQLStatement {
+            fn $visit<V: $Visitor>($($ref)+ self, visitor: &mut V) -> ControlFlow<V::Break> {
+                visitor.pre_visit_statement(self)?;
+                match self {
+                    SQLStatement::SQLQuery(query) => query.$visit(visitor)?,
+                    SQLStatement::SQLInsert {
+                        source,
+                        on,
+                        returning,
+                        ..
+                    } => {
+                        source.$visit(visitor)?;
+                        on.$visit(visitor)?;
+                        returning.$visit(visitor)?;
+                    }
+                    SQLStatement::SQLCopy { .. } => {}
+                    SQLStatement::SQLUpdate {
+                        assignments,
+                        from,
+                        joins,
+                        selection,
+                        returning,
+                        ..
+                    } => {
+                        assignments.$visit(visitor)?;
+                        from.$visit(visitor)?;
+                        joins.$visit(visitor)?;
+                        selection.$visit(visitor)?;
+                        returning.$visit(visitor)?;
+                    }
+                    SQLStatement::SQLDelete {
+                        using,
+                        joins,
+                        selection,
+                        returning,
+                        ..
+                    } => {
+                        using.$visit(visitor)?;
+                        joins.$visit(visitor)?;
+                        selection.$visit(visitor)?;
+                        returning.$visit(visitor)?;
+                    }
+                    SQLStatement::SQLMerge {
+                        table,
+                        source,
+                        on,
+                        clauses,
+                        ..
+                    } => {
+                        table.$visit(visitor)?;
+                        source.$visit(visitor)?;
+                        on.$visit(visitor)?;
+                        clauses.$visit(visitor)?;
+                    }
+                    SQLStatement::SQLCreateView { query, .. } => query.$visit(visitor)?,
+                    SQLStatement::SQLCreateTable {
+                        columns,
+                        constraints,
+                        table_options,
+                        query,
+                        ..
+                    } => {
+                        columns.$visit(visitor)?;
+                        constraints.$visit(visitor)?;
+                        table_options.$visit(visitor)?;
+                        query.$visit(visitor)?;
+                    }
+                    SQLStatement::SQLCreateIndex {
+                        columns, predicate, ..
+                    } => {
+                        columns.$visit(visitor)?;
+                        predicate.$visit(visitor)?;
+                    }
+                    SQLStatement::SQLCreateSchema { .. } | SQLStatement::SQLCreateDatabase { .. } => {}
+                    SQLStatement::SQLCreateFunction {
+                        args,
+                        returns,
+                        attributes,
+                        ..
+                    } => {
+                        args.$visit(visitor)?;
+                        returns.$visit(visitor)?;
+                        attributes.$visit(visitor)?;
+                    }
+                    SQLStatement::SQLCreateSequence { options, .. }
+                    | SQLStatement::SQLAlterSequence { options, .. } => options.$visit(visitor)?,
+                    SQLStatement::SQLAlterTable { operations, .. } => operations.$visit(visitor)?,
+                    SQLStatement::SQLDrop { .. } => {}
+                    SQLStatement::SQLStartTransaction { .. }
+                    | SQLStatement::SQLSetTransaction { .. }
+                    | SQLStatement::SQLCommit { .. }
+                    | SQLStatement::SQLRollback { .. }
+                    | SQLStatement::SQLSavepoint { .. }
+                    | SQLStatement::SQLReleaseSavepoint { .. } => {}
+                    SQLStatement::SQLPragma { value, .. } => value.$visit(visitor)?,
+                    SQLStatement::SQLAttachDatabase {
+                        database_file_name,
+                        ..
+                    } => database_file_name.$visit(visitor)?,
+                }
+                visitor.post_visit_statement(self)
+            }
+        }
+
+        impl $Visit for SQLQuery {
+            fn $visit<V: $Visitor>($($ref)+ self, visitor: &mut V) -> ControlFlow<V::Break> {
+                visitor.pre_visit_query(self)?;
+                self.ctes.$visit(visitor)?;
+                self.body.$visit(visitor)?;
+                self.order_by.$visit(visitor)?;
+                self.limit.$visit(visitor)?;
+                self.offset.$visit(visitor)?;
+                self.fetch.$visit(visitor)?;
+                visitor.post_visit_query(self)
+            }
+        }
+
+        impl $Visit for Offset {
+            fn $visit<V: $Visitor>($($ref)+ self, visitor: &mut V) -> ControlFlow<V::Break> {
+                self.value.$visit(visitor)
+            }
+        }
+
+        impl $Visit for Cte {
+            fn $visit<V: $Visitor>($($ref)+ self, visitor: &mut V) -> ControlFlow<V::Break> {
+                self.query.$visit(visitor)
+            }
+        }
+
+        impl $Visit for SQLSetExpr {
+            fn $visit<V: $Visitor>($($ref)+ self, visitor: &mut V) -> ControlFlow<V::Break> {
+                visitor.pre_visit_set_expr(self)?;
+                match self {
+                    SQLSetExpr::Select(select) => select.$visit(visitor)?,
+                    SQLSetExpr::Query(query) => query.$visit(visitor)?,
+                    SQLSetExpr::SetOperation { left, right, .. } => {
+                        left.$visit(visitor)?;
+                        right.$visit(visitor)?;
+                    }
+                    SQLSetExpr::Values(values) => values.0.$visit(visitor)?,
+                }
+                visitor.post_visit_set_expr(self)
+            }
+        }
+
+        impl $Visit for SQLSelect {
+            fn $visit<V: $Visitor>($($ref)+ self, visitor: &mut V) -> ControlFlow<V::Break> {
+                visitor.pre_visit_select(self)?;
+                self.projection.$visit(visitor)?;
+                self.relation.$visit(visitor)?;
+                self.joins.$visit(visitor)?;
+                self.selection.$visit(visitor)?;
+                self.group_by.$visit(visitor)?;
+                self.having.$visit(visitor)?;
+                visitor.post_visit_select(self)
+            }
+        }
+
+        impl $Visit for SQLSelectItem {
+            fn $visit<V: $Visitor>($($ref)+ self, visitor: &mut V) -> ControlFlow<V::Break> {
+                match self {
+                    SQLSelectItem::UnnamedExpression(expr)
+                    | SQLSelectItem::ExpressionWithAlias { expr, .. } => expr.$visit(visitor),
+                    SQLSelectItem::QualifiedWildcard(..) | SQLSelectItem::Wildcard(_) => {
+                        ControlFlow::Continue(())
+                    }
+                }
+            }
+        }
+
+        impl $Visit for TableFactor {
+            fn $visit<V: $Visitor>($($ref)+ self, visitor: &mut V) -> ControlFlow<V::Break> {
+                visitor.pre_visit_table_factor(self)?;
+                match self {
+                    TableFactor::Table {
+                        args, with_hints, ..
+                    } => {
+                        args.$visit(visitor)?;
+                        with_hints.$visit(visitor)?;
+                    }
+                    TableFactor::Derived { subquery, .. } => subquery.$visit(visitor)?,
+                }
+                visitor.post_visit_table_factor(self)
+            }
+        }
+
+        impl $Visit for Join {
+            fn $visit<V: $Visitor>($($ref)+ self, visitor: &mut V) -> ControlFlow<V::Break> {
+                visitor.pre_visit_join(self)?;
+                self.relation.$visit(visitor)?;
+                match $($ref)+ self.join_operator {
+                    JoinOperator::Inner(constraint)
+                    | JoinOperator::LeftOuter(constraint)
+                    | JoinOperator::RightOuter(constraint)
+                    | JoinOperator::FullOuter(constraint) => {
+                        if let JoinConstraint::On(expr) = constraint {
+                            expr.$visit(visitor)?;
+                        }
+                    }
+                    JoinOperator::Implicit | JoinOperator::Cross => {}
+                }
+                visitor.post_visit_join(self)
+            }
+        }
+
+        impl $Visit for SQLOrderByExpr {
+            fn $visit<V: $Visitor>($($ref)+ self, visitor: &mut V) -> ControlFlow<V::Break> {
+                self.expr.$visit(visitor)
+            }
+        }
+
+        impl $Visit for Fetch {
+            fn $visit<V: $Visitor>($($ref)+ self, visitor: &mut V) -> ControlFlow<V::Break> {
+                self.quantity.$visit(visitor)
+            }
+        }
+
+        impl $Visit for ASTNode {
+            fn $visit<V: $Visitor>($($ref)+ self, visitor: &mut V) -> ControlFlow<V::Break> {
+                visitor.pre_visit_expr(self)?;
+                match self {
+                    ASTNode::SQLIdentifier(..)
+                    | ASTNode::SQLWildcard(_)
+                    | ASTNode::SQLQualifiedWildcard(..)
+                    | ASTNode::SQLCompoundIdentifier(..)
+                    | ASTNode::SQLValue(..)
+                    | ASTNode::SQLPlaceholder(..) => {}
+                    ASTNode::SQLIsNull(expr, _)
+                    | ASTNode::SQLIsNotNull(expr, _)
+                    | ASTNode::SQLNested(expr, _)
+                    | ASTNode::SQLExtract { expr, .. }
+                    | ASTNode::SQLCollate { expr, .. }
+                    | ASTNode::SQLUnary { expr, .. } => expr.$visit(visitor)?,
+                    ASTNode::SQLInList { expr, list, .. } => {
+                        expr.$visit(visitor)?;
+                        list.$visit(visitor)?;
+                    }
+                    ASTNode::SQLInSubquery { expr, subquery, .. } => {
+                        expr.$visit(visitor)?;
+                        subquery.$visit(visitor)?;
+                    }
+                    ASTNode::SQLBetween {
+                        expr, low, high, ..
+                    } => {
+                        expr.$visit(visitor)?;
+                        low.$visit(visitor)?;
+                        high.$visit(visitor)?;
+                    }
+                    ASTNode::SQLBinaryExpr { left, right, .. } => {
+                        left.$visit(visitor)?;
+                        right.$visit(visitor)?;
+                    }
+                    ASTNode::SQLCast {
+                        expr, data_type, ..
+                    } => {
+                        expr.$visit(visitor)?;
+                        data_type.$visit(visitor)?;
+                    }
+                    ASTNode::SQLFunction(function) => function.$visit(visitor)?,
+                    ASTNode::SQLCase {
+                        operand,
+                        conditions,
+                        results,
+                        else_result,
+                        ..
+                    } => {
+                        operand.$visit(visitor)?;
+                        conditions.$visit(visitor)?;
+                        results.$visit(visitor)?;
+                        else_result.$visit(visitor)?;
+                    }
+                    ASTNode::SQLExists(query, _) | ASTNode::SQLSubquery(query, _) => {
+                        query.$visit(visitor)?
+                    }
+                }
+                visitor.post_visit_expr(self)
+            }
+        }
+
+        impl $Visit for SQLFunction {
+            fn $visit<V: $Visitor>($($ref)+ self, visitor: &mut V) -> ControlFlow<V::Break> {
+                self.args.$visit(visitor)?;
+                self.over.$visit(visitor)
+            }
+        }
+
+        impl $Visit for SQLWindowSpec {
+            fn $visit<V: $Visitor>($($ref)+ self, visitor: &mut V) -> ControlFlow<V::Break> {
+                visitor.pre_visit_window_spec(self)?;
+                self.partition_by.$visit(visitor)?;
+                self.order_by.$visit(visitor)?;
+                visitor.post_visit_window_spec(self)
+            }
+        }
+
+        impl $Visit for SQLType {
+            fn $visit<V: $Visitor>($($ref)+ self, visitor: &mut V) -> ControlFlow<V::Break> {
+                visitor.pre_visit_data_type(self)?;
+                if let SQLType::Array(element_type) = self {
+                    element_type.$visit(visitor)?;
+                }
+                visitor.post_visit_data_type(self)
+            }
+        }
+
+        impl $Visit for SQLAssignment {
+            fn $visit<V: $Visitor>($($ref)+ self, visitor: &mut V) -> ControlFlow<V::Break> {
+                self.value.$visit(visitor)
+            }
+        }
+
+        impl $Visit for OnInsert {
+            fn $visit<V: $Visitor>($($ref)+ self, visitor: &mut V) -> ControlFlow<V::Break> {
+                match self {
+                    OnInsert::DuplicateKeyUpdate(assignments, _) => assignments.$visit(visitor),
+                    OnInsert::OnConflict(on_conflict) => {
+                        on_conflict.conflict_target.$visit(visitor)?;
+                        on_conflict.action.$visit(visitor)
+                    }
+                }
+            }
+        }
+
+        impl $Visit for ConflictTarget {
+            fn $visit<V: $Visitor>($($ref)+ self, visitor: &mut V) -> ControlFlow<V::Break> {
+                match self {
+                    ConflictTarget::Columns { selection, .. } => selection.$visit(visitor),
+                    ConflictTarget::OnConstraint(_) => ControlFlow::Continue(()),
+                }
+            }
+        }
+
+        impl $Visit for MergeClause {
+            fn $visit<V: $Visitor>($($ref)+ self, visitor: &mut V) -> ControlFlow<V::Break> {
+                self.predicate.$visit(visitor)?;
+                self.action.$visit(visitor)
+            }
+        }
+
+        impl $Visit for MergeAction {
+            fn $visit<V: $Visitor>($($ref)+ self, visitor: &mut V) -> ControlFlow<V::Break> {
+                match self {
+                    MergeAction::Update { assignments } => assignments.$visit(visitor),
+                    MergeAction::Delete => ControlFlow::Continue(()),
+                    MergeAction::Insert { values, .. } => values.$visit(visitor),
+                }
+            }
+        }
+
+        impl $Visit for OnConflictAction {
+            fn $visit<V: $Visitor>($($ref)+ self, visitor: &mut V) -> ControlFlow<V::Break> {
+                match self {
+                    OnConflictAction::DoNothing => ControlFlow::Continue(()),
+                    OnConflictAction::DoUpdate {
+                        assignments,
+                        selection,
+                    } => {
+                        assignments.$visit(visitor)?;
+                        selection.$visit(visitor)
+                    }
+                }
+            }
+        }
+
+        impl $Visit for SQLColumnDef {
+            fn $visit<V: $Visitor>($($ref)+ self, visitor: &mut V) -> ControlFlow<V::Break> {
+                self.data_type.$visit(visitor)?;
+                self.options.$visit(visitor)
+            }
+        }
+
+        impl $Visit for ColumnOptionDef {
+            fn $visit<V: $Visitor>($($ref)+ self, visitor: &mut V) -> ControlFlow<V::Break> {
+                match $($ref)+ self.option {
+                    ColumnOption::Default(expr) | ColumnOption::Check(expr) => expr.$visit(visitor),
+                    _ => ControlFlow::Continue(()),
+                }
+            }
+        }
+
+        impl $Visit for SQLTableOption {
+            fn $visit<V: $Visitor>($($ref)+ self, visitor: &mut V) -> ControlFlow<V::Break> {
+                self.value.$visit(visitor)
+            }
+        }
+
+        impl $Visit for TableConstraint {
+            fn $visit<V: $Visitor>($($ref)+ self, visitor: &mut V) -> ControlFlow<V::Break> {
+                match self {
+                    TableConstraint::Check { expr, .. } => expr.$visit(visitor),
+                    TableConstraint::Unique { .. } | TableConstraint::ForeignKey { .. } => {
+                        ControlFlow::Continue(())
+                    }
+                }
+            }
+        }
+
+        impl $Visit for AlterTableOperation {
+            fn $visit<V: $Visitor>($($ref)+ self, visitor: &mut V) -> ControlFlow<V::Break> {
+                match self {
+                    AlterTableOperation::AddConstraint(constraint) => constraint.$visit(visitor),
+                    AlterTableOperation::AddColumn { column_def, .. } => {
+                        column_def.$visit(visitor)
+                    }
+                    AlterTableOperation::AlterColumn { op, .. } => op.$visit(visitor),
+                    AlterTableOperation::DropConstraint { .. }
+                    | AlterTableOperation::DropColumn { .. }
+                    | AlterTableOperation::RenameColumn { .. }
+                    | AlterTableOperation::RenameTable { .. } => ControlFlow::Continue(()),
+                }
+            }
+        }
+
+        impl $Visit for FunctionArg {
+            fn $visit<V: $Visitor>($($ref)+ self, visitor: &mut V) -> ControlFlow<V::Break> {
+                self.data_type.$visit(visitor)?;
+                self.default.$visit(visitor)
+            }
+        }
+
+        impl $Visit for FunctionReturns {
+            fn $visit<V: $Visitor>($($ref)+ self, visitor: &mut V) -> ControlFlow<V::Break> {
+                match self {
+                    FunctionReturns::Type(data_type) | FunctionReturns::SetOf(data_type) => {
+                        data_type.$visit(visitor)
+                    }
+                    FunctionReturns::Table(columns) => columns.$visit(visitor),
+                }
+            }
+        }
+
+        impl $Visit for FunctionAttribute {
+            fn $visit<V: $Visitor>($($ref)+ self, visitor: &mut V) -> ControlFlow<V::Break> {
+                match self {
+                    FunctionAttribute::Cost(expr) | FunctionAttribute::Rows(expr) => {
+                        expr.$visit(visitor)
+                    }
+                    _ => ControlFlow::Continue(()),
+                }
+            }
+        }
+
+        impl $Visit for SequenceOption {
+            fn $visit<V: $Visitor>($($ref)+ self, visitor: &mut V) -> ControlFlow<V::Break> {
+                match self {
+                    SequenceOption::As { data_type } => data_type.$visit(visitor),
+                    SequenceOption::IncrementBy { value }
+                    | SequenceOption::StartWith { value }
+                    | SequenceOption::Cache { value } => value.$visit(visitor),
+                    SequenceOption::MinValue { value }
+                    | SequenceOption::MaxValue { value }
+                    | SequenceOption::Restart { value } => value.$visit(visitor),
+                    SequenceOption::Cycle
+                    | SequenceOption::NoCycle
+                    | SequenceOption::OwnedBy { .. } => ControlFlow::Continue(()),
+                }
+            }
+        }
+
+        impl $Visit for AlterColumnOperation {
+            fn $visit<V: $Visitor>($($ref)+ self, visitor: &mut V) -> ControlFlow<V::Break> {
+                match self {
+                    AlterColumnOperation::SetDefault { value } => value.$visit(visitor),
+                    AlterColumnOperation::SetDataType { data_type } => data_type.$visit(visitor),
+                    AlterColumnOperation::SetNotNull
+                    | AlterColumnOperation::DropNotNull
+                    | AlterColumnOperation::DropDefault => ControlFlow::Continue(()),
+                }
+            }
+        }
+    };
+}
+
+impl_visit!(Visit, visit, Visitor, &);
+impl_visit!(VisitMut, visit_mut, VisitorMut, &mut);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::all_dialects;
+
+    /// Records the order in which the hooks are called
+    #[derive(Default)]
+    struct Trace(Vec<String>);
+
+    impl Visitor for Trace {
+        type Break = ();
+
+        fn pre_visit_query(&mut self, _query: &SQLQuery) -> ControlFlow<()> {
+            self.0.push("PRE QUERY".to_string());
+            ControlFlow::Continue(())
+        }
+
+        fn post_visit_query(&mut self, _query: &SQLQuery) -> ControlFlow<()> {
+            self.0.push("POST QUERY".to_string());
+            ControlFlow::Continue(())
+        }
+
+        fn pre_visit_table_factor(&mut self, table_factor: &TableFactor) -> ControlFlow<()> {
+            self.0.push(format!("TABLE {}", table_factor));
+            ControlFlow::Continue(())
+        }
+
+        fn pre_visit_expr(&mut self, expr: &ASTNode) -> ControlFlow<()> {
+            self.0.push(format!("PRE EXPR {}", expr));
+            ControlFlow::Continue(())
+        }
+
+        fn post_visit_expr(&mut self, expr: &ASTNode) -> ControlFlow<()> {
+            self.0.push(format!("POST EXPR {}", expr));
+            ControlFlow::Continue(())
+        }
+
+        fn pre_visit_data_type(&mut self, data_type: &SQLType) -> ControlFlow<()> {
+            self.0.push(format!("TYPE {}", data_type));
+            ControlFlow::Continue(())
+        }
+    }
+
+    #[test]
+    fn visit_order() {
+        let stmt = all_dialects()
+            .verified_stmt("SELECT a + 1 FROM t WHERE b IN (SELECT CAST(c AS int) FROM u)");
+        let mut trace = Trace::default();
+        assert_eq!(ControlFlow::Continue(()), stmt.visit(&mut trace));
+        assert_eq!(
+            vec![
+                "PRE QUERY",
+                "PRE EXPR a + 1",
+                "PRE EXPR a",
+                "POST EXPR a",
+                "PRE EXPR 1",
+                "POST EXPR 1",
+                "POST EXPR a + 1",
+                "TABLE t",
+                "PRE EXPR b IN (SELECT CAST(c AS int) FROM u)",
+                "PRE EXPR b",
+                "POST EXPR b",
+                "PRE QUERY",
+                "PRE EXPR CAST(c AS int)",
+                "PRE EXPR c",
+                "POST EXPR c",
+                "TYPE int",
+                "POST EXPR CAST(c AS int)",
+                "TABLE u",
+                "POST QUERY",
+                "POST EXPR b IN (SELECT CAST(c AS int) FROM u)",
+                "POST QUERY",
+            ],
+            trace.0
+        );
+    }
+
+    #[test]
+    fn visit_break() {
+        /// Finds the first function call in the tree
+        struct FirstFunction;
+
+        impl Visitor for FirstFunction {
+            type Break = String;
+
+            fn pre_visit_expr(&mut self, expr: &ASTNode) -> ControlFlow<String> {
+                match expr {
+                    ASTNode::SQLFunction(f) => ControlFlow::Break(f.name.to_string()),
+                    _ => ControlFlow::Continue(()),
+                }
+            }
+        }
+
+        let stmt = all_dialects().verified_stmt(
+            "SELECT x FROM t JOIN u ON lower(t.a) = upper(u.b) ORDER BY max(y) OVER (ORDER BY z)",
+        );
+        assert_eq!(
+            ControlFlow::Break("lower".to_string()),
+            stmt.visit(&mut FirstFunction)
+        );
+        let stmt = all_dialects().verified_stmt("SELECT x FROM t");
+        assert_eq!(ControlFlow::Continue(()), stmt.visit(&mut FirstFunction));
+    }
+
+    #[test]
+    fn visit_mut() {
+        /// Qualifies unqualified column references with a table name
+        struct Qualify;
+
+        impl VisitorMut for Qualify {
+            type Break = ();
+
+            fn pre_visit_expr(&mut self, expr: &mut ASTNode) -> ControlFlow<()> {
+                if let ASTNode::SQLIdentifier(ident, span) = expr {
+                    *expr =
+                        ASTNode::SQLCompoundIdentifier(vec!["t".to_string(), ident.clone()], *span);
+                }
+                ControlFlow::Continue(())
+            }
+
+            fn pre_visit_window_spec(
+                &mut self,
+                window_spec: &mut SQLWindowSpec,
+            ) -> ControlFlow<()> {
+                window_spec.order_by.clear();
+                ControlFlow::Continue(())
+            }
+        }
+
+        let mut stmt = all_dialects().verified_stmt(
+            "SELECT a, sum(b) OVER (PARTITION BY c ORDER BY d) FROM t WHERE e = 1 GROUP BY f",
+        );
+        let _ = stmt.visit_mut(&mut Qualify);
+        assert_eq!(
+            "SELECT t.a, sum(t.b) OVER (PARTITION BY t.c) FROM t WHERE t.e = 1 GROUP BY t.f",
+            stmt.to_string()
+        );
+    }
+}
@@ -0,0 +1,2303 @@
+//! Pretty-printing of SQL statements.
+//!
+//! The `Display` implementations of the AST types print each statement on a
+//! single line. The functions in this module lay statements out over
+//! multiple lines instead: each clause of a query starts a new line, select
+//! items are printed one per line, joins are indented under `FROM`, and
+//! expressions or lists that don't fit in `FormatOptions::max_width` are
+//! broken up.
+//!
+//! ```
+//! use sqlparser::dialect::GenericSqlDialect;
+//! use sqlparser::sqlformat::{format_statement, FormatOptions, KeywordCase};
+//! use sqlparser::sqlparser::Parser;
+//!
+//! let sql = "select a, b from t join u on t.id = u.id where a > 1 and b < 2";
+//! let statements = Parser::parse_sql(&GenericSqlDialect {}, sql.to_string()).unwrap();
+//! let options = FormatOptions {
+//!     keyword_case: KeywordCase::Lower,
+//!     ..FormatOptions::default()
+//! };
+//! assert_eq!(
+//!     format_statement(&statements[0], &options),
+//!     "select\n  a,\n  b\nfrom t\n  join u on t.id = u.id\nwhere a > 1 and b < 2"
+//! );
+//! ```
+use std::fmt::{Display, Write};
+
+use super::dialect::Dialect;
+use super::sqlast::*;
+
+/// How to print SQL keywords
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum KeywordCase {
+    /// `SELECT a FROM t`
+    Upper,
+    /// `select a from t`
+    Lower,
+}
+
+/// Where to put the commas of a list that is broken over multiple lines
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CommaStyle {
+    /// At the end of each line but the last
+    Trailing,
+    /// At the start of each line but the first
+    Leading,
+}
+
+/// Layout settings for `format_statement`
+#[derive(Debug, Clone)]
+pub struct FormatOptions<'a> {
+    /// Number of spaces per indentation level
+    pub indent: usize,
+    /// The width lines should fit in. Lists and expressions are broken
+    /// over multiple lines when they would exceed it, but longer lines
+    /// are produced when a construct can't be broken up.
+    pub max_width: usize,
+    pub keyword_case: KeywordCase,
+    pub comma_style: CommaStyle,
+    /// When set, identifiers that the dialect doesn't accept unquoted
+    /// (see `Dialect::identifier_requires_quoting`) are quoted. Lone
+    /// identifiers in expressions are printed as is, as they may be
+    /// keywords, like `CURRENT_DATE`.
+    pub dialect: Option<&'a dyn Dialect>,
+}
+
+impl<'a> Default for FormatOptions<'a> {
+    fn default() -> Self {
+        FormatOptions {
+            indent: 2,
+            max_width: 80,
+            keyword_case: KeywordCase::Upper,
+            comma_style: CommaStyle::Trailing,
+            dialect: None,
+        }
+    }
+}
+
+/// Pretty-print a single statement, without the trailing semicolon.
+pub fn format_statement(statement: &SQLStatement, options: &FormatOptions) -> String {
+    let mut printer = Printer::new(options, &[], false);
+    printer.statement(statement);
+    printer.out
+}
+
+/// Pretty-print a script, terminating each statement with a semicolon and
+/// separating statements by an empty line.
+pub fn format_statements(statements: &[SQLStatement], options: &FormatOptions) -> String {
+    format_statements_with_comments(statements, &[], options)
+}
+
+/// Pretty-print a script like `format_statements`, re-emitting the comments
+/// returned by `Parser::parse_sql_with_comments` next to the nodes they were
+/// written next to.
+///
+/// Comments are placed using the spans of the AST: a comment following a
+/// statement, a clause element (such as a select item, a table or a column
+/// definition) or an expression on the same line is printed at the end of
+/// the line that element ends on. Any other comment is printed on its own
+/// line before the element that follows it.
+pub fn format_statements_with_comments(
+    statements: &[SQLStatement],
+    comments: &[SQLComment],
+    options: &FormatOptions,
+) -> String {
+    let mut printer = Printer::new(options, comments, false);
+    for (i, statement) in statements.iter().enumerate() {
+        if i > 0 {
+            printer.text("\n");
+        }
+        // comments after the end of the statement go after its semicolon
+        printer.statement_end = Some(statement.span().end);
+        printer.statement(statement);
+        printer.statement_end = None;
+        printer.text(";");
+        printer.last_end = Some((statement.span().end, printer.out.len()));
+        printer.trailing_comments();
+        if !printer.at_line_start() {
+            printer.text("\n");
+        }
+    }
+    // comments after the last statement
+    printer.leading_comments(Location {
+        offset: usize::MAX,
+        ..Location::default()
+    });
+    printer.out
+}
+
+struct Printer<'a> {
+    options: &'a FormatOptions<'a>,
+    out: String,
+    /// The current indentation level
+    level: usize,
+    /// In flat mode, line breaks are replaced by spaces: this is used to
+    /// find out whether a group fits in the remaining width.
+    flat: bool,
+    /// The comments to emit, ordered by their position in the source
+    comments: &'a [SQLComment],
+    /// The first comment not yet emitted
+    next_comment: usize,
+    /// Where the last element printed ended in the source text, and the
+    /// length of the output at that point
+    last_end: Option<(Location, usize)>,
+    /// Set when a comment is found in flat mode, where it can't be printed
+    must_break: bool,
+    /// The end of the statement being printed: comments after it are left
+    /// for the statement itself
+    statement_end: Option<Location>,
+}
+
+impl<'a> Printer<'a> {
+    fn new(options: &'a FormatOptions<'a>, comments: &'a [SQLComment], flat: bool) -> Self {
+        Printer {
+            options,
+            out: String::new(),
+            level: 0,
+            flat,
+            comments,
+            next_comment: 0,
+            last_end: None,
+            must_break: false,
+            statement_end: None,
+        }
+    }
+
+    /// Print an AST node using `f`, preceded by the comments found before
+    /// it in the source.
+    fn element<N: Spanned, F: FnOnce(&mut Self)>(&mut self, node: &N, f: F) {
+        let span = node.span();
+        self.leading_comments(span.start);
+        f(self);
+        self.last_end = Some((span.end, self.out.len()));
+    }
+
+    /// Emit the comments found before `start` in the source: on the line of
+    /// the last element if they followed it on the same line, otherwise on
+    /// lines of their own.
+    fn leading_comments(&mut self, start: Location) {
+        while let Some(comment) = self.comments.get(self.next_comment) {
+            if comment.span.start.offset >= start.offset || !self.in_statement(comment) {
+                break;
+            }
+            self.next_comment += 1;
+            if self.flat {
+                self.must_break = true;
+                continue;
+            }
+            match self.last_end {
+                Some((end, out_pos)) if follows_on_same_line(comment, end) => {
+                    // print the comment at the end of the line the last
+                    // element is on, even if a new line was started since
+                    match self.out[out_pos..].find('\n') {
+                        Some(i) => self.out.insert_str(out_pos + i, &format!(" {}", comment)),
+                        None => self.trailing_comment(comment),
+                    }
+                }
+                _ => {
+                    if !self.at_line_start() && !self.out.ends_with(' ') {
+                        self.text(" ");
+                    }
+                    let at_line_start = self.at_line_start();
+                    self.display(comment);
+                    if !comment.multiline || at_line_start {
+                        self.newline();
+                    } else {
+                        self.text(" ");
+                    }
+                }
+            }
+        }
+    }
+
+    /// Emit the comments following the last element on the same line.
+    fn trailing_comments(&mut self) {
+        if let Some((end, _)) = self.last_end {
+            while let Some(comment) = self.comments.get(self.next_comment) {
+                if !follows_on_same_line(comment, end) || !self.in_statement(comment) {
+                    break;
+                }
+                self.next_comment += 1;
+                self.trailing_comment(comment);
+            }
+        }
+    }
+
+    /// Whether `comment` is before the end of the statement being printed
+    fn in_statement(&self, comment: &SQLComment) -> bool {
+        match self.statement_end {
+            Some(end) => comment.span.start.offset < end.offset,
+            None => true,
+        }
+    }
+
+    fn trailing_comment(&mut self, comment: &SQLComment) {
+        if self.flat {
+            self.must_break = true;
+            return;
+        }
+        self.text(" ");
+        self.display(comment);
+        if !comment.multiline {
+            self.newline();
+        }
+    }
+
+    /// Whether nothing but indentation was printed on the current line
+    fn at_line_start(&self) -> bool {
+        let line_start = self.out.rfind('\n').map_or(0, |i| i + 1);
+        self.out[line_start..].chars().all(|c| c == ' ')
+    }
+
+    fn kw(&mut self, keyword: &str) {
+        match self.options.keyword_case {
+            KeywordCase::Upper => self.out.push_str(keyword),
+            KeywordCase::Lower => self.out.push_str(&keyword.to_lowercase()),
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        self.out.push_str(text);
+    }
+
+    fn display<T: Display>(&mut self, value: T) {
+        write!(self.out, "{}", value).unwrap();
+    }
+
+    /// Start a new line at the current indentation level, or write
+    /// `flat_text` instead in flat mode.
+    fn line(&mut self, flat_text: &str) {
+        if self.flat {
+            self.out.push_str(flat_text);
+        } else {
+            self.newline();
+        }
+    }
+
+    fn newline(&mut self) {
+        if self.at_line_start() && !self.out.is_empty() {
+            // a comment already ended the line: just fix the indentation
+            let line_start = self.out.rfind('\n').map_or(0, |i| i + 1);
+            self.out.truncate(line_start);
+        } else {
+            self.out.push('\n');
+        }
+        for _ in 0..self.level * self.options.indent {
+            self.out.push(' ');
+        }
+    }
+
+    fn indented<F: FnOnce(&mut Self)>(&mut self, f: F) {
+        self.level += 1;
+        f(self);
+        self.level -= 1;
+    }
+
+    /// Run `f` with the indentation level of the current line, so that
+    /// lines it starts line up with the one the construct began on
+    fn at_line_level<F: FnOnce(&mut Self)>(&mut self, f: F) {
+        let line_start = self.out.rfind('\n').map_or(0, |i| i + 1);
+        let indent =
+            self.out[line_start..].len() - self.out[line_start..].trim_start_matches(' ').len();
+        let level = self.level;
+        self.level = indent.checked_div(self.options.indent).unwrap_or(level);
+        f(self);
+        self.level = level;
+    }
+
+    fn column(&self) -> usize {
+        let line_start = self.out.rfind('\n').map_or(0, |i| i + 1);
+        self.out[line_start..].chars().count()
+    }
+
+    /// Print `f` on the current line if it fits in the remaining width,
+    /// otherwise let it break lines where it calls `line`.
+    fn group<F: Fn(&mut Self)>(&mut self, f: F) {
+        if !self.flat {
+            let mut flat = Printer::new(self.options, self.comments, true);
+            flat.level = self.level;
+            flat.next_comment = self.next_comment;
+            flat.statement_end = self.statement_end;
+            f(&mut flat);
+            if !flat.must_break
+                && self.column() + flat.out.chars().count() <= self.options.max_width
+            {
+                if let Some((end, out_pos)) = flat.last_end {
+                    self.last_end = Some((end, self.out.len() + out_pos));
+                }
+                self.out.push_str(&flat.out);
+                return;
+            }
+        }
+        f(self)
+    }
+
+    /// Print a comma-separated list, one element per line unless in flat mode
+    fn list<T, F: Fn(&mut Self, &T)>(&mut self, items: &[T], f: F) {
+        for (i, item) in items.iter().enumerate() {
+            if i > 0 {
+                match self.options.comma_style {
+                    CommaStyle::Trailing => {
+                        self.text(",");
+                        self.line(" ");
+                    }
+                    CommaStyle::Leading => {
+                        self.line("");
+                        self.text(", ");
+                    }
+                }
+            }
+            f(self, item);
+        }
+    }
+
+    /// A parenthesized list, broken one element per line (indented) if it
+    /// doesn't fit on the current line.
+    fn paren_list<T, F: Fn(&mut Self, &T)>(&mut self, items: &[T], f: F) {
+        self.group(|p| {
+            p.text("(");
+            p.at_line_level(|p| {
+                p.indented(|p| {
+                    p.line("");
+                    p.list(items, &f);
+                    p.trailing_comments();
+                });
+                p.line("");
+            });
+            p.text(")");
+        })
+    }
+
+    /// Print an identifier, quoting it if the dialect in `FormatOptions`
+    /// requires it (unless it is quoted already)
+    fn ident(&mut self, ident: &str) {
+        match self.options.dialect {
+            Some(dialect)
+                if !ident.starts_with(|ch| "\"`['".contains(ch))
+                    && dialect.identifier_requires_quoting(ident) =>
+            {
+                self.text(&dialect.quote_identifier(ident))
+            }
+            _ => self.text(ident),
+        }
+    }
+
+    fn qualified_name(&mut self, idents: &[SQLIdent]) {
+        for (i, ident) in idents.iter().enumerate() {
+            if i > 0 {
+                self.text(".");
+            }
+            self.ident(ident);
+        }
+    }
+
+    fn object_name(&mut self, name: &SQLObjectName) {
+        self.qualified_name(&name.0);
+    }
+
+    fn idents(&mut self, idents: &[SQLIdent]) {
+        self.paren_list(idents, |p, ident| p.ident(ident));
+    }
+
+    fn exprs(&mut self, exprs: &[ASTNode]) {
+        self.paren_list(exprs, |p, expr| p.expr(expr));
+    }
+
+    fn statement(&mut self, statement: &SQLStatement) {
+        self.element(statement, |p| {
+            match statement {
+                SQLStatement::SQLQuery(query) => p.query(query),
+                SQLStatement::SQLInsert {
+                    replace_into,
+                    or,
+                    table_name,
+                    columns,
+                    source,
+                    on,
+                    returning,
+                    ..
+                } => {
+                    p.kw(if *replace_into { "REPLACE" } else { "INSERT" });
+                    if let Some(or) = or {
+                        p.text(" ");
+                        p.kw("OR");
+                        p.text(" ");
+                        p.kw(&or.to_string());
+                    }
+                    p.text(" ");
+                    p.kw("INTO");
+                    p.text(" ");
+                    p.object_name(table_name);
+                    if !columns.is_empty() {
+                        p.text(" ");
+                        p.idents(columns);
+                    }
+                    p.line(" ");
+                    p.query(source);
+                    if let Some(on) = on {
+                        p.line(" ");
+                        p.element(on, |p| match on {
+                            OnInsert::DuplicateKeyUpdate(assignments, _) => {
+                                p.kw("ON DUPLICATE KEY UPDATE");
+                                p.assignments(assignments);
+                            }
+                            OnInsert::OnConflict(on_conflict) => p.on_conflict(on_conflict),
+                        });
+                    }
+                    p.returning(returning);
+                }
+                SQLStatement::SQLCopy {
+                    table_name,
+                    columns,
+                    values,
+                    ..
+                } => {
+                    p.kw("COPY");
+                    p.text(" ");
+                    p.object_name(table_name);
+                    if !columns.is_empty() {
+                        p.text(" ");
+                        p.idents(columns);
+                    }
+                    p.text(" ");
+                    p.kw("FROM");
+                    p.text(" stdin; ");
+                    // the data is copied verbatim
+                    if !values.is_empty() {
+                        p.text("\n");
+                        let values: Vec<&str> = values
+                            .iter()
+                            .map(|v| v.as_ref().map_or("\\N", String::as_str))
+                            .collect();
+                        p.text(&values.join("\t"));
+                    }
+                    p.text("\n\\.");
+                }
+                SQLStatement::SQLUpdate {
+                    table_name,
+                    alias,
+                    assignments,
+                    from,
+                    joins,
+                    selection,
+                    returning,
+                    ..
+                } => {
+                    p.kw("UPDATE");
+                    p.text(" ");
+                    p.object_name(table_name);
+                    p.alias(alias);
+                    if !assignments.is_empty() {
+                        p.line(" ");
+                        p.kw("SET");
+                        p.assignments(assignments);
+                    }
+                    p.relations("FROM", from, joins);
+                    p.where_clause(selection);
+                    p.returning(returning);
+                }
+                SQLStatement::SQLDelete {
+                    table_name,
+                    alias,
+                    using,
+                    joins,
+                    selection,
+                    returning,
+                    ..
+                } => {
+                    p.kw("DELETE FROM");
+                    p.text(" ");
+                    p.object_name(table_name);
+                    p.alias(alias);
+                    p.relations("USING", using, joins);
+                    p.where_clause(selection);
+                    p.returning(returning);
+                }
+                SQLStatement::SQLMerge {
+                    into,
+                    table,
+                    source,
+                    on,
+                    clauses,
+                    ..
+                } => {
+                    p.kw(if *into { "MERGE INTO" } else { "MERGE" });
+                    p.text(" ");
+                    p.table_factor(table);
+                    p.line(" ");
+                    p.kw("USING");
+                    p.text(" ");
+                    p.table_factor(source);
+                    p.indented(|p| {
+                        p.line(" ");
+                        p.kw("ON");
+                        p.text(" ");
+                        p.indented(|p| p.expr(on));
+                    });
+                    for clause in clauses {
+                        p.line(" ");
+                        p.merge_clause(clause);
+                    }
+                }
+                SQLStatement::SQLCreateView {
+                    or_replace,
+                    name,
+                    columns,
+                    query,
+                    materialized,
+                    with_options,
+                    ..
+                } => {
+                    p.kw("CREATE");
+                    if *or_replace {
+                        p.text(" ");
+                        p.kw("OR REPLACE");
+                    }
+                    if *materialized {
+                        p.text(" ");
+                        p.kw("MATERIALIZED");
+                    }
+                    p.text(" ");
+                    p.kw("VIEW");
+                    p.text(" ");
+                    p.object_name(name);
+                    p.with_options(with_options);
+                    if !columns.is_empty() {
+                        p.text(" ");
+                        p.idents(columns);
+                    }
+                    p.text(" ");
+                    p.kw("AS");
+                    p.line(" ");
+                    p.query(query);
+                }
+                SQLStatement::SQLCreateTable {
+                    persistence,
+                    if_not_exists,
+                    name,
+                    columns,
+                    constraints,
+                    with_options,
+                    table_options,
+                    without_rowid,
+                    external,
+                    file_format,
+                    location,
+                    query,
+                    ..
+                } => {
+                    p.kw("CREATE");
+                    if let Some(persistence) = persistence {
+                        p.text(" ");
+                        p.kw(&persistence.to_string());
+                    }
+                    if *external {
+                        p.text(" ");
+                        p.kw("EXTERNAL");
+                    }
+                    p.text(" ");
+                    p.kw("TABLE");
+                    if *if_not_exists {
+                        p.text(" ");
+                        p.kw("IF NOT EXISTS");
+                    }
+                    p.text(" ");
+                    p.object_name(name);
+                    if query.is_none() || !columns.is_empty() || !constraints.is_empty() {
+                        p.text(" (");
+                        let elements: Vec<TableElement> = columns
+                            .iter()
+                            .map(TableElement::Column)
+                            .chain(constraints.iter().map(TableElement::Constraint))
+                            .collect();
+                        p.indented(|p| {
+                            p.line("");
+                            p.list(&elements, |p, element| match element {
+                                TableElement::Column(column) => p.column_def(column),
+                                TableElement::Constraint(constraint) => {
+                                    p.table_constraint(constraint)
+                                }
+                            });
+                            p.trailing_comments();
+                        });
+                        p.line("");
+                        p.text(")");
+                    }
+                    if *without_rowid {
+                        p.text(" ");
+                        p.kw("WITHOUT ROWID");
+                    }
+                    if *external {
+                        p.text(" ");
+                        p.kw("STORED AS");
+                        p.text(" ");
+                        p.display(file_format.as_ref().unwrap());
+                        p.text(" ");
+                        p.kw("LOCATION");
+                        p.text(" ");
+                        p.display(Value::SingleQuotedString(location.clone().unwrap()));
+                    }
+                    p.with_options(with_options);
+                    for option in table_options {
+                        p.text(" ");
+                        p.element(option, |p| {
+                            if option.default {
+                                p.kw("DEFAULT");
+                                p.text(" ");
+                            }
+                            p.kw(&option.name);
+                            p.text("=");
+                            p.expr(&option.value);
+                        });
+                    }
+                    if let Some(query) = query {
+                        p.text(" ");
+                        p.kw("AS");
+                        p.line(" ");
+                        p.query(query);
+                    }
+                }
+                SQLStatement::SQLCreateIndex {
+                    name,
+                    table_name,
+                    unique,
+                    concurrently,
+                    if_not_exists,
+                    using,
+                    columns,
+                    include,
+                    predicate,
+                    ..
+                } => {
+                    p.kw("CREATE");
+                    if *unique {
+                        p.text(" ");
+                        p.kw("UNIQUE");
+                    }
+                    p.text(" ");
+                    p.kw("INDEX");
+                    if *concurrently {
+                        p.text(" ");
+                        p.kw("CONCURRENTLY");
+                    }
+                    if *if_not_exists {
+                        p.text(" ");
+                        p.kw("IF NOT EXISTS");
+                    }
+                    p.text(" ");
+                    p.object_name(name);
+                    p.text(" ");
+                    p.kw("ON");
+                    p.text(" ");
+                    p.object_name(table_name);
+                    if let Some(using) = using {
+                        p.text(" ");
+                        p.kw("USING");
+                        p.text(" ");
+                        p.ident(using);
+                    }
+                    p.text(" ");
+                    p.paren_list(columns, |p, column| p.order_by(column));
+                    if !include.is_empty() {
+                        p.text(" ");
+                        p.kw("INCLUDE");
+                        p.text(" ");
+                        p.idents(include);
+                    }
+                    if let Some(predicate) = predicate {
+                        p.line(" ");
+                        p.kw("WHERE");
+                        p.text(" ");
+                        p.expr(predicate);
+                    }
+                }
+                SQLStatement::SQLCreateSchema {
+                    schema_name,
+                    if_not_exists,
+                    ..
+                } => {
+                    p.kw("CREATE SCHEMA");
+                    if *if_not_exists {
+                        p.text(" ");
+                        p.kw("IF NOT EXISTS");
+                    }
+                    p.text(" ");
+                    p.object_name(schema_name);
+                }
+                SQLStatement::SQLCreateDatabase {
+                    db_name,
+                    if_not_exists,
+                    ..
+                } => {
+                    p.kw("CREATE DATABASE");
+                    if *if_not_exists {
+                        p.text(" ");
+                        p.kw("IF NOT EXISTS");
+                    }
+                    p.text(" ");
+                    p.object_name(db_name);
+                }
+                SQLStatement::SQLCreateSequence {
+                    name,
+                    if_not_exists,
+                    options,
+                    ..
+                } => {
+                    p.kw("CREATE SEQUENCE");
+                    if *if_not_exists {
+                        p.text(" ");
+                        p.kw("IF NOT EXISTS");
+                    }
+                    p.text(" ");
+                    p.object_name(name);
+                    p.sequence_options(options);
+                }
+                SQLStatement::SQLCreateFunction {
+                    or_replace,
+                    procedure,
+                    name,
+                    args,
+                    returns,
+                    attributes,
+                    body,
+                    ..
+                } => {
+                    p.kw("CREATE");
+                    if *or_replace {
+                        p.text(" ");
+                        p.kw("OR REPLACE");
+                    }
+                    p.text(" ");
+                    p.kw(if *procedure { "PROCEDURE" } else { "FUNCTION" });
+                    p.text(" ");
+                    p.object_name(name);
+                    if let Some(args) = args {
+                        p.paren_list(args, |p, arg| p.function_arg(arg));
+                    }
+                    if let Some(returns) = returns {
+                        p.line(" ");
+                        p.kw("RETURNS");
+                        p.text(" ");
+                        match returns {
+                            FunctionReturns::Type(data_type) => p.display(data_type),
+                            FunctionReturns::SetOf(data_type) => {
+                                p.kw("SETOF");
+                                p.text(" ");
+                                p.display(data_type);
+                            }
+                            FunctionReturns::Table(columns) => {
+                                p.kw("TABLE");
+                                if !columns.is_empty() {
+                                    p.text(" ");
+                                    p.paren_list(columns, |p, column| p.column_def(column));
+                                }
+                            }
+                        }
+                    }
+                    for attribute in attributes {
+                        p.line(" ");
+                        match attribute {
+                            FunctionAttribute::Language(name) => {
+                                p.kw("LANGUAGE");
+                                p.text(" ");
+                                p.ident(name);
+                            }
+                            FunctionAttribute::Cost(expr) => p.keyword_expr("COST", expr),
+                            FunctionAttribute::Rows(expr) => p.keyword_expr("ROWS", expr),
+                            FunctionAttribute::With(options) => {
+                                p.kw("WITH");
+                                p.text(" ");
+                                p.group(|p| {
+                                    p.indented(|p| p.list(options, |p, option| p.ident(option)))
+                                });
+                            }
+                            other => p.kw(&other.to_string()),
+                        }
+                    }
+                    p.line(" ");
+                    p.kw("AS");
+                    p.text(" ");
+                    p.display(body);
+                }
+                SQLStatement::SQLAlterSequence {
+                    name,
+                    if_exists,
+                    options,
+                    ..
+                } => {
+                    p.kw("ALTER SEQUENCE");
+                    if *if_exists {
+                        p.text(" ");
+                        p.kw("IF EXISTS");
+                    }
+                    p.text(" ");
+                    p.object_name(name);
+                    p.sequence_options(options);
+                }
+                SQLStatement::SQLAlterTable {
+                    name, operations, ..
+                } => {
+                    p.kw("ALTER TABLE");
+                    p.text(" ");
+                    p.object_name(name);
+                    p.clause_list(operations, |p, operation| {
+                        p.alter_table_operation(operation)
+                    });
+                }
+                SQLStatement::SQLDrop {
+                    object_type,
+                    if_exists,
+                    names,
+                    cascade,
+                    ..
+                } => {
+                    p.kw("DROP");
+                    p.text(" ");
+                    p.kw(&object_type.to_string());
+                    if *if_exists {
+                        p.text(" ");
+                        p.kw("IF EXISTS");
+                    }
+                    p.text(" ");
+                    p.group(|p| p.indented(|p| p.list(names, |p, name| p.object_name(name))));
+                    if *cascade {
+                        p.text(" ");
+                        p.kw("CASCADE");
+                    }
+                }
+                SQLStatement::SQLStartTransaction { begin, modes, .. } => {
+                    p.kw(if *begin { "BEGIN" } else { "START TRANSACTION" });
+                    p.transaction_modes(modes);
+                }
+                SQLStatement::SQLSetTransaction { modes, .. } => {
+                    p.kw("SET TRANSACTION");
+                    p.transaction_modes(modes);
+                }
+                SQLStatement::SQLCommit { chain, .. } => {
+                    p.kw(if *chain { "COMMIT AND CHAIN" } else { "COMMIT" });
+                }
+                SQLStatement::SQLRollback {
+                    chain, savepoint, ..
+                } => {
+                    p.kw(if *chain {
+                        "ROLLBACK AND CHAIN"
+                    } else {
+                        "ROLLBACK"
+                    });
+                    if let Some(savepoint) = savepoint {
+                        p.text(" ");
+                        p.kw("TO SAVEPOINT");
+                        p.text(" ");
+                        p.ident(savepoint);
+                    }
+                }
+                SQLStatement::SQLSavepoint { name, .. } => {
+                    p.kw("SAVEPOINT");
+                    p.text(" ");
+                    p.ident(name);
+                }
+                SQLStatement::SQLReleaseSavepoint { name, .. } => {
+                    p.kw("RELEASE SAVEPOINT");
+                    p.text(" ");
+                    p.ident(name);
+                }
+                SQLStatement::SQLPragma {
+                    name, value, is_eq, ..
+                } => {
+                    p.kw("PRAGMA");
+                    p.text(" ");
+                    p.display(name);
+                    match value {
+                        Some(value) if *is_eq => {
+                            p.text(" = ");
+                            p.expr(value);
+                        }
+                        Some(value) => {
+                            p.text("(");
+                            p.expr(value);
+                            p.text(")");
+                        }
+                        None => (),
+                    }
+                }
+                SQLStatement::SQLAttachDatabase {
+                    database,
+                    database_file_name,
+                    schema_name,
+                    ..
+                } => {
+                    p.kw(if *database {
+                        "ATTACH DATABASE"
+                    } else {
+                        "ATTACH"
+                    });
+                    p.text(" ");
+                    p.expr(database_file_name);
+                    p.text(" ");
+                    p.kw("AS");
+                    p.text(" ");
+                    p.ident(schema_name);
+                }
+            }
+        });
+    }
+
+    fn query(&mut self, query: &SQLQuery) {
+        if !query.ctes.is_empty() {
+            self.kw("WITH");
+            self.text(" ");
+            self.list(&query.ctes, |p, cte| {
+                p.leading_comments(cte.span.start);
+                p.ident(&cte.alias);
+                if !cte.renamed_columns.is_empty() {
+                    p.text(" ");
+                    p.idents(&cte.renamed_columns);
+                }
+                p.text(" ");
+                p.kw("AS");
+                p.text(" ");
+                p.subquery(&cte.query);
+            });
+            self.line(" ");
+        }
+        self.set_expr(&query.body);
+        if !query.order_by.is_empty() {
+            self.line(" ");
+            self.kw("ORDER BY");
+            self.clause_list(&query.order_by, |p, order_by| p.order_by(order_by));
+        }
+        if let Some(ref limit) = query.limit {
+            self.line(" ");
+            self.kw("LIMIT");
+            self.text(" ");
+            self.expr(limit);
+        }
+        if let Some(ref offset) = query.offset {
+            self.line(" ");
+            self.kw("OFFSET");
+            self.text(" ");
+            self.expr(&offset.value);
+            if offset.rows {
+                self.text(" ");
+                self.kw("ROWS");
+            }
+        }
+        if let Some(ref fetch) = query.fetch {
+            self.line(" ");
+            self.kw("FETCH FIRST");
+            if let Some(ref quantity) = fetch.quantity {
+                self.text(" ");
+                self.expr(quantity);
+                if fetch.percent {
+                    self.text(" ");
+                    self.kw("PERCENT");
+                }
+            }
+            self.text(" ");
+            self.kw(if fetch.with_ties {
+                "ROWS WITH TIES"
+            } else {
+                "ROWS ONLY"
+            });
+        }
+    }
+
+    /// A query in parentheses, kept on the current line if it fits.
+    fn subquery(&mut self, query: &SQLQuery) {
+        self.group(|p| {
+            p.text("(");
+            p.at_line_level(|p| {
+                p.indented(|p| {
+                    p.line("");
+                    p.query(query);
+                });
+                p.line("");
+            });
+            p.text(")");
+        })
+    }
+
+    fn set_expr(&mut self, body: &SQLSetExpr) {
+        match body {
+            SQLSetExpr::Select(select) => self.select(select),
+            SQLSetExpr::Query(query) => self.subquery(query),
+            SQLSetExpr::SetOperation {
+                op,
+                all,
+                left,
+                right,
+                ..
+            } => {
+                self.set_expr(left);
+                self.line(" ");
+                self.kw(&op.to_string());
+                if *all {
+                    self.text(" ");
+                    self.kw("ALL");
+                }
+                self.line(" ");
+                self.set_expr(right);
+            }
+            SQLSetExpr::Values(values) => {
+                self.kw("VALUES");
+                self.group(|p| {
+                    p.indented(|p| {
+                        p.line(" ");
+                        p.list(&values.0, |p, row| p.exprs(row));
+                    })
+                });
+            }
+        }
+    }
+
+    fn select(&mut self, select: &SQLSelect) {
+        self.element(select, |p| {
+            p.kw("SELECT");
+            if select.distinct {
+                p.text(" ");
+                p.kw("DISTINCT");
+            }
+            if select.projection.len() == 1 {
+                p.text(" ");
+                p.select_item(&select.projection[0]);
+            } else {
+                p.indented(|p| {
+                    p.line(" ");
+                    p.list(&select.projection, |p, item| p.select_item(item));
+                });
+            }
+            p.relations("FROM", &select.relation, &select.joins);
+            p.where_clause(&select.selection);
+            if !select.group_by.is_empty() {
+                p.line(" ");
+                p.kw("GROUP BY");
+                p.clause_list(&select.group_by, |p, expr| p.expr(expr));
+            }
+            if let Some(ref having) = select.having {
+                p.line(" ");
+                p.kw("HAVING");
+                p.text(" ");
+                p.indented(|p| p.expr(having));
+            }
+        });
+    }
+
+    /// The list following a clause keyword like `GROUP BY`: on the same line
+    /// if it fits, otherwise one element per line.
+    fn clause_list<T, F: Fn(&mut Self, &T)>(&mut self, items: &[T], f: F) {
+        self.group(|p| {
+            p.indented(|p| {
+                p.line(" ");
+                p.list(items, &f);
+            })
+        });
+    }
+
+    /// Print a `FROM`-like clause: `<keyword> <relation> <joins>`
+    fn relations(&mut self, keyword: &str, relation: &Option<TableFactor>, joins: &[Join]) {
+        if let Some(relation) = relation {
+            self.line(" ");
+            self.kw(keyword);
+            self.text(" ");
+            self.table_factor(relation);
+            self.indented(|p| {
+                for join in joins {
+                    p.join(join);
+                }
+            });
+        }
+    }
+
+    fn where_clause(&mut self, selection: &Option<ASTNode>) {
+        if let Some(selection) = selection {
+            self.line(" ");
+            self.kw("WHERE");
+            self.text(" ");
+            self.indented(|p| p.expr(selection));
+        }
+    }
+
+    fn with_options(&mut self, options: &[SQLOption]) {
+        if !options.is_empty() {
+            self.text(" ");
+            self.kw("WITH");
+            self.text(" ");
+            self.paren_list(options, |p, option| {
+                p.text(&option.name);
+                p.text(" = ");
+                p.value(&option.value);
+            });
+        }
+    }
+
+    fn assignments(&mut self, assignments: &[SQLAssignment]) {
+        self.clause_list(assignments, |p, assignment| {
+            p.leading_comments(assignment.span.start);
+            p.qualified_name(&assignment.id);
+            p.text(" = ");
+            p.expr(&assignment.value);
+        });
+    }
+
+    fn on_conflict(&mut self, on_conflict: &OnConflict) {
+        self.kw("ON CONFLICT");
+        match &on_conflict.conflict_target {
+            Some(ConflictTarget::Columns { columns, selection }) => {
+                self.text(" ");
+                self.idents(columns);
+                if let Some(selection) = selection {
+                    self.text(" ");
+                    self.kw("WHERE");
+                    self.text(" ");
+                    self.expr(selection);
+                }
+            }
+            Some(ConflictTarget::OnConstraint(name)) => {
+                self.text(" ");
+                self.kw("ON CONSTRAINT");
+                self.text(" ");
+                self.object_name(name);
+            }
+            None => {}
+        }
+        self.text(" ");
+        match &on_conflict.action {
+            OnConflictAction::DoNothing => self.kw("DO NOTHING"),
+            OnConflictAction::DoUpdate {
+                assignments,
+                selection,
+            } => {
+                self.kw("DO UPDATE SET");
+                self.assignments(assignments);
+                self.indented(|p| p.where_clause(selection));
+            }
+        }
+    }
+
+    fn merge_clause(&mut self, clause: &MergeClause) {
+        self.element(clause, |p| {
+            p.kw("WHEN");
+            p.text(" ");
+            p.kw(&clause.kind.to_string());
+            if let Some(predicate) = &clause.predicate {
+                p.text(" ");
+                p.kw("AND");
+                p.text(" ");
+                p.expr(predicate);
+            }
+            p.text(" ");
+            p.kw("THEN");
+            p.indented(|p| {
+                p.line(" ");
+                match &clause.action {
+                    MergeAction::Update { assignments } => {
+                        p.kw("UPDATE SET");
+                        p.assignments(assignments);
+                    }
+                    MergeAction::Delete => p.kw("DELETE"),
+                    MergeAction::Insert { columns, values } => {
+                        p.kw("INSERT");
+                        p.text(" ");
+                        if !columns.is_empty() {
+                            p.idents(columns);
+                            p.text(" ");
+                        }
+                        p.kw("VALUES");
+                        p.text(" ");
+                        p.exprs(values);
+                    }
+                }
+            });
+        });
+    }
+
+    fn transaction_modes(&mut self, modes: &[TransactionMode]) {
+        for (i, mode) in modes.iter().enumerate() {
+            self.text(if i == 0 { " " } else { ", " });
+            self.kw(&mode.to_string());
+        }
+    }
+
+    fn returning(&mut self, returning: &[SQLSelectItem]) {
+        if !returning.is_empty() {
+            self.line(" ");
+            self.kw("RETURNING");
+            self.clause_list(returning, |p, item| p.select_item(item));
+        }
+    }
+
+    fn select_item(&mut self, item: &SQLSelectItem) {
+        self.element(item, |p| match item {
+            SQLSelectItem::UnnamedExpression(expr) => p.expr(expr),
+            SQLSelectItem::ExpressionWithAlias { expr, alias, .. } => {
+                p.expr(expr);
+                p.text(" ");
+                p.kw("AS");
+                p.text(" ");
+                p.ident(alias);
+            }
+            SQLSelectItem::QualifiedWildcard(prefix, _) => {
+                p.object_name(prefix);
+                p.text(".*");
+            }
+            SQLSelectItem::Wildcard(_) => p.text("*"),
+        });
+    }
+
+    fn table_factor(&mut self, relation: &TableFactor) {
+        self.element(relation, |p| {
+            let alias = match relation {
+                TableFactor::Table {
+                    name,
+                    alias,
+                    args,
+                    with_hints,
+                    ..
+                } => {
+                    p.object_name(name);
+                    if !args.is_empty() {
+                        p.exprs(args);
+                    }
+                    if !with_hints.is_empty() {
+                        // hints follow the alias
+                        p.table_alias(alias);
+                        p.text(" ");
+                        p.kw("WITH");
+                        p.text(" ");
+                        p.exprs(with_hints);
+                        return;
+                    }
+                    alias
+                }
+                TableFactor::Derived {
+                    lateral,
+                    subquery,
+                    alias,
+                    ..
+                } => {
+                    if *lateral {
+                        p.kw("LATERAL");
+                        p.text(" ");
+                    }
+                    p.subquery(subquery);
+                    alias
+                }
+            };
+            p.table_alias(alias);
+        });
+    }
+
+    fn alias(&mut self, alias: &Option<SQLIdent>) {
+        if let Some(alias) = alias {
+            self.text(" ");
+            self.kw("AS");
+            self.text(" ");
+            self.ident(alias);
+        }
+    }
+
+    fn table_alias(&mut self, alias: &Option<TableAlias>) {
+        if let Some(alias) = alias {
+            self.text(" ");
+            self.kw("AS");
+            self.text(" ");
+            self.ident(&alias.name);
+            if !alias.columns.is_empty() {
+                self.text(" ");
+                self.idents(&alias.columns);
+            }
+        }
+    }
+
+    fn join(&mut self, join: &Join) {
+        let (keyword, constraint) = match &join.join_operator {
+            JoinOperator::Inner(constraint) => ("JOIN", Some(constraint)),
+            JoinOperator::LeftOuter(constraint) => ("LEFT JOIN", Some(constraint)),
+            JoinOperator::RightOuter(constraint) => ("RIGHT JOIN", Some(constraint)),
+            JoinOperator::FullOuter(constraint) => ("FULL JOIN", Some(constraint)),
+            JoinOperator::Cross => ("CROSS JOIN", None),
+            JoinOperator::Implicit => {
+                self.text(",");
+                self.line(" ");
+                self.table_factor(&join.relation);
+                return;
+            }
+        };
+        self.line(" ");
+        self.leading_comments(join.span.start);
+        if let Some(JoinConstraint::Natural) = constraint {
+            self.kw("NATURAL");
+            self.text(" ");
+        }
+        self.kw(keyword);
+        self.text(" ");
+        self.table_factor(&join.relation);
+        match constraint {
+            Some(JoinConstraint::On(expr)) => self.group(|p| {
+                p.indented(|p| {
+                    p.line(" ");
+                    p.kw("ON");
+                    p.text(" ");
+                    p.indented(|p| p.expr(expr));
+                })
+            }),
+            Some(JoinConstraint::Using(attrs)) => self.group(|p| {
+                p.indented(|p| {
+                    p.line(" ");
+                    p.kw("USING");
+                    p.text(" ");
+                    p.idents(attrs);
+                })
+            }),
+            Some(JoinConstraint::Natural) | None => (),
+        }
+    }
+
+    fn order_by(&mut self, order_by: &SQLOrderByExpr) {
+        self.element(order_by, |p| {
+            p.expr(&order_by.expr);
+            match order_by.asc {
+                Some(true) => {
+                    p.text(" ");
+                    p.kw("ASC");
+                }
+                Some(false) => {
+                    p.text(" ");
+                    p.kw("DESC");
+                }
+                None => (),
+            }
+            if let Some(nulls_first) = order_by.nulls_first {
+                p.text(" ");
+                p.kw(if nulls_first {
+                    "NULLS FIRST"
+                } else {
+                    "NULLS LAST"
+                });
+            }
+        });
+    }
+
+    fn column_def(&mut self, column: &SQLColumnDef) {
+        self.element(column, |p| {
+            p.ident(&column.name);
+            if column.data_type != SQLType::Unspecified {
+                p.text(" ");
+                p.display(&column.data_type);
+            }
+            for option in &column.options {
+                p.text(" ");
+                p.column_option(option);
+            }
+        });
+    }
+
+    fn function_arg(&mut self, arg: &FunctionArg) {
+        self.element(arg, |p| {
+            if let Some(mode) = arg.mode {
+                p.kw(&mode.to_string());
+                p.text(" ");
+            }
+            if let Some(name) = &arg.name {
+                p.ident(name);
+                p.text(" ");
+            }
+            p.display(&arg.data_type);
+            if let Some(default) = &arg.default {
+                p.text(" = ");
+                p.expr(default);
+            }
+            if arg.output {
+                p.text(" ");
+                p.kw("OUTPUT");
+            }
+        });
+    }
+
+    fn sequence_options(&mut self, options: &[SequenceOption]) {
+        for option in options {
+            self.text(" ");
+            match option {
+                SequenceOption::As { data_type } => {
+                    self.kw("AS");
+                    self.text(" ");
+                    self.display(data_type);
+                }
+                SequenceOption::IncrementBy { value } => self.keyword_expr("INCREMENT BY", value),
+                SequenceOption::MinValue { value: Some(value) } => {
+                    self.keyword_expr("MINVALUE", value)
+                }
+                SequenceOption::MaxValue { value: Some(value) } => {
+                    self.keyword_expr("MAXVALUE", value)
+                }
+                SequenceOption::StartWith { value } => self.keyword_expr("START WITH", value),
+                SequenceOption::Restart { value: Some(value) } => {
+                    self.keyword_expr("RESTART WITH", value)
+                }
+                SequenceOption::Cache { value } => self.keyword_expr("CACHE", value),
+                SequenceOption::OwnedBy {
+                    column: Some(column),
+                } => {
+                    self.kw("OWNED BY");
+                    self.text(" ");
+                    self.object_name(column);
+                }
+                other => self.kw(&other.to_string()),
+            }
+        }
+    }
+
+    fn keyword_expr(&mut self, keyword: &str, expr: &ASTNode) {
+        self.kw(keyword);
+        self.text(" ");
+        self.expr(expr);
+    }
+
+    fn column_option(&mut self, option: &ColumnOptionDef) {
+        self.element(option, |p| {
+            if let Some(name) = &option.name {
+                p.kw("CONSTRAINT");
+                p.text(" ");
+                p.ident(name);
+                p.text(" ");
+            }
+            match &option.option {
+                ColumnOption::Default(expr) => {
+                    p.kw("DEFAULT");
+                    p.text(" ");
+                    p.expr(expr);
+                }
+                ColumnOption::ForeignKey {
+                    foreign_table,
+                    referred_columns,
+                    actions,
+                } => {
+                    p.kw("REFERENCES");
+                    p.text(" ");
+                    p.object_name(foreign_table);
+                    if !referred_columns.is_empty() {
+                        p.idents(referred_columns);
+                    }
+                    for action in actions {
+                        p.text(" ");
+                        p.kw(&action.to_string());
+                    }
+                }
+                ColumnOption::Check(expr) => {
+                    p.kw("CHECK");
+                    p.text(" (");
+                    p.expr(expr);
+                    p.text(")");
+                }
+                ColumnOption::Collate(collation) => {
+                    p.kw("COLLATE");
+                    p.text(" ");
+                    p.object_name(collation);
+                }
+                other => p.kw(&other.to_string()),
+            }
+        });
+    }
+
+    fn alter_table_operation(&mut self, operation: &AlterTableOperation) {
+        self.element(operation, |p| match operation {
+            AlterTableOperation::AddConstraint(constraint) => {
+                p.kw("ADD");
+                p.text(" ");
+                p.table_constraint(constraint);
+            }
+            AlterTableOperation::AddColumn {
+                column_keyword,
+                column_def,
+                ..
+            } => {
+                p.kw(if *column_keyword { "ADD COLUMN" } else { "ADD" });
+                p.text(" ");
+                p.column_def(column_def);
+            }
+            AlterTableOperation::DropConstraint { name, .. } => {
+                p.kw("DROP CONSTRAINT");
+                p.text(" ");
+                p.ident(name);
+            }
+            AlterTableOperation::DropColumn {
+                column_name,
+                if_exists,
+                cascade,
+                ..
+            } => {
+                p.kw("DROP COLUMN");
+                if *if_exists {
+                    p.text(" ");
+                    p.kw("IF EXISTS");
+                }
+                p.text(" ");
+                p.ident(column_name);
+                if *cascade {
+                    p.text(" ");
+                    p.kw("CASCADE");
+                }
+            }
+            AlterTableOperation::RenameColumn {
+                old_column_name,
+                new_column_name,
+                ..
+            } => {
+                p.kw("RENAME COLUMN");
+                p.text(" ");
+                p.ident(old_column_name);
+                p.text(" ");
+                p.kw("TO");
+                p.text(" ");
+                p.ident(new_column_name);
+            }
+            AlterTableOperation::RenameTable { table_name, .. } => {
+                p.kw("RENAME TO");
+                p.text(" ");
+                p.object_name(table_name);
+            }
+            AlterTableOperation::AlterColumn {
+                column_name, op, ..
+            } => {
+                p.kw("ALTER COLUMN");
+                p.text(" ");
+                p.ident(column_name);
+                p.text(" ");
+                match op {
+                    AlterColumnOperation::SetDefault { value } => {
+                        p.kw("SET DEFAULT");
+                        p.text(" ");
+                        p.expr(value);
+                    }
+                    AlterColumnOperation::SetDataType { data_type } => {
+                        p.kw("SET DATA TYPE");
+                        p.text(" ");
+                        p.display(data_type);
+                    }
+                    _ => p.kw(&op.to_string()),
+                }
+            }
+        });
+    }
+
+    fn table_constraint(&mut self, constraint: &TableConstraint) {
+        self.element(constraint, |p| {
+            let name = match constraint {
+                TableConstraint::Unique { name, .. }
+                | TableConstraint::ForeignKey { name, .. }
+                | TableConstraint::Check { name, .. } => name,
+            };
+            if let Some(name) = name {
+                p.kw("CONSTRAINT");
+                p.text(" ");
+                p.ident(name);
+                p.text(" ");
+            }
+            match constraint {
+                TableConstraint::Unique {
+                    columns,
+                    is_primary,
+                    ..
+                } => {
+                    p.kw(if *is_primary { "PRIMARY KEY" } else { "UNIQUE" });
+                    p.text(" ");
+                    p.idents(columns);
+                }
+                TableConstraint::ForeignKey {
+                    columns,
+                    foreign_table,
+                    referred_columns,
+                    ..
+                } => {
+                    p.kw("FOREIGN KEY");
+                    p.text(" ");
+                    p.idents(columns);
+                    p.text(" ");
+                    p.kw("REFERENCES");
+                    p.text(" ");
+                    p.object_name(foreign_table);
+                    p.idents(referred_columns);
+                }
+                TableConstraint::Check { expr, .. } => {
+                    p.kw("CHECK");
+                    p.text(" (");
+                    p.expr(expr);
+                    p.text(")");
+                }
+            }
+        });
+    }
+
+    fn value(&mut self, value: &Value) {
+        match value {
+            Value::Boolean(true) => self.kw("TRUE"),
+            Value::Boolean(false) => self.kw("FALSE"),
+            Value::Null => self.kw("NULL"),
+            Value::Date(v) | Value::Time(v) | Value::Timestamp(v) => {
+                self.kw(match value {
+                    Value::Date(_) => "DATE",
+                    Value::Time(_) => "TIME",
+                    _ => "TIMESTAMP",
+                });
+                self.text(" ");
+                self.display(Value::SingleQuotedString(v.clone()));
+            }
+            _ => self.display(value),
+        }
+    }
+
+    fn operator(&mut self, op: &SQLOperator) {
+        match op {
+            SQLOperator::And
+            | SQLOperator::Or
+            | SQLOperator::Xor
+            | SQLOperator::IntegerDivide
+            | SQLOperator::Not
+            | SQLOperator::Like
+            | SQLOperator::NotLike => self.kw(&op.to_string()),
+            _ => self.display(op),
+        }
+    }
+
+    fn negated(&mut self, negated: bool) {
+        if negated {
+            self.kw("NOT");
+            self.text(" ");
+        }
+    }
+
+    fn expr(&mut self, expr: &ASTNode) {
+        self.element(expr, |p| {
+            match expr {
+                // A lone identifier is printed as is, as some reserved words
+                // (e.g. CURRENT_DATE) are parsed as one
+                ASTNode::SQLIdentifier(_, _) | ASTNode::SQLWildcard(_) => p.display(expr),
+                ASTNode::SQLQualifiedWildcard(idents, _) => {
+                    p.qualified_name(idents);
+                    p.text(".*");
+                }
+                ASTNode::SQLCompoundIdentifier(idents, _) => p.qualified_name(idents),
+                ASTNode::SQLIsNull(expr, _) => {
+                    p.expr(expr);
+                    p.text(" ");
+                    p.kw("IS NULL");
+                }
+                ASTNode::SQLIsNotNull(expr, _) => {
+                    p.expr(expr);
+                    p.text(" ");
+                    p.kw("IS NOT NULL");
+                }
+                ASTNode::SQLInList {
+                    expr,
+                    list,
+                    negated,
+                    ..
+                } => {
+                    p.expr(expr);
+                    p.text(" ");
+                    p.negated(*negated);
+                    p.kw("IN");
+                    p.text(" ");
+                    p.exprs(list);
+                }
+                ASTNode::SQLInSubquery {
+                    expr,
+                    subquery,
+                    negated,
+                    ..
+                } => {
+                    p.expr(expr);
+                    p.text(" ");
+                    p.negated(*negated);
+                    p.kw("IN");
+                    p.text(" ");
+                    p.subquery(subquery);
+                }
+                ASTNode::SQLBetween {
+                    expr,
+                    negated,
+                    low,
+                    high,
+                    ..
+                } => {
+                    p.expr(expr);
+                    p.text(" ");
+                    p.negated(*negated);
+                    p.kw("BETWEEN");
+                    p.text(" ");
+                    p.expr(low);
+                    p.text(" ");
+                    p.kw("AND");
+                    p.text(" ");
+                    p.expr(high);
+                }
+                ASTNode::SQLBinaryExpr { op, .. }
+                    if *op == SQLOperator::And || *op == SQLOperator::Or =>
+                {
+                    // print a chain of `AND`s (or `OR`s) one operand per line
+                    let mut operands = vec![];
+                    collect_operands(expr, op, &mut operands);
+                    p.group(|p| {
+                        for (i, operand) in operands.iter().enumerate() {
+                            if i > 0 {
+                                p.line(" ");
+                                p.operator(op);
+                                p.text(" ");
+                            }
+                            p.expr(operand);
+                        }
+                    });
+                }
+                ASTNode::SQLBinaryExpr {
+                    left, op, right, ..
+                } => {
+                    p.expr(left);
+                    p.text(" ");
+                    p.operator(op);
+                    p.text(" ");
+                    p.expr(right);
+                }
+                ASTNode::SQLCast {
+                    expr, data_type, ..
+                } => {
+                    p.kw("CAST");
+                    p.text("(");
+                    p.expr(expr);
+                    p.text(" ");
+                    p.kw("AS");
+                    p.text(" ");
+                    p.display(data_type);
+                    p.text(")");
+                }
+                ASTNode::SQLExtract { field, expr, .. } => {
+                    p.kw("EXTRACT");
+                    p.text("(");
+                    p.kw(&field.to_string());
+                    p.text(" ");
+                    p.kw("FROM");
+                    p.text(" ");
+                    p.expr(expr);
+                    p.text(")");
+                }
+                ASTNode::SQLCollate {
+                    expr, collation, ..
+                } => {
+                    p.expr(expr);
+                    p.text(" ");
+                    p.kw("COLLATE");
+                    p.text(" ");
+                    p.display(collation);
+                }
+                ASTNode::SQLNested(expr, _) => {
+                    p.group(|p| {
+                        p.text("(");
+                        p.indented(|p| {
+                            p.line("");
+                            p.expr(expr);
+                        });
+                        p.line("");
+                        p.text(")");
+                    });
+                }
+                ASTNode::SQLUnary { operator, expr, .. } => {
+                    p.operator(operator);
+                    p.text(" ");
+                    p.expr(expr);
+                }
+                ASTNode::SQLValue(value, _) => p.value(value),
+                ASTNode::SQLPlaceholder(s, _) => p.text(s),
+                ASTNode::SQLFunction(function) => p.function(function),
+                ASTNode::SQLCase {
+                    operand,
+                    conditions,
+                    results,
+                    else_result,
+                    ..
+                } => p.group(|p| {
+                    p.kw("CASE");
+                    if let Some(operand) = operand {
+                        p.text(" ");
+                        p.expr(operand);
+                    }
+                    p.indented(|p| {
+                        for (condition, result) in conditions.iter().zip(results) {
+                            p.line(" ");
+                            p.kw("WHEN");
+                            p.text(" ");
+                            p.expr(condition);
+                            p.text(" ");
+                            p.kw("THEN");
+                            p.text(" ");
+                            p.expr(result);
+                        }
+                        if let Some(else_result) = else_result {
+                            p.line(" ");
+                            p.kw("ELSE");
+                            p.text(" ");
+                            p.expr(else_result);
+                        }
+                    });
+                    p.line(" ");
+                    p.kw("END");
+                }),
+                ASTNode::SQLExists(query, _) => {
+                    p.kw("EXISTS");
+                    p.text(" ");
+                    p.subquery(query);
+                }
+                ASTNode::SQLSubquery(query, _) => p.subquery(query),
+            }
+        });
+    }
+
+    fn function(&mut self, function: &SQLFunction) {
+        self.display(&function.name);
+        self.group(|p| {
+            p.text("(");
+            p.indented(|p| {
+                p.line("");
+                if function.distinct {
+                    p.kw("DISTINCT");
+                    p.text(" ");
+                }
+                p.list(&function.args, |p, arg| p.expr(arg));
+            });
+            p.line("");
+            p.text(")");
+        });
+        if let Some(ref over) = function.over {
+            self.text(" ");
+            self.kw("OVER");
+            self.text(" ");
+            self.window_spec(over);
+        }
+    }
+
+    fn window_spec(&mut self, spec: &SQLWindowSpec) {
+        self.group(|p| {
+            p.text("(");
+            p.indented(|p| {
+                let mut first = true;
+                let mut clause = |p: &mut Self| {
+                    p.line(if first { "" } else { " " });
+                    first = false;
+                };
+                if !spec.partition_by.is_empty() {
+                    clause(p);
+                    p.kw("PARTITION BY");
+                    p.text(" ");
+                    p.list(&spec.partition_by, |p, expr| p.expr(expr));
+                }
+                if !spec.order_by.is_empty() {
+                    clause(p);
+                    p.kw("ORDER BY");
+                    p.text(" ");
+                    p.list(&spec.order_by, |p, order_by| p.order_by(order_by));
+                }
+                if let Some(ref frame) = spec.window_frame {
+                    clause(p);
+                    p.kw(&frame.units.to_string());
+                    p.text(" ");
+                    if let Some(ref end_bound) = frame.end_bound {
+                        p.kw("BETWEEN");
+                        p.text(" ");
+                        p.window_frame_bound(&frame.start_bound);
+                        p.text(" ");
+                        p.kw("AND");
+                        p.text(" ");
+                        p.window_frame_bound(end_bound);
+                    } else {
+                        p.window_frame_bound(&frame.start_bound);
+                    }
+                }
+            });
+            p.line("");
+            p.text(")");
+        });
+    }
+
+    fn window_frame_bound(&mut self, bound: &SQLWindowFrameBound) {
+        match bound {
+            SQLWindowFrameBound::Preceding(Some(n)) | SQLWindowFrameBound::Following(Some(n)) => {
+                self.display(n);
+                self.text(" ");
+                self.kw(match bound {
+                    SQLWindowFrameBound::Preceding(_) => "PRECEDING",
+                    _ => "FOLLOWING",
+                });
+            }
+            _ => self.kw(&bound.to_string()),
+        }
+    }
+}
+
+/// Whether `comment` follows code ending at `end` on the same line
+fn follows_on_same_line(comment: &SQLComment, end: Location) -> bool {
+    comment.span.start.line == end.line && comment.span.start.offset >= end.offset
+}
+
+/// A column definition or a table constraint in `CREATE TABLE`
+enum TableElement<'e> {
+    Column(&'e SQLColumnDef),
+    Constraint(&'e TableConstraint),
+}
+
+/// Flatten a left- or right-nested chain of `op` into its operands.
+fn collect_operands<'e>(expr: &'e ASTNode, op: &SQLOperator, operands: &mut Vec<&'e ASTNode>) {
+    match expr {
+        ASTNode::SQLBinaryExpr {
+            left,
+            op: expr_op,
+            right,
+            ..
+        } if expr_op == op => {
+            collect_operands(left, op, operands);
+            collect_operands(right, op, operands);
+        }
+        _ => operands.push(expr),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialect::{
+        GenericSqlDialect, MsSqlDialect, MySqlDialect, PostgreSqlDialect, SQLiteDialect,
+    };
+    use crate::sqlparser::Parser;
+    use crate::test_utils::all_dialects;
+
+    fn format(sql: &str, options: &FormatOptions) -> String {
+        let statements = all_dialects().parse_sql_statements(sql).unwrap();
+        let formatted = format_statements(&statements, options);
+        // the formatted SQL must parse back to the same AST
+        let reparsed = all_dialects().parse_sql_statements(&formatted).unwrap();
+        assert_eq!(statements, reparsed);
+        formatted
+    }
+
+    #[test]
+    fn format_query() {
+        let sql = "WITH c AS (SELECT x FROM z) \
+                   SELECT DISTINCT a, count(*) AS n \
+                   FROM t AS t1 \
+                   LEFT JOIN u ON t1.id = u.id AND t1.other_column = u.other_column \
+                   JOIN c USING(x) \
+                   WHERE a IN (SELECT a FROM v WHERE v.flag = true AND v.date > DATE '2019-01-01' \
+                   AND v.other IS NOT NULL) \
+                   AND b BETWEEN 1 AND 10 \
+                   GROUP BY a HAVING count(*) > 1 ORDER BY a DESC LIMIT 10";
+        assert_eq!(
+            format(sql, &FormatOptions::default()),
+            "WITH c AS (SELECT x FROM z)
+SELECT DISTINCT
+  a,
+  count(*) AS n
+FROM t AS t1
+  LEFT JOIN u ON t1.id = u.id AND t1.other_column = u.other_column
+  JOIN c USING (x)
+WHERE a IN (
+  SELECT a
+  FROM v
+  WHERE v.flag = TRUE AND v.date > DATE '2019-01-01' AND v.other IS NOT NULL
+)
+  AND b BETWEEN 1 AND 10
+GROUP BY a
+HAVING count(*) > 1
+ORDER BY a DESC
+LIMIT 10;
+"
+        );
+    }
+
+    #[test]
+    fn format_join_using() {
+        let sql = "SELECT * FROM some_long_table_name AS a \
+                   JOIN another_long_table_name AS b USING (id, other_id) \
+                   WHERE a.x = 1 AND b.y IN (SELECT y FROM z WHERE q = 1 AND r = 2 AND s = 3)";
+        let options = FormatOptions {
+            max_width: 50,
+            ..FormatOptions::default()
+        };
+        assert_eq!(
+            format(sql, &options),
+            "SELECT *
+FROM some_long_table_name AS a
+  JOIN another_long_table_name AS b
+    USING (id, other_id)
+WHERE a.x = 1
+  AND b.y IN (
+    SELECT y
+    FROM z
+    WHERE q = 1 AND r = 2 AND s = 3
+  );
+"
+        );
+    }
+
+    #[test]
+    fn format_options() {
+        let sql = "SELECT a, CASE WHEN b > 1 THEN 'big' ELSE 'small' END AS size \
+                   FROM t JOIN u ON t.id = u.id AND t.other_column = u.other_column \
+                   WHERE c NOT IN (1, 2, 3)";
+        let options = FormatOptions {
+            indent: 4,
+            max_width: 40,
+            keyword_case: KeywordCase::Lower,
+            comma_style: CommaStyle::Leading,
+            dialect: None,
+        };
+        assert_eq!(
+            format(sql, &options),
+            "select
+    a
+    , case
+        when b > 1 then 'big'
+        else 'small'
+    end as size
+from t
+    join u
+        on t.id = u.id
+            and t.other_column = u.other_column
+where c not in (1, 2, 3);
+"
+        );
+    }
+
+    #[test]
+    fn format_quoted_identifiers() {
+        let sql = "SELECT t.user, \"a b\".* FROM t AS user JOIN \"end\" ON t.id = \"end\".id \
+                   WHERE key > 1";
+        let options = |dialect| FormatOptions {
+            dialect: Some(dialect),
+            ..FormatOptions::default()
+        };
+        let generic = format(sql, &options(&GenericSqlDialect {}));
+        assert_eq!(
+            generic,
+            "SELECT
+  t.user,
+  \"a b\".*
+FROM t AS user
+  JOIN \"end\" ON t.id = \"end\".id
+WHERE key > 1;
+"
+        );
+        // Already quoted identifiers are left alone, lone identifiers in
+        // expressions aren't quoted
+        // (the quotes become part of the identifiers, so the result doesn't
+        // parse back to the same AST)
+        let statements = all_dialects().parse_sql_statements(sql).unwrap();
+        assert_eq!(
+            format_statements(&statements, &options(&PostgreSqlDialect {})),
+            "SELECT
+  t.\"user\",
+  \"a b\".*
+FROM t AS \"user\"
+  JOIN \"end\" ON t.id = \"end\".id
+WHERE key > 1;
+"
+        );
+        assert_eq!(format(sql, &options(&MySqlDialect {})), generic);
+        let statements = all_dialects()
+            .parse_sql_statements("CREATE TABLE orders (key int, \"a b\" text)")
+            .unwrap();
+        assert_eq!(
+            format_statement(&statements[0], &options(&MySqlDialect {})),
+            "CREATE TABLE orders (\n  `key` int,\n  \"a b\" text\n)"
+        );
+        let statement = SQLStatement::SQLDelete {
+            table_name: SQLObjectName(vec!["my table".to_string(), "a]b".to_string()]),
+            alias: None,
+            using: None,
+            joins: vec![],
+            selection: None,
+            returning: vec![],
+            span: Span::empty(),
+        };
+        assert_eq!(
+            format_statement(&statement, &options(&MsSqlDialect {})),
+            "DELETE FROM [my table].[a]]b]"
+        );
+    }
+
+    #[test]
+    fn format_mysql_operators() {
+        let sql = "SELECT a XOR b <=> c, a DIV 2 FROM t";
+        let statements = Parser::parse_sql(&MySqlDialect {}, sql.to_string()).unwrap();
+        let formatted = format_statements(&statements, &FormatOptions::default());
+        assert_eq!("SELECT\n  a XOR b <=> c,\n  a DIV 2\nFROM t;\n", formatted);
+        let reparsed = Parser::parse_sql(&MySqlDialect {}, formatted).unwrap();
+        assert_eq!(statements, reparsed);
+    }
+
+    #[test]
+    fn format_sqlite_column_types() {
+        let sql = "CREATE TABLE t (a UNSIGNED BIG INT, b NVARCHAR(10), c)";
+        let statements = Parser::parse_sql(&SQLiteDialect {}, sql.to_string()).unwrap();
+        let formatted = format_statements(&statements, &FormatOptions::default());
+        assert_eq!(
+            "CREATE TABLE t (\n  a UNSIGNED BIG INT,\n  b NVARCHAR(10),\n  c\n);\n",
+            formatted
+        );
+        let reparsed = Parser::parse_sql(&SQLiteDialect {}, formatted).unwrap();
+        assert_eq!(statements, reparsed);
+    }
+
+    #[test]
+    fn format_statements_roundtrip() {
+        let sql = "CREATE TABLE t (a int NOT NULL, b character varying(10) DEFAULT 'x', \
+                   CONSTRAINT fk FOREIGN KEY (b) REFERENCES u(c)); \
+                   INSERT INTO t (a, b) VALUES (1, 'a'), (2, 'b'); \
+                   UPDATE t SET a = 1, b = 2 WHERE c = 3; \
+                   DELETE FROM t WHERE NOT a; \
+                   INSERT INTO t VALUES (1) ON CONFLICT (a) DO UPDATE SET b = 1 WHERE t.a > 0 \
+                   RETURNING a, b AS c; \
+                   INSERT INTO t VALUES (1) ON CONFLICT ON CONSTRAINT pk DO NOTHING; \
+                   INSERT INTO t VALUES (1) ON CONFLICT (a, b) WHERE c > 0 DO NOTHING; \
+                   UPDATE t SET a = 1 RETURNING *; DELETE FROM t RETURNING a; \
+                   UPDATE t AS x SET x.a = u.a FROM u JOIN v ON u.b = v.b WHERE x.b = u.b; \
+                   DELETE FROM t AS x USING u WHERE x.a = u.a; \
+                   START TRANSACTION ISOLATION LEVEL SERIALIZABLE, READ ONLY; SAVEPOINT s; \
+                   ROLLBACK TO SAVEPOINT s; RELEASE SAVEPOINT s; COMMIT AND CHAIN; BEGIN; \
+                   SET TRANSACTION READ WRITE; ROLLBACK; \
+                   MERGE INTO t USING (SELECT 1 AS a) AS s ON t.a = s.a \
+                   WHEN MATCHED AND t.b > 1 THEN DELETE WHEN MATCHED THEN UPDATE SET b = 2 \
+                   WHEN NOT MATCHED THEN INSERT (a) VALUES (s.a); \
+                   CREATE MATERIALIZED VIEW v WITH (foo = 'bar') AS SELECT 1 UNION ALL SELECT 2; \
+                   ALTER TABLE t ADD CONSTRAINT c CHECK (a > 0); \
+                   ALTER TABLE t ADD COLUMN d int NOT NULL, DROP COLUMN IF EXISTS e CASCADE, \
+                   RENAME COLUMN f TO g, RENAME TO u, ALTER COLUMN a SET DEFAULT 1, \
+                   ALTER COLUMN a SET DATA TYPE bigint, ALTER COLUMN b DROP NOT NULL; \
+                   DROP TABLE IF EXISTS t, u CASCADE; \
+                   CREATE OR REPLACE FUNCTION f(IN a int, b text = 'x') RETURNS TABLE (c int) \
+                   LANGUAGE sql STABLE SECURITY DEFINER COST 10 AS 'SELECT 1'; \
+                   CREATE PROCEDURE p(a int OUTPUT) WITH RECOMPILE AS BEGIN SELECT 1; END; \
+                   CREATE TEMPORARY TABLE IF NOT EXISTS c AS SELECT a FROM t; \
+                   CREATE UNLOGGED TABLE l (a int); CREATE OR REPLACE VIEW w AS SELECT 1; \
+                   CREATE SCHEMA IF NOT EXISTS s; CREATE DATABASE d; DROP SCHEMA s CASCADE; \
+                   CREATE SEQUENCE q AS int INCREMENT BY -1 NO MINVALUE MAXVALUE 9 CACHE 2 CYCLE \
+                   OWNED BY t.a; ALTER SEQUENCE q RESTART WITH 1 OWNED BY NONE; \
+                   CREATE UNIQUE INDEX CONCURRENTLY IF NOT EXISTS i ON t USING btree \
+                   (a DESC NULLS LAST, lower(b)) INCLUDE (c) WHERE d > 0; DROP INDEX i; \
+                   CREATE TABLE o (a int NULL CONSTRAINT pk PRIMARY KEY, b int UNIQUE \
+                   REFERENCES t ON DELETE SET NULL ON UPDATE NO ACTION CHECK (b > 0), \
+                   c text COLLATE \"C\", d int GENERATED BY DEFAULT AS IDENTITY); \
+                   REPLACE INTO t VALUES (1) ON DUPLICATE KEY UPDATE a = a / 2; \
+                   CREATE TABLE m (id int NOT NULL AUTO_INCREMENT) ENGINE=InnoDB DEFAULT CHARSET=utf8; \
+                   SELECT a FROM t LIMIT 1 OFFSET 2; \
+                   INSERT OR IGNORE INTO t VALUES (1); \
+                   CREATE TABLE s (id int PRIMARY KEY AUTOINCREMENT, b varchar(10)) WITHOUT ROWID; \
+                   PRAGMA s.cache_size = 10; PRAGMA table_info(s); ATTACH DATABASE 'x.db' AS x; \
+                   SELECT sum(x) OVER (PARTITION BY y ORDER BY z \
+                   ROWS BETWEEN 1 PRECEDING AND UNBOUNDED FOLLOWING), \
+                   CAST(a AS bigint), EXTRACT(YEAR FROM d), \
+                   EXISTS (SELECT * FROM (SELECT 1) AS s (x) CROSS JOIN w), - a \
+                   FROM t, u ORDER BY 1 OFFSET 2 ROWS FETCH FIRST 50 PERCENT ROWS ONLY";
+        let formatted = format(sql, &FormatOptions::default());
+        assert!(formatted.starts_with(
+            "CREATE TABLE t (
+  a int NOT NULL,
+  b character varying(10) DEFAULT 'x',
+  CONSTRAINT fk FOREIGN KEY (b) REFERENCES u(c)
+);
+
+INSERT INTO t (a, b)
+VALUES (1, 'a'), (2, 'b');
+"
+        ));
+        format(
+            sql,
+            &FormatOptions {
+                max_width: 0,
+                comma_style: CommaStyle::Leading,
+                ..FormatOptions::default()
+            },
+        );
+    }
+
+    #[test]
+    fn format_with_comments() {
+        let sql = "-- Daily report
+SELECT a, -- the a column
+  b /* inline */ , c
+FROM t -- main table
+  /* join users */
+  JOIN u ON t.id = u.id
+WHERE x = 1 -- first
+  AND y = 2; -- end of query
+CREATE TABLE t (
+  -- the key
+  id int, name text /* name */);
+-- final comment";
+        let (statements, comments) =
+            Parser::parse_sql_with_comments(&GenericSqlDialect {}, sql.to_string()).unwrap();
+        let formatted =
+            format_statements_with_comments(&statements, &comments, &FormatOptions::default());
+        assert_eq!(
+            formatted,
+            "-- Daily report
+SELECT
+  a, -- the a column
+  b, /* inline */
+  c
+FROM t -- main table
+  /* join users */
+  JOIN u ON t.id = u.id
+WHERE x = 1 -- first
+  AND y = 2; -- end of query
+
+CREATE TABLE t (
+  -- the key
+  id int,
+  name text /* name */
+);
+-- final comment
+"
+        );
+        let (reparsed, reparsed_comments) =
+            Parser::parse_sql_with_comments(&GenericSqlDialect {}, formatted).unwrap();
+        assert_eq!(statements, reparsed);
+        let texts = |comments: &[SQLComment]| -> Vec<String> {
+            comments.iter().map(|c| c.to_string()).collect()
+        };
+        assert_eq!(texts(&comments), texts(&reparsed_comments));
+
+        // a comment after the statement's end goes after its semicolon,
+        // rather than into its last parenthesized list
+        let sql = "CREATE INDEX i ON t (id); -- c
+SELECT * FROM t WHERE x IN (1, 2); -- d
+INSERT INTO t VALUES (1); /* e */
+MERGE INTO t USING s ON t.id = s.id WHEN NOT MATCHED THEN INSERT VALUES (s.id); -- f";
+        let (statements, comments) =
+            Parser::parse_sql_with_comments(&GenericSqlDialect {}, sql.to_string()).unwrap();
+        assert_eq!(
+            format_statements_with_comments(&statements, &comments, &FormatOptions::default()),
+            "CREATE INDEX i ON t (id); -- c
+
+SELECT *
+FROM t
+WHERE x IN (1, 2); -- d
+
+INSERT INTO t
+VALUES (1); /* e */
+
+MERGE INTO t
+USING s
+  ON t.id = s.id
+WHEN NOT MATCHED THEN
+  INSERT VALUES (s.id); -- f
+"
+        );
+
+        // a comment inside an expression forces it to be broken up
+        let sql = "SELECT f(a, /* b */ b)";
+        let (statements, comments) =
+            Parser::parse_sql_with_comments(&GenericSqlDialect {}, sql.to_string()).unwrap();
+        assert_eq!(
+            format_statements_with_comments(&statements, &comments, &FormatOptions::default()),
+            "SELECT f(\n  a, /* b */\n  b\n);\n"
+        );
+    }
+}
@@ -17,24 +17,123 @@
 use log::debug;
 
 use super::dialect::keywords;
-use super::dialect::Dialect;
+use super::dialect::{Dialect, GenericSqlDialect};
 use super::sqlast::*;
 use super::sqltokenizer::*;
 use std::error::Error;
 
+/// The category of a `ParserError`, for callers that need to react to a
+/// particular failure rather than just display it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParserErrorKind {
+    /// The input could not be split into tokens
+    TokenizerError,
+    /// A token other than the expected ones was found
+    UnexpectedToken,
+    /// The input ended while more tokens were expected
+    UnexpectedEof,
+    /// A literal could not be converted to a value, e.g. an out of range number
+    InvalidLiteral,
+    /// A construct that is well-formed but not valid SQL, e.g. `SELECT ALL DISTINCT`
+    InvalidSyntax,
+}
+
 #[derive(Debug, Clone, PartialEq)]
-pub enum ParserError {
-    TokenizerError(String),
-    ParserError(String),
+pub struct ParserError {
+    pub kind: ParserErrorKind,
+    /// A human-readable description, e.g. "Expected SET, found: WHERE"
+    pub message: String,
+    /// The location of the offending token (empty if unknown)
+    pub span: Span,
+    /// The alternatives that would have been accepted instead of `found`
+    pub expected: Vec<String>,
+    /// The token that caused the error, `None` at the end of input or when
+    /// the error is not about a single token (boxed to keep `Result`s small)
+    pub found: Option<Box<Token>>,
+}
+
+impl ParserError {
+    pub fn new(kind: ParserErrorKind, message: String, span: Span) -> Self {
+        ParserError {
+            kind,
+            message,
+            span,
+            expected: vec![],
+            found: None,
+        }
+    }
+
+    /// Format the error for display to a user: the message, followed by the
+    /// line of `sql` the error occurred on with the offending part
+    /// underlined. `sql` must be the text that was parsed.
+    pub fn render(&self, sql: &str) -> String {
+        let mut out = self.to_string();
+        let (start, end) = (self.span.start, self.span.end);
+        if self.span.is_empty() || start.offset > sql.len() {
+            return out;
+        }
+        let is_newline = |ch: char| ch == '\n' || ch == '\r';
+        let line_start = sql[..start.offset].rfind(is_newline).map_or(0, |i| i + 1);
+        let line_end = sql[start.offset..]
+            .find(is_newline)
+            .map_or(sql.len(), |i| start.offset + i);
+        // keep tabs, so that the caret lines up with the text above it
+        let indent: String = sql[line_start..start.offset]
+            .chars()
+            .map(|ch| if ch == '\t' { '\t' } else { ' ' })
+            .collect();
+        let width = sql[start.offset..end.offset.max(start.offset).min(line_end)]
+            .chars()
+            .count()
+            .max(1);
+        let gutter = " ".repeat(start.line.to_string().len());
+        out += &format!(
+            "\n{}--> line {}, column {}\n{} |\n{} | {}\n{} | {}{}",
+            gutter,
+            start.line,
+            start.column,
+            gutter,
+            start.line,
+            &sql[line_start..line_end],
+            gutter,
+            indent,
+            "^".repeat(width)
+        );
+        out
+    }
 }
 
 // Use `Parser::expected` instead, if possible
 macro_rules! parser_err {
-    ($MSG:expr) => {
-        Err(ParserError::ParserError($MSG.to_string()))
+    ($KIND:ident, $SPAN:expr, $MSG:expr) => {
+        Err(ParserError::new(
+            ParserErrorKind::$KIND,
+            $MSG.to_string(),
+            $SPAN,
+        ))
     };
 }
 
+/// An error encountered by `Parser::parse_sql_with_recovery`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseDiagnostic {
+    /// The position of the statement the error occurred in, counting from 0
+    /// and skipping empty statements; an index into `RecoveredParse::statements`
+    pub statement_index: usize,
+    pub error: ParserError,
+}
+
+/// The result of `Parser::parse_sql_with_recovery`
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RecoveredParse {
+    /// One entry per statement in the input, `None` if the statement could
+    /// not be parsed at all. Statements with errors in some of their
+    /// clauses may be present with those clauses left out.
+    pub statements: Vec<Option<SQLStatement>>,
+    /// All errors encountered, in the order of the statements they belong to
+    pub diagnostics: Vec<ParseDiagnostic>,
+}
+
 #[derive(PartialEq)]
 pub enum IsOptional {
     Optional,
@@ -44,71 +143,254 @@ use IsOptional::*;
 
 impl From<TokenizerError> for ParserError {
     fn from(e: TokenizerError) -> Self {
-        ParserError::TokenizerError(format!("{:?}", e))
+        ParserError::new(
+            ParserErrorKind::TokenizerError,
+            e.message,
+            Span::new(e.location, e.location),
+        )
     }
 }
 
 impl std::fmt::Display for ParserError {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(
-            f,
-            "sql parser error: {}",
-            match self {
-                ParserError::TokenizerError(s) => s,
-                ParserError::ParserError(s) => s,
-            }
-        )
+        write!(f, "sql parser error: {}", self.message)
     }
 }
 
 impl Error for ParserError {}
 
 /// SQL Parser
-pub struct Parser {
-    tokens: Vec<Token>,
+pub struct Parser<'a> {
+    /// The dialect whose parser hooks (see `Dialect::parse_statement` etc.)
+    /// are consulted before the default grammar
+    dialect: &'a dyn Dialect,
+    tokens: Vec<TokenWithSpan>,
     /// The index of the first unprocessed token in `self.tokens`
     index: usize,
+    /// Whether to recover from errors in the clauses of a statement, see
+    /// `parse_clause`
+    recover: bool,
+    /// The errors recovered from so far
+    errors: Vec<ParserError>,
 }
 
-impl Parser {
-    /// Parse the specified tokens
+impl<'a> Parser<'a> {
+    /// Parse the specified tokens. The resulting AST nodes will have empty
+    /// spans, use `Parser::new_with_spans` to keep track of locations.
     pub fn new(tokens: Vec<Token>) -> Self {
-        Parser { tokens, index: 0 }
+        Parser::new_with_spans(tokens.into_iter().map(TokenWithSpan::wrap).collect())
+    }
+
+    /// Parse the specified tokens, as returned by `Tokenizer::tokenize_with_span`
+    pub fn new_with_spans(tokens: Vec<TokenWithSpan>) -> Self {
+        Parser::new_with_dialect(&GenericSqlDialect {}, tokens)
+    }
+
+    /// Parse the specified tokens (as returned by `Tokenizer::tokenize_with_span`)
+    /// giving the `dialect` a chance to override the default grammar
+    pub fn new_with_dialect(dialect: &'a dyn Dialect, tokens: Vec<TokenWithSpan>) -> Self {
+        Parser {
+            dialect,
+            tokens,
+            index: 0,
+            recover: false,
+            errors: vec![],
+        }
     }
 
     /// Parse a SQL statement and produce an Abstract Syntax Tree (AST)
     pub fn parse_sql(dialect: &dyn Dialect, sql: String) -> Result<Vec<SQLStatement>, ParserError> {
         let mut tokenizer = Tokenizer::new(dialect, &sql);
-        let tokens = tokenizer.tokenize()?;
-        let mut parser = Parser::new(tokens);
+        let tokens = tokenizer.tokenize_with_span()?;
+        debug!("Parsing sql '{}'...", sql);
+        Parser::new_with_dialect(dialect, tokens).parse_statements()
+    }
+
+    /// Like `parse_sql`, but also return the comments found in the source
+    /// (in order), so that they can be re-emitted when printing the AST, see
+    /// `sqlformat::format_statements_with_comments`.
+    pub fn parse_sql_with_comments(
+        dialect: &dyn Dialect,
+        sql: String,
+    ) -> Result<(Vec<SQLStatement>, Vec<SQLComment>), ParserError> {
+        let mut tokenizer = Tokenizer::new(dialect, &sql);
+        let tokens = tokenizer.tokenize_with_span()?;
+        let comments = tokens
+            .iter()
+            .filter_map(|t| match &t.token {
+                Token::Whitespace(Whitespace::SingleLineComment { comment, .. }) => {
+                    Some(SQLComment {
+                        text: comment.trim_end_matches('\n').to_string(),
+                        multiline: false,
+                        span: t.span,
+                    })
+                }
+                Token::Whitespace(Whitespace::MultiLineComment(text)) => Some(SQLComment {
+                    text: text.to_string(),
+                    multiline: true,
+                    span: t.span,
+                }),
+                _ => None,
+            })
+            .collect();
+        debug!("Parsing sql '{}' with comments...", sql);
+        let statements = Parser::new_with_dialect(dialect, tokens).parse_statements()?;
+        Ok((statements, comments))
+    }
+
+    /// Parse statements separated by semicolons until the end of input
+    fn parse_statements(&mut self) -> Result<Vec<SQLStatement>, ParserError> {
         let mut stmts = Vec::new();
         let mut expecting_statement_delimiter = false;
-        debug!("Parsing sql '{}'...", sql);
         loop {
             // ignore empty statements (between successive statement delimiters)
-            while parser.consume_token(&Token::SemiColon) {
+            while self.consume_token(&Token::SemiColon) {
                 expecting_statement_delimiter = false;
             }
 
-            if parser.peek_token().is_none() {
+            if self.peek_token().is_none() {
                 break;
             } else if expecting_statement_delimiter {
-                return parser.expected("end of statement", parser.peek_token());
+                return self.expected("end of statement", self.peek_token());
             }
 
-            let statement = parser.parse_statement()?;
+            let statement = self.parse_statement()?;
             stmts.push(statement);
             expecting_statement_delimiter = true;
         }
         Ok(stmts)
     }
 
+    /// Parse a SQL script without stopping at the first error. Each error is
+    /// recorded, and parsing resumes at the next clause of the current
+    /// statement (e.g. `FROM` or `WHERE` in a `SELECT`) or, failing that,
+    /// at the next statement.
+    pub fn parse_sql_with_recovery(dialect: &dyn Dialect, sql: String) -> RecoveredParse {
+        let mut tokenizer = Tokenizer::new(dialect, &sql);
+        let (tokens, tokenizer_errors) = tokenizer.tokenize_with_recovery();
+        let mut parser = Parser::new_with_dialect(dialect, tokens);
+        parser.recover = true;
+        let mut result = RecoveredParse::default();
+        // the offsets at which each statement begins
+        let mut statement_starts = vec![];
+        debug!("Parsing sql '{}' with recovery...", sql);
+        loop {
+            while parser.consume_token(&Token::SemiColon) {}
+            if parser.peek_token().is_none() {
+                break;
+            }
+
+            let statement_index = result.statements.len();
+            statement_starts.push(parser.peek_span().start.offset);
+            let statement = match parser.parse_statement() {
+                Ok(statement) => {
+                    if let Some(token) = parser.peek_token() {
+                        if token != Token::SemiColon {
+                            let err = parser.expected::<()>("end of statement", Some(token));
+                            parser.errors.extend(err.err());
+                        }
+                    }
+                    Some(statement)
+                }
+                Err(err) => {
+                    parser.errors.push(err);
+                    None
+                }
+            };
+            // skip whatever is left of the statement
+            while parser.peek_token().is_some_and(|t| t != Token::SemiColon) {
+                parser.next_token();
+            }
+            result.statements.push(statement);
+            result
+                .diagnostics
+                .extend(parser.errors.drain(..).map(|error| ParseDiagnostic {
+                    statement_index,
+                    error,
+                }));
+        }
+
+        for error in tokenizer_errors {
+            let offset = error.location.offset;
+            // attribute the error to the last statement starting before it
+            let statement_index = statement_starts
+                .iter()
+                .filter(|start| **start <= offset)
+                .count()
+                .saturating_sub(1);
+            result.diagnostics.push(ParseDiagnostic {
+                statement_index,
+                error: error.into(),
+            });
+        }
+        // `sort_by_key` is stable, so errors within a statement stay in order
+        result.diagnostics.sort_by_key(|d| d.statement_index);
+        result
+    }
+
+    /// Parse a clause of a larger construct using `f`. In recovery mode (see
+    /// `parse_sql_with_recovery`) a failure is recorded, the rest of the
+    /// clause is skipped and `default` is returned in place of the clause.
+    fn parse_clause<T, F>(&mut self, default: T, f: F) -> Result<T, ParserError>
+    where
+        F: FnOnce(&mut Self) -> Result<T, ParserError>,
+    {
+        let clause_start = self.index;
+        match f(self) {
+            Err(err) if self.recover => {
+                debug!("recovering from {:?}", err);
+                self.errors.push(err);
+                self.skip_clause(clause_start);
+                Ok(default)
+            }
+            result => result,
+        }
+    }
+
+    /// Skip tokens up to the next keyword that can start a new clause of a
+    /// query, the end of the enclosing parenthesized expression or the end
+    /// of the statement. Parentheses opened since `clause_start` (the index
+    /// of the first token of the current clause) have to be closed first.
+    fn skip_clause(&mut self, clause_start: usize) {
+        let mut depth = 0;
+        for t in &self.tokens[clause_start..self.index.min(self.tokens.len())] {
+            match t.token {
+                Token::LParen => depth += 1,
+                Token::RParen if depth > 0 => depth -= 1,
+                _ => (),
+            }
+        }
+        loop {
+            match self.peek_token() {
+                None | Some(Token::SemiColon) => return,
+                Some(Token::RParen) if depth == 0 => return,
+                Some(Token::SQLWord(ref w))
+                    if depth == 0
+                        && keywords::QUERY_CLAUSE_KEYWORDS.contains(&w.keyword.as_str()) =>
+                {
+                    return
+                }
+                Some(Token::LParen) => depth += 1,
+                Some(Token::RParen) => depth -= 1,
+                _ => (),
+            }
+            self.next_token();
+        }
+    }
+
     /// Parse a single top-level statement (such as SELECT, INSERT, CREATE, etc.),
     /// stopping before the statement separator, if any.
     pub fn parse_statement(&mut self) -> Result<SQLStatement, ParserError> {
-        match self.next_token() {
+        let start = self.peek_span();
+        let dialect = self.dialect;
+        if let Some(statement) = dialect.parse_statement(self) {
+            let mut statement = statement?;
+            *statement.span_mut() = self.span_from(start);
+            return Ok(statement);
+        }
+        let mut statement = match self.next_token() {
             Some(t) => match t {
-                Token::SQLWord(ref w) if w.keyword != "" => match w.keyword.as_ref() {
+                Token::SQLWord(ref w) if !w.keyword.is_empty() => match w.keyword.as_ref() {
                     "SELECT" | "WITH" => {
                         self.prev_token();
                         Ok(SQLStatement::SQLQuery(Box::new(self.parse_query()?)))
@@ -117,22 +399,37 @@ impl Parser {
                     "DROP" => Ok(self.parse_drop()?),
                     "DELETE" => Ok(self.parse_delete()?),
                     "INSERT" => Ok(self.parse_insert()?),
+                    "REPLACE" => Ok(self.parse_replace()?),
                     "UPDATE" => Ok(self.parse_update()?),
+                    "MERGE" => Ok(self.parse_merge()?),
                     "ALTER" => Ok(self.parse_alter()?),
                     "COPY" => Ok(self.parse_copy()?),
-                    "BEGIN" => Ok(self.parse_transaction()?),
-                    _ => parser_err!(format!(
-                        "Unexpected keyword {:?} at the beginning of a statement",
-                        w.to_string()
-                    )),
+                    "BEGIN" => Ok(self.parse_begin()?),
+                    "START" => Ok(self.parse_start_transaction()?),
+                    "SET" => Ok(self.parse_set_transaction()?),
+                    "COMMIT" => Ok(self.parse_commit()?),
+                    "ROLLBACK" => Ok(self.parse_rollback()?),
+                    "SAVEPOINT" => Ok(self.parse_savepoint()?),
+                    "RELEASE" => Ok(self.parse_release()?),
+                    "PRAGMA" => Ok(self.parse_pragma()?),
+                    "ATTACH" => Ok(self.parse_attach_database()?),
+                    _ => {
+                        self.prev_token();
+                        self.expected("a keyword at the beginning of a statement", Some(t))
+                    }
                 },
-                unexpected => self.expected(
-                    "a keyword at the beginning of a statement",
-                    Some(unexpected),
-                ),
+                unexpected => {
+                    self.prev_token();
+                    self.expected(
+                        "a keyword at the beginning of a statement",
+                        Some(unexpected),
+                    )
+                }
             },
             None => self.expected("SQL statement", None),
-        }
+        }?;
+        *statement.span_mut() = self.span_from(start);
+        Ok(statement)
     }
 
     /// Parse a new expression
@@ -182,9 +479,15 @@ impl Parser {
 
     /// Parse an expression prefix
     pub fn parse_prefix(&mut self) -> Result<ASTNode, ParserError> {
-        let tok = self
-            .next_token()
-            .ok_or_else(|| ParserError::ParserError("Unexpected EOF".to_string()))?;
+        let dialect = self.dialect;
+        if let Some(prefix) = dialect.parse_prefix(self) {
+            return prefix;
+        }
+        let start = self.peek_span();
+        let tok = match self.next_token() {
+            Some(tok) => tok,
+            None => return self.expected("an expression", None),
+        };
         let expr = match tok {
             Token::SQLWord(w) => match w.keyword.as_ref() {
                 "TRUE" | "FALSE" | "NULL" => {
@@ -193,17 +496,25 @@ impl Parser {
                 }
                 "CASE" => self.parse_case_expression(),
                 "CAST" => self.parse_cast_expression(),
-                "DATE" => Ok(ASTNode::SQLValue(Value::Date(self.parse_literal_string()?))),
+                "DATE" => Ok(ASTNode::SQLValue(
+                    Value::Date(self.parse_literal_string()?),
+                    self.span_from(start),
+                )),
                 "EXISTS" => self.parse_exists_expression(),
                 "EXTRACT" => self.parse_extract_expression(),
                 "NOT" => Ok(ASTNode::SQLUnary {
                     operator: SQLOperator::Not,
                     expr: Box::new(self.parse_subexpr(Self::UNARY_NOT_PREC)?),
+                    span: self.span_from(start),
                 }),
-                "TIME" => Ok(ASTNode::SQLValue(Value::Time(self.parse_literal_string()?))),
-                "TIMESTAMP" => Ok(ASTNode::SQLValue(Value::Timestamp(
-                    self.parse_literal_string()?,
-                ))),
+                "TIME" => Ok(ASTNode::SQLValue(
+                    Value::Time(self.parse_literal_string()?),
+                    self.span_from(start),
+                )),
+                "TIMESTAMP" => Ok(ASTNode::SQLValue(
+                    Value::Timestamp(self.parse_literal_string()?),
+                    self.span_from(start),
+                )),
                 // Here `w` is a word, check if it's a part of a multi-part
                 // identifier, a function call, or a simple identifier:
                 _ => match self.peek_token() {
@@ -224,18 +535,32 @@ impl Parser {
                             }
                         }
                         if ends_with_wildcard {
-                            Ok(ASTNode::SQLQualifiedWildcard(id_parts))
+                            Ok(ASTNode::SQLQualifiedWildcard(
+                                id_parts,
+                                self.span_from(start),
+                            ))
                         } else if self.consume_token(&Token::LParen) {
                             self.prev_token();
-                            self.parse_function(SQLObjectName(id_parts))
+                            let mut function = self.parse_function(SQLObjectName(id_parts))?;
+                            if let ASTNode::SQLFunction(SQLFunction { span, .. }) = &mut function {
+                                // include all parts of a qualified function name
+                                *span = self.span_from(start);
+                            }
+                            Ok(function)
                         } else {
-                            Ok(ASTNode::SQLCompoundIdentifier(id_parts))
+                            Ok(ASTNode::SQLCompoundIdentifier(
+                                id_parts,
+                                self.span_from(start),
+                            ))
                         }
                     }
-                    _ => Ok(ASTNode::SQLIdentifier(w.as_sql_ident())),
+                    _ => Ok(ASTNode::SQLIdentifier(
+                        w.as_sql_ident(),
+                        self.span_from(start),
+                    )),
                 },
             }, // End of Token::SQLWord
-            Token::Mult => Ok(ASTNode::SQLWildcard),
+            Token::Mult => Ok(ASTNode::SQLWildcard(self.span_from(start))),
             tok @ Token::Minus | tok @ Token::Plus => {
                 let operator = if tok == Token::Plus {
                     SQLOperator::Plus
@@ -245,32 +570,45 @@ impl Parser {
                 Ok(ASTNode::SQLUnary {
                     operator,
                     expr: Box::new(self.parse_subexpr(Self::PLUS_MINUS_PREC)?),
+                    span: self.span_from(start),
                 })
             }
             Token::Number(_)
             | Token::SingleQuotedString(_)
+            | Token::DoubleQuotedString(_)
             | Token::NationalStringLiteral(_)
-            | Token::HexStringLiteral(_) => {
+            | Token::HexStringLiteral(_)
+            | Token::DollarQuotedString(_) => {
                 self.prev_token();
                 self.parse_sql_value()
             }
+            Token::Placeholder(s) => Ok(ASTNode::SQLPlaceholder(s, self.span_from(start))),
             Token::LParen => {
-                let expr = if self.parse_keyword("SELECT") || self.parse_keyword("WITH") {
+                if self.parse_keyword("SELECT") || self.parse_keyword("WITH") {
                     self.prev_token();
-                    ASTNode::SQLSubquery(Box::new(self.parse_query()?))
+                    let subquery = self.parse_query()?;
+                    self.expect_token(&Token::RParen)?;
+                    Ok(ASTNode::SQLSubquery(
+                        Box::new(subquery),
+                        self.span_from(start),
+                    ))
                 } else {
-                    ASTNode::SQLNested(Box::new(self.parse_expr()?))
-                };
-                self.expect_token(&Token::RParen)?;
-                Ok(expr)
+                    let expr = self.parse_expr()?;
+                    self.expect_token(&Token::RParen)?;
+                    Ok(ASTNode::SQLNested(Box::new(expr), self.span_from(start)))
+                }
+            }
+            unexpected => {
+                self.prev_token();
+                self.expected("an expression", Some(unexpected))
             }
-            unexpected => self.expected("an expression", Some(unexpected)),
         }?;
 
         if self.parse_keyword("COLLATE") {
             Ok(ASTNode::SQLCollate {
                 expr: Box::new(expr),
                 collation: self.parse_object_name()?,
+                span: self.span_from(start),
             })
         } else {
             Ok(expr)
@@ -278,18 +616,24 @@ impl Parser {
     }
 
     pub fn parse_function(&mut self, name: SQLObjectName) -> Result<ASTNode, ParserError> {
+        let start = self.prev_span();
         self.expect_token(&Token::LParen)?;
         let all = self.parse_keyword("ALL");
         let distinct = self.parse_keyword("DISTINCT");
         if all && distinct {
-            return parser_err!(format!(
-                "Cannot specify both ALL and DISTINCT in function: {}",
-                name.to_string(),
-            ));
+            return parser_err!(
+                InvalidSyntax,
+                self.prev_span(),
+                format!(
+                    "Cannot specify both ALL and DISTINCT in function: {}",
+                    name.to_string(),
+                )
+            );
         }
         let args = self.parse_optional_args()?;
         let over = if self.parse_keyword("OVER") {
             // TBD: support window names (`OVER mywin`) in place of inline specification
+            let spec_start = self.peek_span();
             self.expect_token(&Token::LParen)?;
             let partition_by = if self.parse_keywords(vec!["PARTITION", "BY"]) {
                 // a list of possibly-qualified column names
@@ -308,6 +652,7 @@ impl Parser {
                 partition_by,
                 order_by,
                 window_frame,
+                span: self.span_from(spec_start),
             })
         } else {
             None
@@ -318,13 +663,22 @@ impl Parser {
             args,
             over,
             distinct,
+            span: self.span_from(start),
         }))
     }
 
     pub fn parse_window_frame(&mut self) -> Result<Option<SQLWindowFrame>, ParserError> {
+        let start = self.peek_span();
         let window_frame = match self.peek_token() {
             Some(Token::SQLWord(w)) => {
-                let units = w.keyword.parse::<SQLWindowFrameUnits>()?;
+                let units = w
+                    .keyword
+                    .parse::<SQLWindowFrameUnits>()
+                    .map_err(|e| ParserError {
+                        span: self.peek_span(),
+                        found: self.peek_token().map(Box::new),
+                        ..e
+                    })?;
                 self.next_token();
                 if self.parse_keyword("BETWEEN") {
                     let start_bound = self.parse_window_frame_bound()?;
@@ -334,6 +688,7 @@ impl Parser {
                         units,
                         start_bound,
                         end_bound,
+                        span: self.span_from(start),
                     })
                 } else {
                     let start_bound = self.parse_window_frame_bound()?;
@@ -342,11 +697,19 @@ impl Parser {
                         units,
                         start_bound,
                         end_bound,
+                        span: self.span_from(start),
                     })
                 }
             }
             Some(Token::RParen) => None,
-            unexpected => return self.expected("'ROWS', 'RANGE', 'GROUPS', or ')'", unexpected),
+            unexpected => {
+                self.prev_token();
+                return self.expected_one_of(
+                    "'ROWS', 'RANGE', 'GROUPS', or ')'",
+                    &["ROWS", "RANGE", "GROUPS", ")"],
+                    unexpected,
+                );
+            }
         };
         self.expect_token(&Token::RParen)?;
         Ok(window_frame)
@@ -368,12 +731,17 @@ impl Parser {
             } else if self.parse_keyword("FOLLOWING") {
                 Ok(SQLWindowFrameBound::Following(rows))
             } else {
-                self.expected("PRECEDING or FOLLOWING", self.peek_token())
+                self.expected_one_of(
+                    "PRECEDING or FOLLOWING",
+                    &["PRECEDING", "FOLLOWING"],
+                    self.peek_token(),
+                )
             }
         }
     }
 
     pub fn parse_case_expression(&mut self) -> Result<ASTNode, ParserError> {
+        let start = self.prev_span();
         let mut operand = None;
         if !self.parse_keyword("WHEN") {
             operand = Some(Box::new(self.parse_expr()?));
@@ -400,11 +768,13 @@ impl Parser {
             conditions,
             results,
             else_result,
+            span: self.span_from(start),
         })
     }
 
     /// Parse a SQL CAST function e.g. `CAST(expr AS FLOAT)`
     pub fn parse_cast_expression(&mut self) -> Result<ASTNode, ParserError> {
+        let start = self.prev_span();
         self.expect_token(&Token::LParen)?;
         let expr = self.parse_expr()?;
         self.expect_keyword("AS")?;
@@ -413,20 +783,23 @@ impl Parser {
         Ok(ASTNode::SQLCast {
             expr: Box::new(expr),
             data_type,
+            span: self.span_from(start),
         })
     }
 
     /// Parse a SQL EXISTS expression e.g. `WHERE EXISTS(SELECT ...)`.
     pub fn parse_exists_expression(&mut self) -> Result<ASTNode, ParserError> {
+        let start = self.prev_span();
         self.expect_token(&Token::LParen)?;
-        let exists_node = ASTNode::SQLExists(Box::new(self.parse_query()?));
+        let query = self.parse_query()?;
         self.expect_token(&Token::RParen)?;
-        Ok(exists_node)
+        Ok(ASTNode::SQLExists(Box::new(query), self.span_from(start)))
     }
 
     pub fn parse_extract_expression(&mut self) -> Result<ASTNode, ParserError> {
+        let start = self.prev_span();
         self.expect_token(&Token::LParen)?;
-        let tok = self.next_token();
+        let tok = self.peek_token();
         let field = if let Some(Token::SQLWord(ref k)) = tok {
             match k.keyword.as_ref() {
                 "YEAR" => SQLDateTimeField::Year,
@@ -440,18 +813,25 @@ impl Parser {
         } else {
             self.expected("Date/time field inside of EXTRACT function", tok)?
         };
+        self.next_token();
         self.expect_keyword("FROM")?;
         let expr = self.parse_expr()?;
         self.expect_token(&Token::RParen)?;
         Ok(ASTNode::SQLExtract {
             field,
             expr: Box::new(expr),
+            span: self.span_from(start),
         })
     }
 
     /// Parse an operator following an expression
     pub fn parse_infix(&mut self, expr: ASTNode, precedence: u8) -> Result<ASTNode, ParserError> {
+        let dialect = self.dialect;
+        if let Some(infix) = dialect.parse_infix(self, &expr, precedence) {
+            return infix;
+        }
         debug!("parsing infix");
+        let start = expr.span();
         let tok = self.next_token().unwrap(); // safe as EOF's precedence is the lowest
 
         let regular_binary_operator = match tok {
@@ -487,16 +867,21 @@ impl Parser {
                 left: Box::new(expr),
                 op,
                 right: Box::new(self.parse_subexpr(precedence)?),
+                span: self.span_from(start),
             })
         } else if let Token::SQLWord(ref k) = tok {
             match k.keyword.as_ref() {
                 "IS" => {
                     if self.parse_keyword("NULL") {
-                        Ok(ASTNode::SQLIsNull(Box::new(expr)))
+                        Ok(ASTNode::SQLIsNull(Box::new(expr), self.span_from(start)))
                     } else if self.parse_keywords(vec!["NOT", "NULL"]) {
-                        Ok(ASTNode::SQLIsNotNull(Box::new(expr)))
+                        Ok(ASTNode::SQLIsNotNull(Box::new(expr), self.span_from(start)))
                     } else {
-                        self.expected("NULL or NOT NULL after IS", self.peek_token())
+                        self.expected_one_of(
+                            "NULL or NOT NULL after IS",
+                            &["NULL", "NOT NULL"],
+                            self.peek_token(),
+                        )
                     }
                 }
                 "NOT" | "IN" | "BETWEEN" => {
@@ -507,7 +892,11 @@ impl Parser {
                     } else if self.parse_keyword("BETWEEN") {
                         self.parse_between(expr, negated)
                     } else {
-                        self.expected("IN or BETWEEN after NOT", self.peek_token())
+                        self.expected_one_of(
+                            "IN or BETWEEN after NOT",
+                            &["IN", "BETWEEN"],
+                            self.peek_token(),
+                        )
                     }
                 }
                 // Can only happen if `get_precedence` got out of sync with this function
@@ -523,23 +912,28 @@ impl Parser {
 
     /// Parses the parens following the `[ NOT ] IN` operator
     pub fn parse_in(&mut self, expr: ASTNode, negated: bool) -> Result<ASTNode, ParserError> {
+        let start = expr.span();
         self.expect_token(&Token::LParen)?;
-        let in_op = if self.parse_keyword("SELECT") || self.parse_keyword("WITH") {
+        if self.parse_keyword("SELECT") || self.parse_keyword("WITH") {
             self.prev_token();
-            ASTNode::SQLInSubquery {
+            let subquery = self.parse_query()?;
+            self.expect_token(&Token::RParen)?;
+            Ok(ASTNode::SQLInSubquery {
                 expr: Box::new(expr),
-                subquery: Box::new(self.parse_query()?),
+                subquery: Box::new(subquery),
                 negated,
-            }
+                span: self.span_from(start),
+            })
         } else {
-            ASTNode::SQLInList {
+            let list = self.parse_expr_list()?;
+            self.expect_token(&Token::RParen)?;
+            Ok(ASTNode::SQLInList {
                 expr: Box::new(expr),
-                list: self.parse_expr_list()?,
+                list,
                 negated,
-            }
-        };
-        self.expect_token(&Token::RParen)?;
-        Ok(in_op)
+                span: self.span_from(start),
+            })
+        }
     }
 
     /// Parses `BETWEEN <low> AND <high>`, assuming the `BETWEEN` keyword was already consumed
@@ -550,6 +944,7 @@ impl Parser {
         self.expect_keyword("AND")?;
         let high = self.parse_subexpr(Self::BETWEEN_PREC)?;
         Ok(ASTNode::SQLBetween {
+            span: self.span_from(expr.span()),
             expr: Box::new(expr),
             negated,
             low: Box::new(low),
@@ -559,9 +954,11 @@ impl Parser {
 
     /// Parse a postgresql casting style which is in the form of `expr::datatype`
     pub fn parse_pg_cast(&mut self, expr: ASTNode) -> Result<ASTNode, ParserError> {
+        let start = expr.span();
         Ok(ASTNode::SQLCast {
             expr: Box::new(expr),
             data_type: self.parse_data_type()?,
+            span: self.span_from(start),
         })
     }
 
@@ -571,6 +968,9 @@ impl Parser {
 
     /// Get the precedence of the next token
     pub fn get_next_precedence(&self) -> Result<u8, ParserError> {
+        if let Some(precedence) = self.dialect.get_next_precedence(self) {
+            return precedence;
+        }
         if let Some(token) = self.peek_token() {
             debug!("get_precedence() {:?}", token);
 
@@ -611,15 +1011,22 @@ impl Parser {
     }
 
     /// Return nth non-whitespace token that has not yet been processed
-    pub fn peek_nth_token(&self, mut n: usize) -> Option<Token> {
+    pub fn peek_nth_token(&self, n: usize) -> Option<Token> {
+        self.peek_nth_token_with_span(n).map(|t| t.token.clone())
+    }
+
+    fn peek_nth_token_with_span(&self, mut n: usize) -> Option<&TokenWithSpan> {
         let mut index = self.index;
         loop {
             index += 1;
             match self.tokens.get(index - 1) {
-                Some(Token::Whitespace(_)) => continue,
+                Some(TokenWithSpan {
+                    token: Token::Whitespace(_),
+                    ..
+                }) => continue,
                 non_whitespace => {
                     if n == 0 {
-                        return non_whitespace.cloned();
+                        return non_whitespace;
                     }
                     n -= 1;
                 }
@@ -634,8 +1041,11 @@ impl Parser {
         loop {
             self.index += 1;
             match self.tokens.get(self.index - 1) {
-                Some(Token::Whitespace(_)) => continue,
-                token => return token.cloned(),
+                Some(TokenWithSpan {
+                    token: Token::Whitespace(_),
+                    ..
+                }) => continue,
+                token => return token.map(|t| t.token.clone()),
             }
         }
     }
@@ -643,7 +1053,7 @@ impl Parser {
     /// Return the first unprocessed token, possibly whitespace.
     pub fn next_token_no_skip(&mut self) -> Option<&Token> {
         self.index += 1;
-        self.tokens.get(self.index - 1)
+        self.tokens.get(self.index - 1).map(|t| &t.token)
     }
 
     /// Push back the last one non-whitespace token. Must be called after
@@ -653,20 +1063,74 @@ impl Parser {
         loop {
             assert!(self.index > 0);
             self.index -= 1;
-            if let Some(Token::Whitespace(_)) = self.tokens.get(self.index) {
+            if let Some(TokenWithSpan {
+                token: Token::Whitespace(_),
+                ..
+            }) = self.tokens.get(self.index)
+            {
                 continue;
             }
             return;
         }
     }
 
-    /// Report unexpected token
-    fn expected<T>(&self, expected: &str, found: Option<Token>) -> Result<T, ParserError> {
-        parser_err!(format!(
+    /// Return the span of the first non-whitespace token that has not yet
+    /// been processed (or an empty span if reached end-of-file)
+    pub fn peek_span(&self) -> Span {
+        self.peek_nth_token_with_span(0)
+            .map_or_else(Span::empty, |t| t.span)
+    }
+
+    /// Return the span of the last non-whitespace token that has been
+    /// processed (or an empty span if there is none)
+    pub fn prev_span(&self) -> Span {
+        self.tokens[..self.index.min(self.tokens.len())]
+            .iter()
+            .rev()
+            .find(|t| !matches!(t.token, Token::Whitespace(_)))
+            .map_or_else(Span::empty, |t| t.span)
+    }
+
+    /// Return a span starting at `start` and ending with the last processed
+    /// token, i.e. the span of the AST node that began at `start` and whose
+    /// parsing has just finished.
+    pub fn span_from(&self, start: Span) -> Span {
+        Span::new(start.start, self.prev_span().end)
+    }
+
+    /// Report unexpected token. `found` must be the next token, i.e. the one
+    /// `peek_token()` would return: call `prev_token()` first if it has
+    /// already been consumed, so that the error points at it.
+    pub fn expected<T>(&self, expected: &str, found: Option<Token>) -> Result<T, ParserError> {
+        self.expected_one_of(expected, &[expected], found)
+    }
+
+    /// Report unexpected token, listing the `alternatives` that would have
+    /// been accepted in its place (`description` summarizes them for the
+    /// error message).
+    fn expected_one_of<T>(
+        &self,
+        description: &str,
+        alternatives: &[&str],
+        found: Option<Token>,
+    ) -> Result<T, ParserError> {
+        let message = format!(
             "Expected {}, found: {}",
-            expected,
-            found.map_or("EOF".to_string(), |t| t.to_string())
-        ))
+            description,
+            found.as_ref().map_or("EOF".to_string(), |t| t.to_string())
+        );
+        let (kind, span) = match found {
+            Some(_) => (ParserErrorKind::UnexpectedToken, self.peek_span()),
+            None => {
+                let end = self.prev_span().end;
+                (ParserErrorKind::UnexpectedEof, Span::new(end, end))
+            }
+        };
+        Err(ParserError {
+            expected: alternatives.iter().map(|a| a.to_string()).collect(),
+            found: found.map(Box::new),
+            ..ParserError::new(kind, message, span)
+        })
     }
 
     /// Look for an expected keyword and consume it if it exists
@@ -691,7 +1155,7 @@ impl Parser {
     pub fn parse_keywords(&mut self, keywords: Vec<&'static str>) -> bool {
         let index = self.index;
         for keyword in keywords {
-            if !self.parse_keyword(&keyword) {
+            if !self.parse_keyword(keyword) {
                 //println!("parse_keywords aborting .. did not find {}", keyword);
                 // reset index and return immediately
                 self.index = index;
@@ -720,7 +1184,6 @@ impl Parser {
     }
 
     /// Bail out if the current token is not one of the expected keywords, or consume it if it is
-    #[must_use]
     pub fn expect_one_of_keywords(
         &mut self,
         keywords: &[&'static str],
@@ -728,8 +1191,9 @@ impl Parser {
         if let Some(keyword) = self.parse_one_of_keywords(keywords) {
             Ok(keyword)
         } else {
-            self.expected(
+            self.expected_one_of(
                 &format!("one of {}", keywords.join(" or ")),
+                keywords,
                 self.peek_token(),
             )
         }
@@ -767,45 +1231,117 @@ impl Parser {
 
     /// Parse a SQL CREATE statement
     pub fn parse_create(&mut self) -> Result<SQLStatement, ParserError> {
-        if self.parse_keyword("TABLE") {
-            self.parse_create_table()
+        if self.parse_keywords(vec!["OR", "REPLACE"]) {
+            return if self.parse_keyword("MATERIALIZED") || self.parse_keyword("VIEW") {
+                self.prev_token();
+                self.parse_create_view(true)
+            } else if self.parse_keyword("FUNCTION") {
+                self.parse_create_function(true, false)
+            } else if self.parse_one_of_keywords(&["PROCEDURE", "PROC"]).is_some() {
+                self.parse_create_function(true, true)
+            } else {
+                self.expected_one_of(
+                    "VIEW, FUNCTION or PROCEDURE after CREATE OR REPLACE",
+                    &["VIEW", "MATERIALIZED VIEW", "FUNCTION", "PROCEDURE"],
+                    self.peek_token(),
+                )
+            };
+        }
+        let persistence = match self.parse_one_of_keywords(&["TEMP", "TEMPORARY", "UNLOGGED"]) {
+            Some("TEMP") => Some(TablePersistence::Temp),
+            Some("TEMPORARY") => Some(TablePersistence::Temporary),
+            Some(_) => Some(TablePersistence::Unlogged),
+            None => None,
+        };
+        if persistence.is_some() {
+            self.expect_keyword("TABLE")?;
+            self.parse_create_table(persistence)
+        } else if self.parse_keyword("TABLE") {
+            self.parse_create_table(None)
+        } else if self.parse_keyword("INDEX") {
+            self.parse_create_index(false)
+        } else if self.parse_keywords(vec!["UNIQUE", "INDEX"]) {
+            self.parse_create_index(true)
+        } else if self.parse_keyword("SCHEMA") {
+            self.parse_create_schema()
+        } else if self.parse_keyword("DATABASE") {
+            self.parse_create_database()
+        } else if self.parse_keyword("SEQUENCE") {
+            self.parse_create_sequence()
         } else if self.parse_keyword("MATERIALIZED") || self.parse_keyword("VIEW") {
             self.prev_token();
-            self.parse_create_view()
+            self.parse_create_view(false)
         } else if self.parse_keyword("EXTERNAL") {
             self.parse_create_external_table()
+        } else if self.parse_keyword("FUNCTION") {
+            self.parse_create_function(false, false)
+        } else if self.parse_one_of_keywords(&["PROCEDURE", "PROC"]).is_some() {
+            self.parse_create_function(false, true)
         } else {
-            self.expected("TABLE or VIEW after CREATE", self.peek_token())
+            self.expected_one_of(
+                "TABLE, VIEW, INDEX, SCHEMA, DATABASE, SEQUENCE, FUNCTION or PROCEDURE after CREATE",
+                &[
+                    "TABLE",
+                    "VIEW",
+                    "MATERIALIZED VIEW",
+                    "EXTERNAL TABLE",
+                    "INDEX",
+                    "UNIQUE INDEX",
+                    "SCHEMA",
+                    "DATABASE",
+                    "SEQUENCE",
+                    "FUNCTION",
+                    "PROCEDURE",
+                ],
+                self.peek_token(),
+            )
         }
     }
 
     pub fn parse_create_external_table(&mut self) -> Result<SQLStatement, ParserError> {
+        let start = self.prev_span();
         self.expect_keyword("TABLE")?;
         let table_name = self.parse_object_name()?;
         let (columns, constraints) = self.parse_columns()?;
         self.expect_keyword("STORED")?;
         self.expect_keyword("AS")?;
-        let file_format = self.parse_identifier()?.parse::<FileFormat>()?;
+        let file_format = self
+            .parse_identifier()?
+            .parse::<FileFormat>()
+            .map_err(|e| ParserError {
+                span: self.prev_span(),
+                found: self
+                    .tokens
+                    .get(self.index - 1)
+                    .map(|t| Box::new(t.token.clone())),
+                ..e
+            })?;
 
         self.expect_keyword("LOCATION")?;
         let location = self.parse_literal_string()?;
 
         Ok(SQLStatement::SQLCreateTable {
+            persistence: None,
+            if_not_exists: false,
             name: table_name,
             columns,
             constraints,
             with_options: vec![],
+            table_options: vec![],
+            without_rowid: false,
             external: true,
             file_format: Some(file_format),
             location: Some(location),
+            query: None,
+            span: self.span_from(start),
         })
     }
 
-    pub fn parse_create_view(&mut self) -> Result<SQLStatement, ParserError> {
+    pub fn parse_create_view(&mut self, or_replace: bool) -> Result<SQLStatement, ParserError> {
+        let start = self.peek_span();
         let materialized = self.parse_keyword("MATERIALIZED");
         self.expect_keyword("VIEW")?;
-        // Many dialects support `OR REPLACE` | `OR ALTER` right after `CREATE`, but we don't (yet).
-        // ANSI SQL and Postgres support RECURSIVE here, but we don't support it either.
+        // ANSI SQL and Postgres support RECURSIVE here, but we don't support it (yet).
         let name = self.parse_object_name()?;
         let columns = self.parse_parenthesized_column_list(Optional)?;
         let with_options = if self.parse_keyword("WITH") {
@@ -817,24 +1353,420 @@ impl Parser {
         let query = Box::new(self.parse_query()?);
         // Optional `WITH [ CASCADED | LOCAL ] CHECK OPTION` is widely supported here.
         Ok(SQLStatement::SQLCreateView {
+            or_replace,
             name,
             columns,
             query,
             materialized,
             with_options,
+            span: self.span_from(start),
+        })
+    }
+
+    /// Parse a `CREATE [ UNIQUE ] INDEX` statement, after the `INDEX` keyword
+    pub fn parse_create_index(&mut self, unique: bool) -> Result<SQLStatement, ParserError> {
+        let start = self.prev_span();
+        let concurrently = self.parse_keyword("CONCURRENTLY");
+        let if_not_exists = self.parse_keywords(vec!["IF", "NOT", "EXISTS"]);
+        let name = self.parse_object_name()?;
+        self.expect_keyword("ON")?;
+        let table_name = self.parse_object_name()?;
+        let using = if self.parse_keyword("USING") {
+            Some(self.parse_identifier()?)
+        } else {
+            None
+        };
+        self.expect_token(&Token::LParen)?;
+        let columns = self.parse_order_by_expr_list()?;
+        self.expect_token(&Token::RParen)?;
+        let include = if self.parse_keyword("INCLUDE") {
+            self.parse_parenthesized_column_list(Mandatory)?
+        } else {
+            vec![]
+        };
+        let predicate = if self.parse_keyword("WHERE") {
+            Some(self.parse_expr()?)
+        } else {
+            None
+        };
+        Ok(SQLStatement::SQLCreateIndex {
+            name,
+            table_name,
+            unique,
+            concurrently,
+            if_not_exists,
+            using,
+            columns,
+            include,
+            predicate,
+            span: self.span_from(start),
+        })
+    }
+
+    pub fn parse_create_schema(&mut self) -> Result<SQLStatement, ParserError> {
+        let start = self.prev_span();
+        let if_not_exists = self.parse_keywords(vec!["IF", "NOT", "EXISTS"]);
+        let schema_name = self.parse_object_name()?;
+        Ok(SQLStatement::SQLCreateSchema {
+            schema_name,
+            if_not_exists,
+            span: self.span_from(start),
+        })
+    }
+
+    pub fn parse_create_database(&mut self) -> Result<SQLStatement, ParserError> {
+        let start = self.prev_span();
+        let if_not_exists = self.parse_keywords(vec!["IF", "NOT", "EXISTS"]);
+        let db_name = self.parse_object_name()?;
+        Ok(SQLStatement::SQLCreateDatabase {
+            db_name,
+            if_not_exists,
+            span: self.span_from(start),
+        })
+    }
+
+    pub fn parse_create_sequence(&mut self) -> Result<SQLStatement, ParserError> {
+        let start = self.prev_span();
+        let if_not_exists = self.parse_keywords(vec!["IF", "NOT", "EXISTS"]);
+        let name = self.parse_object_name()?;
+        let options = self.parse_sequence_options()?;
+        Ok(SQLStatement::SQLCreateSequence {
+            name,
+            if_not_exists,
+            options,
+            span: self.span_from(start),
         })
     }
 
+    /// Parse the options of a `CREATE SEQUENCE` or `ALTER SEQUENCE`
+    /// statement, which may be given in any order
+    pub fn parse_sequence_options(&mut self) -> Result<Vec<SequenceOption>, ParserError> {
+        let mut options = vec![];
+        loop {
+            let option = if self.parse_keyword("AS") {
+                SequenceOption::As {
+                    data_type: self.parse_data_type()?,
+                }
+            } else if self.parse_keyword("INCREMENT") {
+                let _ = self.parse_keyword("BY");
+                SequenceOption::IncrementBy {
+                    value: self.parse_expr()?,
+                }
+            } else if self.parse_keyword("MINVALUE") {
+                SequenceOption::MinValue {
+                    value: Some(self.parse_expr()?),
+                }
+            } else if self.parse_keywords(vec!["NO", "MINVALUE"]) {
+                SequenceOption::MinValue { value: None }
+            } else if self.parse_keyword("MAXVALUE") {
+                SequenceOption::MaxValue {
+                    value: Some(self.parse_expr()?),
+                }
+            } else if self.parse_keywords(vec!["NO", "MAXVALUE"]) {
+                SequenceOption::MaxValue { value: None }
+            } else if self.parse_keyword("START") {
+                let _ = self.parse_keyword("WITH");
+                SequenceOption::StartWith {
+                    value: self.parse_expr()?,
+                }
+            } else if self.parse_keyword("RESTART") {
+                let value = if self.parse_keyword("WITH") {
+                    Some(self.parse_expr()?)
+                } else {
+                    match self.peek_token() {
+                        Some(Token::Number(_)) | Some(Token::Minus) => Some(self.parse_expr()?),
+                        _ => None,
+                    }
+                };
+                SequenceOption::Restart { value }
+            } else if self.parse_keyword("CACHE") {
+                SequenceOption::Cache {
+                    value: self.parse_expr()?,
+                }
+            } else if self.parse_keyword("CYCLE") {
+                SequenceOption::Cycle
+            } else if self.parse_keywords(vec!["NO", "CYCLE"]) {
+                SequenceOption::NoCycle
+            } else if self.parse_keywords(vec!["OWNED", "BY"]) {
+                let column = if self.parse_keyword("NONE") {
+                    None
+                } else {
+                    Some(self.parse_object_name()?)
+                };
+                SequenceOption::OwnedBy { column }
+            } else {
+                break;
+            };
+            options.push(option);
+        }
+        Ok(options)
+    }
+
+    /// Parse a `CREATE [OR REPLACE] FUNCTION` or `CREATE PROCEDURE` statement,
+    /// after the `FUNCTION`/`PROCEDURE` keyword. The body is not parsed, but
+    /// kept as the source text.
+    pub fn parse_create_function(
+        &mut self,
+        or_replace: bool,
+        procedure: bool,
+    ) -> Result<SQLStatement, ParserError> {
+        let start = self.prev_span();
+        let name = self.parse_object_name()?;
+        let args = if self.consume_token(&Token::LParen) {
+            if self.consume_token(&Token::RParen) {
+                Some(vec![])
+            } else {
+                let args = self.parse_function_args()?;
+                self.expect_token(&Token::RParen)?;
+                Some(args)
+            }
+        } else {
+            match self.peek_token() {
+                // MSSQL doesn't require parentheses around procedure parameters
+                Some(Token::SQLWord(ref w)) if w.value.starts_with('@') => {
+                    Some(self.parse_function_args()?)
+                }
+                _ => None,
+            }
+        };
+        // `RETURNS NULL ON NULL INPUT` is an attribute, not a return type
+        let returns = match self.peek_nth_token(1) {
+            Some(Token::SQLWord(ref w)) if w.keyword == "NULL" => None,
+            _ if self.parse_keyword("RETURNS") => Some(self.parse_function_returns()?),
+            _ => None,
+        };
+
+        let mut attributes = vec![];
+        let mut body = None;
+        loop {
+            if self.parse_keyword("AS") {
+                match self.parse_function_body()? {
+                    raw @ FunctionBody::Raw(_) => {
+                        // The raw body extends to the end of the statement
+                        body = Some(raw);
+                        break;
+                    }
+                    quoted => body = Some(quoted),
+                }
+                continue;
+            }
+            let attribute = if self.parse_keyword("LANGUAGE") {
+                FunctionAttribute::Language(self.parse_identifier()?)
+            } else if self.parse_keyword("IMMUTABLE") {
+                FunctionAttribute::Immutable
+            } else if self.parse_keyword("STABLE") {
+                FunctionAttribute::Stable
+            } else if self.parse_keyword("VOLATILE") {
+                FunctionAttribute::Volatile
+            } else if self.parse_keyword("STRICT") {
+                FunctionAttribute::Strict
+            } else if self.parse_keywords(vec!["CALLED", "ON", "NULL", "INPUT"]) {
+                FunctionAttribute::CalledOnNullInput
+            } else if self.parse_keywords(vec!["RETURNS", "NULL", "ON", "NULL", "INPUT"]) {
+                FunctionAttribute::ReturnsNullOnNullInput
+            } else if self.parse_keyword("SECURITY") {
+                let definer = self.expect_one_of_keywords(&["DEFINER", "INVOKER"])? == "DEFINER";
+                FunctionAttribute::Security { definer }
+            } else if self.parse_keyword("PARALLEL") {
+                FunctionAttribute::Parallel(
+                    match self.expect_one_of_keywords(&["UNSAFE", "RESTRICTED", "SAFE"])? {
+                        "UNSAFE" => FunctionParallel::Unsafe,
+                        "RESTRICTED" => FunctionParallel::Restricted,
+                        _ => FunctionParallel::Safe,
+                    },
+                )
+            } else if self.parse_keyword("COST") {
+                FunctionAttribute::Cost(self.parse_expr()?)
+            } else if self.parse_keyword("ROWS") {
+                FunctionAttribute::Rows(self.parse_expr()?)
+            } else if self.parse_keyword("WITH") {
+                let mut options = vec![self.parse_identifier()?];
+                while self.consume_token(&Token::Comma) {
+                    options.push(self.parse_identifier()?);
+                }
+                FunctionAttribute::With(options)
+            } else {
+                break;
+            };
+            attributes.push(attribute);
+        }
+        let body = match body {
+            Some(body) => body,
+            None => return self.expected("AS or a function attribute", self.peek_token()),
+        };
+        Ok(SQLStatement::SQLCreateFunction {
+            or_replace,
+            procedure,
+            name,
+            args,
+            returns,
+            attributes,
+            body,
+            span: self.span_from(start),
+        })
+    }
+
+    /// Parse a comma-separated list of function or procedure parameters
+    pub fn parse_function_args(&mut self) -> Result<Vec<FunctionArg>, ParserError> {
+        let mut args = vec![self.parse_function_arg()?];
+        while self.consume_token(&Token::Comma) {
+            args.push(self.parse_function_arg()?);
+        }
+        Ok(args)
+    }
+
+    /// Parse `[ IN | OUT | INOUT | VARIADIC ] [ name ] type [ { DEFAULT | = } expr ] [ OUTPUT ]`
+    pub fn parse_function_arg(&mut self) -> Result<FunctionArg, ParserError> {
+        let start = self.peek_span();
+        let mode = match self.parse_one_of_keywords(&["IN", "OUT", "INOUT", "VARIADIC"]) {
+            Some("IN") => Some(ArgMode::In),
+            Some("OUT") => Some(ArgMode::Out),
+            Some("INOUT") => Some(ArgMode::InOut),
+            Some(_) => Some(ArgMode::Variadic),
+            None => None,
+        };
+        // The name is optional: try parsing a type, and if something other
+        // than the end of the parameter follows, it was the name instead.
+        let index = self.index;
+        let data_type = self.parse_data_type()?;
+        let (name, data_type) = match self.peek_token() {
+            None | Some(Token::Comma) | Some(Token::RParen) | Some(Token::Eq) => (None, data_type),
+            Some(Token::SQLWord(ref w))
+                if ["DEFAULT", "OUT", "OUTPUT", "AS", "WITH"].contains(&w.keyword.as_str()) =>
+            {
+                (None, data_type)
+            }
+            _ => {
+                self.index = index;
+                (Some(self.parse_identifier()?), self.parse_data_type()?)
+            }
+        };
+        let default = if self.parse_keyword("DEFAULT") || self.consume_token(&Token::Eq) {
+            Some(self.parse_expr()?)
+        } else {
+            None
+        };
+        let output = self.parse_one_of_keywords(&["OUT", "OUTPUT"]).is_some();
+        Ok(FunctionArg {
+            mode,
+            name,
+            data_type,
+            default,
+            output,
+            span: self.span_from(start),
+        })
+    }
+
+    /// Parse the return type of a function, after `RETURNS`
+    pub fn parse_function_returns(&mut self) -> Result<FunctionReturns, ParserError> {
+        if self.parse_keyword("SETOF") {
+            Ok(FunctionReturns::SetOf(self.parse_data_type()?))
+        } else if self.parse_keyword("TABLE") {
+            let mut columns = vec![];
+            if self.consume_token(&Token::LParen) {
+                loop {
+                    columns.push(self.parse_column_def()?);
+                    if !self.consume_token(&Token::Comma) {
+                        break;
+                    }
+                }
+                self.expect_token(&Token::RParen)?;
+            }
+            Ok(FunctionReturns::Table(columns))
+        } else {
+            Ok(FunctionReturns::Type(self.parse_data_type()?))
+        }
+    }
+
+    /// Parse the body of a function after `AS`: a string literal, or
+    /// (as in MSSQL) the unquoted text of a statement or a `BEGIN ... END` block
+    pub fn parse_function_body(&mut self) -> Result<FunctionBody, ParserError> {
+        match self.next_token() {
+            Some(Token::SingleQuotedString(s)) => Ok(FunctionBody::SingleQuoted(s)),
+            Some(Token::DollarQuotedString(s)) => Ok(FunctionBody::DollarQuoted(s)),
+            Some(_) => {
+                self.prev_token();
+                Ok(FunctionBody::Raw(self.parse_raw_function_body()?))
+            }
+            None => self.expected("function body", None),
+        }
+    }
+
+    /// Consume a `BEGIN ... END` block, or the tokens up to the next `;`
+    /// outside of one, returning their source text
+    fn parse_raw_function_body(&mut self) -> Result<String, ParserError> {
+        // `self.index` points at the first token of the body
+        let start_index = self.index;
+        let mut depth = 0usize;
+        let mut block = false;
+        loop {
+            match self.peek_token() {
+                None => break,
+                Some(Token::SemiColon) if depth == 0 => break,
+                _ => (),
+            }
+            let is_first = self.index == start_index;
+            if let Some(Token::SQLWord(w)) = self.next_token() {
+                match w.keyword.as_ref() {
+                    "BEGIN" if !self.is_begin_transaction() => {
+                        block |= is_first;
+                        depth += 1;
+                    }
+                    "CASE" => depth += 1,
+                    "END" if depth > 0 => {
+                        depth -= 1;
+                        // MSSQL's `BEGIN TRY ... END TRY` is followed by a
+                        // `BEGIN CATCH ... END CATCH` block
+                        let end_try = self.parse_keyword("TRY");
+                        if !end_try {
+                            let _ = self.parse_keyword("CATCH");
+                        }
+                        if depth == 0 && block && !end_try {
+                            break;
+                        }
+                    }
+                    _ => (),
+                }
+            }
+        }
+        if depth > 0 {
+            return self.expected("END", self.peek_token());
+        }
+        Ok(self.tokens[start_index..self.index]
+            .iter()
+            .map(|t| t.token.to_string())
+            .collect())
+    }
+
+    /// Whether the `BEGIN` just consumed starts a transaction (as in MSSQL's
+    /// `BEGIN TRAN`) rather than a block
+    fn is_begin_transaction(&self) -> bool {
+        match self.peek_token() {
+            Some(Token::SQLWord(ref w)) => w.keyword == "TRAN" || w.keyword == "TRANSACTION",
+            _ => false,
+        }
+    }
+
     pub fn parse_drop(&mut self) -> Result<SQLStatement, ParserError> {
+        let start = self.prev_span();
         let object_type = if self.parse_keyword("TABLE") {
             SQLObjectType::Table
         } else if self.parse_keyword("VIEW") {
             SQLObjectType::View
+        } else if self.parse_keyword("INDEX") {
+            SQLObjectType::Index
+        } else if self.parse_keyword("SCHEMA") {
+            SQLObjectType::Schema
+        } else if self.parse_keyword("DATABASE") {
+            SQLObjectType::Database
+        } else if self.parse_keyword("SEQUENCE") {
+            SQLObjectType::Sequence
         } else {
-            return parser_err!(format!(
-                "Unexpected token after DROP: {:?}",
-                self.peek_token()
-            ));
+            return self.expected_one_of(
+                "TABLE, VIEW, INDEX, SCHEMA, DATABASE or SEQUENCE after DROP",
+                &["TABLE", "VIEW", "INDEX", "SCHEMA", "DATABASE", "SEQUENCE"],
+                self.peek_token(),
+            );
         };
         let if_exists = self.parse_keywords(vec!["IF", "EXISTS"]);
         let mut names = vec![self.parse_object_name()?];
@@ -852,35 +1784,58 @@ impl Parser {
         let cascade = self.parse_keyword("CASCADE");
         let restrict = self.parse_keyword("RESTRICT");
         if cascade && restrict {
-            return parser_err!("Cannot specify both CASCADE and RESTRICT in DROP");
+            return parser_err!(
+                InvalidSyntax,
+                self.prev_span(),
+                "Cannot specify both CASCADE and RESTRICT in DROP"
+            );
         }
         Ok(SQLStatement::SQLDrop {
             object_type,
             if_exists,
             names,
             cascade,
+            span: self.span_from(start),
         })
     }
 
-    pub fn parse_create_table(&mut self) -> Result<SQLStatement, ParserError> {
+    pub fn parse_create_table(
+        &mut self,
+        persistence: Option<TablePersistence>,
+    ) -> Result<SQLStatement, ParserError> {
+        let start = self.prev_span();
+        let if_not_exists = self.parse_keywords(vec!["IF", "NOT", "EXISTS"]);
         let table_name = self.parse_object_name()?;
         // parse optional column list (schema)
         let (columns, constraints) = self.parse_columns()?;
+        let without_rowid = self.parse_keywords(vec!["WITHOUT", "ROWID"]);
 
         let with_options = if self.parse_keyword("WITH") {
             self.parse_with_options()?
         } else {
             vec![]
         };
+        let table_options = self.parse_table_options()?;
+        let query = if self.parse_keyword("AS") {
+            Some(Box::new(self.parse_query()?))
+        } else {
+            None
+        };
 
         Ok(SQLStatement::SQLCreateTable {
+            persistence,
+            if_not_exists,
             name: table_name,
             columns,
             constraints,
             with_options,
+            table_options,
+            without_rowid,
             external: false,
             file_format: None,
             location: None,
+            query,
+            span: self.span_from(start),
         })
     }
 
@@ -894,33 +1849,8 @@ impl Parser {
         loop {
             if let Some(constraint) = self.parse_optional_table_constraint()? {
                 constraints.push(constraint);
-            } else if let Some(Token::SQLWord(column_name)) = self.peek_token() {
-                self.next_token();
-                let data_type = self.parse_data_type()?;
-                let is_primary = self.parse_keywords(vec!["PRIMARY", "KEY"]);
-                let is_unique = self.parse_keyword("UNIQUE");
-                let default = if self.parse_keyword("DEFAULT") {
-                    let expr = self.parse_default_expr(0)?;
-                    Some(expr)
-                } else {
-                    None
-                };
-                let allow_null = if self.parse_keywords(vec!["NOT", "NULL"]) {
-                    false
-                } else {
-                    let _ = self.parse_keyword("NULL");
-                    true
-                };
-                debug!("default: {:?}", default);
-
-                columns.push(SQLColumnDef {
-                    name: column_name.as_sql_ident(),
-                    data_type,
-                    allow_null,
-                    is_primary,
-                    is_unique,
-                    default,
-                });
+            } else if let Some(Token::SQLWord(_)) = self.peek_token() {
+                columns.push(self.parse_column_def()?);
             } else {
                 return self.expected("column name or constraint definition", self.peek_token());
             }
@@ -929,16 +1859,132 @@ impl Parser {
                 // allow a trailing comma, even though it's not in standard
                 break;
             } else if !comma {
-                return self.expected("',' or ')' after column definition", self.peek_token());
+                return self.expected_one_of(
+                    "',' or ')' after column definition",
+                    &[",", ")"],
+                    self.peek_token(),
+                );
             }
         }
 
         Ok((columns, constraints))
     }
 
+    /// Parse a column definition: `<name> <data type> <options>`
+    pub fn parse_column_def(&mut self) -> Result<SQLColumnDef, ParserError> {
+        let start = self.peek_span();
+        let name = self.parse_identifier()?;
+        let data_type = self.parse_column_data_type()?;
+        let mut options = vec![];
+        while let Some(option) = self.parse_optional_column_option()? {
+            options.push(option);
+        }
+        Ok(SQLColumnDef {
+            name,
+            data_type,
+            options,
+            span: self.span_from(start),
+        })
+    }
+
+    /// Parse a column constraint or other option following the data type in
+    /// a column definition, returning `None` if there are no more options
+    pub fn parse_optional_column_option(&mut self) -> Result<Option<ColumnOptionDef>, ParserError> {
+        let start = self.peek_span();
+        let name = if self.parse_keyword("CONSTRAINT") {
+            Some(self.parse_identifier()?)
+        } else {
+            None
+        };
+        let option = if self.parse_keywords(vec!["NOT", "NULL"]) {
+            ColumnOption::NotNull
+        } else if self.parse_keyword("NULL") {
+            ColumnOption::Null
+        } else if self.parse_keyword("DEFAULT") {
+            ColumnOption::Default(self.parse_default_expr(0)?)
+        } else if self.parse_keywords(vec!["PRIMARY", "KEY"]) {
+            ColumnOption::Unique { is_primary: true }
+        } else if self.parse_keyword("UNIQUE") {
+            ColumnOption::Unique { is_primary: false }
+        } else if self.parse_keyword("REFERENCES") {
+            let foreign_table = self.parse_object_name()?;
+            let referred_columns = self.parse_parenthesized_column_list(Optional)?;
+            let mut actions = vec![];
+            let (mut on_delete, mut on_update) = (false, false);
+            loop {
+                if !on_delete && self.parse_keywords(vec!["ON", "DELETE"]) {
+                    on_delete = true;
+                    actions.push(ForeignKeyAction::OnDelete(self.parse_referential_action()?));
+                } else if !on_update && self.parse_keywords(vec!["ON", "UPDATE"]) {
+                    on_update = true;
+                    actions.push(ForeignKeyAction::OnUpdate(self.parse_referential_action()?));
+                } else {
+                    break;
+                }
+            }
+            ColumnOption::ForeignKey {
+                foreign_table,
+                referred_columns,
+                actions,
+            }
+        } else if self.parse_keyword("CHECK") {
+            self.expect_token(&Token::LParen)?;
+            let expr = self.parse_expr()?;
+            self.expect_token(&Token::RParen)?;
+            ColumnOption::Check(expr)
+        } else if self.parse_keyword("COLLATE") {
+            ColumnOption::Collate(self.parse_object_name()?)
+        } else if self.parse_keyword("GENERATED") {
+            let always = if self.parse_keyword("ALWAYS") {
+                true
+            } else {
+                self.expect_keyword("BY")?;
+                self.expect_keyword("DEFAULT")?;
+                false
+            };
+            self.expect_keyword("AS")?;
+            self.expect_keyword("IDENTITY")?;
+            ColumnOption::GeneratedAsIdentity { always }
+        } else if self.parse_keyword("AUTOINCREMENT") {
+            ColumnOption::SqliteAutoincrement
+        } else if self.parse_keyword("AUTO_INCREMENT") {
+            ColumnOption::MySqlAutoIncrement
+        } else if name.is_some() {
+            return self.expected("column option after CONSTRAINT <name>", self.peek_token());
+        } else {
+            return Ok(None);
+        };
+        Ok(Some(ColumnOptionDef {
+            name,
+            option,
+            span: self.span_from(start),
+        }))
+    }
+
+    /// Parse the action of an `ON DELETE` or `ON UPDATE` clause
+    pub fn parse_referential_action(&mut self) -> Result<ReferentialAction, ParserError> {
+        if self.parse_keyword("RESTRICT") {
+            Ok(ReferentialAction::Restrict)
+        } else if self.parse_keyword("CASCADE") {
+            Ok(ReferentialAction::Cascade)
+        } else if self.parse_keywords(vec!["SET", "NULL"]) {
+            Ok(ReferentialAction::SetNull)
+        } else if self.parse_keywords(vec!["NO", "ACTION"]) {
+            Ok(ReferentialAction::NoAction)
+        } else if self.parse_keywords(vec!["SET", "DEFAULT"]) {
+            Ok(ReferentialAction::SetDefault)
+        } else {
+            self.expected(
+                "one of RESTRICT, CASCADE, SET NULL, NO ACTION or SET DEFAULT",
+                self.peek_token(),
+            )
+        }
+    }
+
     pub fn parse_optional_table_constraint(
         &mut self,
     ) -> Result<Option<TableConstraint>, ParserError> {
+        let start = self.peek_span();
         let name = if self.parse_keyword("CONSTRAINT") {
             Some(self.parse_identifier()?)
         } else {
@@ -955,6 +2001,7 @@ impl Parser {
                     name,
                     columns,
                     is_primary,
+                    span: self.span_from(start),
                 }))
             }
             Some(Token::SQLWord(ref k)) if k.keyword == "FOREIGN" => {
@@ -968,19 +2015,28 @@ impl Parser {
                     columns,
                     foreign_table,
                     referred_columns,
+                    span: self.span_from(start),
                 }))
             }
             Some(Token::SQLWord(ref k)) if k.keyword == "CHECK" => {
                 self.expect_token(&Token::LParen)?;
                 let expr = Box::new(self.parse_expr()?);
                 self.expect_token(&Token::RParen)?;
-                Ok(Some(TableConstraint::Check { name, expr }))
+                Ok(Some(TableConstraint::Check {
+                    name,
+                    expr,
+                    span: self.span_from(start),
+                }))
             }
             unexpected => {
+                self.prev_token();
                 if name.is_some() {
-                    self.expected("PRIMARY, UNIQUE, FOREIGN, or CHECK", unexpected)
+                    self.expected_one_of(
+                        "PRIMARY, UNIQUE, FOREIGN, or CHECK",
+                        &["PRIMARY", "UNIQUE", "FOREIGN", "CHECK"],
+                        unexpected,
+                    )
                 } else {
-                    self.prev_token();
                     Ok(None)
                 }
             }
@@ -991,10 +2047,15 @@ impl Parser {
         self.expect_token(&Token::LParen)?;
         let mut options = vec![];
         loop {
+            let start = self.peek_span();
             let name = self.parse_identifier()?;
             self.expect_token(&Token::Eq)?;
             let value = self.parse_value()?;
-            options.push(SQLOption { name, value });
+            options.push(SQLOption {
+                name,
+                value,
+                span: self.span_from(start),
+            });
             match self.peek_token() {
                 Some(Token::Comma) => self.next_token(),
                 _ => break,
@@ -1004,27 +2065,228 @@ impl Parser {
         Ok(options)
     }
 
-    pub fn parse_alter(&mut self) -> Result<SQLStatement, ParserError> {
-        self.expect_keyword("TABLE")?;
+    /// Parse MySQL table options following the column list of a `CREATE TABLE`,
+    /// e.g. `ENGINE=InnoDB AUTO_INCREMENT=10 DEFAULT CHARSET=utf8mb4`
+    pub fn parse_table_options(&mut self) -> Result<Vec<SQLTableOption>, ParserError> {
+        let mut options = vec![];
+        loop {
+            let start = self.peek_span();
+            let default = self.parse_keyword("DEFAULT");
+            let name = if self.parse_keywords(vec!["CHARACTER", "SET"]) {
+                "CHARACTER SET".to_string()
+            } else {
+                match (self.peek_token(), self.peek_nth_token(1)) {
+                    (Some(Token::SQLWord(w)), Some(Token::Eq)) => {
+                        self.next_token();
+                        w.as_sql_ident()
+                    }
+                    (Some(Token::SQLWord(ref w)), _)
+                        if default && (w.keyword == "CHARSET" || w.keyword == "COLLATE") =>
+                    {
+                        self.next_token();
+                        w.as_sql_ident()
+                    }
+                    _ if default => {
+                        return self.expected("table option after DEFAULT", self.peek_token())
+                    }
+                    _ => break,
+                }
+            };
+            // MySQL allows omitting the `=`
+            let _ = self.consume_token(&Token::Eq);
+            let value = match self.next_token() {
+                Some(Token::SQLWord(w)) => {
+                    ASTNode::SQLIdentifier(w.as_sql_ident(), self.prev_span())
+                }
+                Some(_) => {
+                    self.prev_token();
+                    self.parse_sql_value()?
+                }
+                None => return self.expected("table option value", None),
+            };
+            options.push(SQLTableOption {
+                default,
+                name,
+                value,
+                span: self.span_from(start),
+            });
+            let _ = self.consume_token(&Token::Comma);
+        }
+        Ok(options)
+    }
+
+    pub fn parse_alter(&mut self) -> Result<SQLStatement, ParserError> {
+        match self.expect_one_of_keywords(&["TABLE", "SEQUENCE"])? {
+            "TABLE" => self.parse_alter_table(),
+            _ => self.parse_alter_sequence(),
+        }
+    }
+
+    pub fn parse_alter_sequence(&mut self) -> Result<SQLStatement, ParserError> {
+        let start = self.prev_span();
+        let if_exists = self.parse_keywords(vec!["IF", "EXISTS"]);
+        let name = self.parse_object_name()?;
+        let options = self.parse_sequence_options()?;
+        if options.is_empty() {
+            return self.expected("a sequence option", self.peek_token());
+        }
+        Ok(SQLStatement::SQLAlterSequence {
+            name,
+            if_exists,
+            options,
+            span: self.span_from(start),
+        })
+    }
+
+    pub fn parse_alter_table(&mut self) -> Result<SQLStatement, ParserError> {
+        let start = self.prev_span();
         let _ = self.parse_keyword("ONLY");
         let table_name = self.parse_object_name()?;
-        let operation = if self.parse_keyword("ADD") {
-            if let Some(constraint) = self.parse_optional_table_constraint()? {
-                AlterTableOperation::AddConstraint(constraint)
+        let mut operations = vec![];
+        loop {
+            operations.push(self.parse_alter_table_operation()?);
+            if !self.consume_token(&Token::Comma) {
+                break;
+            }
+        }
+        Ok(SQLStatement::SQLAlterTable {
+            name: table_name,
+            operations,
+            span: self.span_from(start),
+        })
+    }
+
+    /// Parse a single operation of an `ALTER TABLE` statement
+    pub fn parse_alter_table_operation(&mut self) -> Result<AlterTableOperation, ParserError> {
+        let start = self.peek_span();
+        if self.parse_keyword("ADD") {
+            let column_keyword = self.parse_keyword("COLUMN");
+            if !column_keyword {
+                if let Some(constraint) = self.parse_optional_table_constraint()? {
+                    return Ok(AlterTableOperation::AddConstraint(constraint));
+                }
+            }
+            match self.peek_token() {
+                Some(Token::SQLWord(_)) => Ok(AlterTableOperation::AddColumn {
+                    column_keyword,
+                    column_def: self.parse_column_def()?,
+                    span: self.span_from(start),
+                }),
+                unexpected => self.expected("column name or constraint definition", unexpected),
+            }
+        } else if self.parse_keyword("DROP") {
+            if self.parse_keyword("CONSTRAINT") {
+                Ok(AlterTableOperation::DropConstraint {
+                    name: self.parse_identifier()?,
+                    span: self.span_from(start),
+                })
+            } else {
+                let _ = self.parse_keyword("COLUMN");
+                let if_exists = self.parse_keywords(vec!["IF", "EXISTS"]);
+                let column_name = self.parse_identifier()?;
+                let cascade = self.parse_keyword("CASCADE");
+                Ok(AlterTableOperation::DropColumn {
+                    column_name,
+                    if_exists,
+                    cascade,
+                    span: self.span_from(start),
+                })
+            }
+        } else if self.parse_keyword("RENAME") {
+            if self.parse_keyword("TO") {
+                Ok(AlterTableOperation::RenameTable {
+                    table_name: self.parse_object_name()?,
+                    span: self.span_from(start),
+                })
             } else {
-                return self.expected("a constraint in ALTER TABLE .. ADD", self.peek_token());
+                let _ = self.parse_keyword("COLUMN");
+                let old_column_name = self.parse_identifier()?;
+                self.expect_keyword("TO")?;
+                let new_column_name = self.parse_identifier()?;
+                Ok(AlterTableOperation::RenameColumn {
+                    old_column_name,
+                    new_column_name,
+                    span: self.span_from(start),
+                })
             }
+        } else if self.parse_keyword("ALTER") {
+            let _ = self.parse_keyword("COLUMN");
+            let column_name = self.parse_identifier()?;
+            let op = if self.parse_keywords(vec!["SET", "NOT", "NULL"]) {
+                AlterColumnOperation::SetNotNull
+            } else if self.parse_keywords(vec!["DROP", "NOT", "NULL"]) {
+                AlterColumnOperation::DropNotNull
+            } else if self.parse_keywords(vec!["SET", "DEFAULT"]) {
+                AlterColumnOperation::SetDefault {
+                    value: self.parse_expr()?,
+                }
+            } else if self.parse_keywords(vec!["DROP", "DEFAULT"]) {
+                AlterColumnOperation::DropDefault
+            } else if self.parse_keywords(vec!["SET", "DATA", "TYPE"]) || self.parse_keyword("TYPE")
+            {
+                AlterColumnOperation::SetDataType {
+                    data_type: self.parse_data_type()?,
+                }
+            } else {
+                return self.expected(
+                    "SET/DROP NOT NULL, SET DEFAULT, DROP DEFAULT or SET DATA TYPE after ALTER COLUMN",
+                    self.peek_token(),
+                );
+            };
+            Ok(AlterTableOperation::AlterColumn {
+                column_name,
+                op,
+                span: self.span_from(start),
+            })
+        } else {
+            self.expected(
+                "ADD, DROP, RENAME or ALTER after ALTER TABLE",
+                self.peek_token(),
+            )
+        }
+    }
+
+    /// Parse SQLite's `PRAGMA <name> [ = <value> | (<value>) ]`, assuming
+    /// the `PRAGMA` keyword was already consumed
+    pub fn parse_pragma(&mut self) -> Result<SQLStatement, ParserError> {
+        let start = self.prev_span();
+        let name = self.parse_object_name()?;
+        let (value, is_eq) = if self.consume_token(&Token::Eq) {
+            (Some(self.parse_expr()?), true)
+        } else if self.consume_token(&Token::LParen) {
+            let value = self.parse_expr()?;
+            self.expect_token(&Token::RParen)?;
+            (Some(value), false)
         } else {
-            return self.expected("ADD after ALTER TABLE", self.peek_token());
+            (None, false)
         };
-        Ok(SQLStatement::SQLAlterTable {
-            name: table_name,
-            operation,
+        Ok(SQLStatement::SQLPragma {
+            name,
+            value,
+            is_eq,
+            span: self.span_from(start),
+        })
+    }
+
+    /// Parse SQLite's `ATTACH [ DATABASE ] <file name> AS <schema name>`,
+    /// assuming the `ATTACH` keyword was already consumed
+    pub fn parse_attach_database(&mut self) -> Result<SQLStatement, ParserError> {
+        let start = self.prev_span();
+        let database = self.parse_keyword("DATABASE");
+        let database_file_name = self.parse_expr()?;
+        self.expect_keyword("AS")?;
+        let schema_name = self.parse_identifier()?;
+        Ok(SQLStatement::SQLAttachDatabase {
+            database,
+            database_file_name,
+            schema_name,
+            span: self.span_from(start),
         })
     }
 
     /// Parse a copy statement
     pub fn parse_copy(&mut self) -> Result<SQLStatement, ParserError> {
+        let start = self.prev_span();
         let table_name = self.parse_object_name()?;
         let columns = self.parse_parenthesized_column_list(Optional)?;
         self.expect_keyword("FROM")?;
@@ -1035,6 +2297,7 @@ impl Parser {
             table_name,
             columns,
             values,
+            span: self.span_from(start),
         })
     }
 
@@ -1046,7 +2309,11 @@ impl Parser {
     }
 
     fn parse_sql_value(&mut self) -> Result<ASTNode, ParserError> {
-        Ok(ASTNode::SQLValue(self.parse_value()?))
+        let start = self.peek_span();
+        Ok(ASTNode::SQLValue(
+            self.parse_value()?,
+            self.span_from(start),
+        ))
     }
 
     fn parse_tab_value(&mut self) -> Result<Vec<Option<String>>, ParserError> {
@@ -1093,25 +2360,40 @@ impl Parser {
                     "FALSE" => Ok(Value::Boolean(false)),
                     "NULL" => Ok(Value::Null),
                     _ => {
-                        return parser_err!(format!("No value parser for keyword {}", k.keyword));
+                        self.prev_token();
+                        self.expected("a value", Some(Token::SQLWord(k)))
                     }
                 },
                 Token::Number(ref n) if n.contains('.') => match n.parse::<f64>() {
                     Ok(n) => Ok(Value::Double(n.into())),
-                    Err(e) => parser_err!(format!("Could not parse '{}' as f64: {}", n, e)),
+                    Err(e) => parser_err!(
+                        InvalidLiteral,
+                        self.prev_span(),
+                        format!("Could not parse '{}' as f64: {}", n, e)
+                    ),
                 },
                 Token::Number(ref n) => match n.parse::<u64>() {
                     Ok(n) => Ok(Value::Long(n)),
-                    Err(e) => parser_err!(format!("Could not parse '{}' as u64: {}", n, e)),
+                    Err(e) => parser_err!(
+                        InvalidLiteral,
+                        self.prev_span(),
+                        format!("Could not parse '{}' as u64: {}", n, e)
+                    ),
                 },
-                Token::SingleQuotedString(ref s) => Ok(Value::SingleQuotedString(s.to_string())),
+                Token::SingleQuotedString(ref s) | Token::DoubleQuotedString(ref s) => {
+                    Ok(Value::SingleQuotedString(s.to_string()))
+                }
                 Token::NationalStringLiteral(ref s) => {
                     Ok(Value::NationalStringLiteral(s.to_string()))
                 }
                 Token::HexStringLiteral(ref s) => Ok(Value::HexStringLiteral(s.to_string())),
-                _ => parser_err!(format!("Unsupported value: {:?}", t)),
+                Token::DollarQuotedString(ref s) => Ok(Value::DollarQuotedString(s.clone())),
+                _ => {
+                    self.prev_token();
+                    self.expected("a value", Some(t))
+                }
             },
-            None => parser_err!("Expecting a value, but found EOF"),
+            None => self.expected("a value", None),
         }
     }
 
@@ -1119,9 +2401,16 @@ impl Parser {
     pub fn parse_literal_uint(&mut self) -> Result<u64, ParserError> {
         match self.next_token() {
             Some(Token::Number(s)) => s.parse::<u64>().map_err(|e| {
-                ParserError::ParserError(format!("Could not parse '{}' as u64: {}", s, e))
+                ParserError::new(
+                    ParserErrorKind::InvalidLiteral,
+                    format!("Could not parse '{}' as u64: {}", s, e),
+                    self.prev_span(),
+                )
             }),
-            other => self.expected("literal int", other),
+            other => {
+                self.prev_token();
+                self.expected("literal int", other)
+            }
         }
     }
 
@@ -1129,23 +2418,89 @@ impl Parser {
     pub fn parse_literal_double(&mut self) -> Result<f64, ParserError> {
         match self.next_token() {
             Some(Token::Number(s)) => s.parse::<f64>().map_err(|e| {
-                ParserError::ParserError(format!("Could not parse '{}' as f64: {}", s, e))
+                ParserError::new(
+                    ParserErrorKind::InvalidLiteral,
+                    format!("Could not parse '{}' as f64: {}", s, e),
+                    self.prev_span(),
+                )
             }),
-            other => parser_err!(format!("Expected literal number, found {:?}", other)),
+            other => {
+                self.prev_token();
+                self.expected("literal number", other)
+            }
         }
     }
 
     /// Parse a literal string
     pub fn parse_literal_string(&mut self) -> Result<String, ParserError> {
         match self.next_token() {
-            Some(Token::SingleQuotedString(ref s)) => Ok(s.clone()),
-            other => parser_err!(format!("Expected literal string, found {:?}", other)),
+            Some(Token::SingleQuotedString(ref s)) | Some(Token::DoubleQuotedString(ref s)) => {
+                Ok(s.clone())
+            }
+            other => {
+                self.prev_token();
+                self.expected("literal string", other)
+            }
+        }
+    }
+
+    /// Parse the data type in a column definition. Besides the types known to
+    /// `parse_data_type`, this accepts free-form type names if the dialect
+    /// supports them (see `Dialect::supports_free_form_types`), and no type
+    /// at all if it supports untyped columns.
+    pub fn parse_column_data_type(&mut self) -> Result<SQLType, ParserError> {
+        if self.dialect.supports_untyped_columns() {
+            match self.peek_token() {
+                None | Some(Token::Comma) | Some(Token::RParen) => return Ok(SQLType::Unspecified),
+                Some(Token::SQLWord(ref w))
+                    if keywords::COLUMN_OPTION_KEYWORDS.contains(&w.keyword.as_str()) =>
+                {
+                    return Ok(SQLType::Unspecified)
+                }
+                _ => (),
+            }
+        }
+        if !self.dialect.supports_free_form_types() {
+            return self.parse_data_type();
+        }
+        let start_index = self.index;
+        let data_type = self.parse_data_type()?;
+        match self.peek_token() {
+            Some(Token::LParen) => (),
+            Some(Token::SQLWord(ref w))
+                if !keywords::COLUMN_OPTION_KEYWORDS.contains(&w.keyword.as_str()) => {}
+            _ => return Ok(data_type),
+        }
+        // Not a type we know of, so start over treating it as a free-form type
+        self.index = start_index;
+        let mut words = vec![];
+        while let Some(Token::SQLWord(w)) = self.peek_token() {
+            if !words.is_empty() && keywords::COLUMN_OPTION_KEYWORDS.contains(&w.keyword.as_str()) {
+                break;
+            }
+            self.next_token();
+            words.push(w.as_sql_ident());
+        }
+        let mut args = vec![];
+        if self.consume_token(&Token::LParen) {
+            loop {
+                args.push(self.parse_literal_uint()?);
+                if !self.consume_token(&Token::Comma) {
+                    break;
+                }
+            }
+            self.expect_token(&Token::RParen)?;
         }
+        Ok(SQLType::FreeForm(words, args))
     }
 
     /// Parse a SQL datatype (in the context of a CREATE TABLE statement for example)
     pub fn parse_data_type(&mut self) -> Result<SQLType, ParserError> {
-        match self.next_token() {
+        let dialect = self.dialect;
+        if let Some(data_type) = dialect.parse_data_type(self) {
+            return data_type;
+        }
+        let mut data_type = match self.next_token() {
             Some(Token::SQLWord(k)) => match k.keyword.as_ref() {
                 "BOOLEAN" => Ok(SQLType::Boolean),
                 "FLOAT" => Ok(SQLType::Float(self.parse_optional_precision()?)),
@@ -1184,15 +2539,7 @@ impl Parser {
                     Ok(SQLType::Time)
                 }
                 "REGCLASS" => Ok(SQLType::Regclass),
-                "TEXT" => {
-                    if self.consume_token(&Token::LBracket) {
-                        // Note: this is postgresql-specific
-                        self.expect_token(&Token::RBracket)?;
-                        Ok(SQLType::Array(Box::new(SQLType::Text)))
-                    } else {
-                        Ok(SQLType::Text)
-                    }
-                }
+                "TEXT" => Ok(SQLType::Text),
                 "BYTEA" => Ok(SQLType::Bytea),
                 "NUMERIC" | "DECIMAL" | "DEC" => {
                     let (precision, scale) = self.parse_optional_precision_scale()?;
@@ -1204,8 +2551,17 @@ impl Parser {
                     Ok(SQLType::Custom(type_name))
                 }
             },
-            other => self.expected("a data type name", other),
+            other => {
+                self.prev_token();
+                self.expected("a data type name", other)
+            }
+        }?;
+        // Note: this is postgresql-specific
+        while self.consume_token(&Token::LBracket) {
+            self.expect_token(&Token::RBracket)?;
+            data_type = SQLType::Array(Box::new(data_type));
         }
+        Ok(data_type)
     }
 
     /// Parse `AS identifier` (or simply `identifier` if it's not a reserved keyword)
@@ -1231,10 +2587,10 @@ impl Parser {
             // We accept them as table aliases too, although MSSQL does not.
             Some(Token::SingleQuotedString(ref s)) => Ok(Some(format!("'{}'", s))),
             not_an_ident => {
+                self.prev_token();
                 if after_as {
                     return self.expected("an identifier after AS", not_an_ident);
                 }
-                self.prev_token();
                 Ok(None) // no alias found
             }
         }
@@ -1248,10 +2604,15 @@ impl Parser {
         &mut self,
         reserved_kwds: &[&str],
     ) -> Result<Option<TableAlias>, ParserError> {
+        let start = self.peek_span();
         match self.parse_optional_alias(reserved_kwds)? {
             Some(name) => {
                 let columns = self.parse_parenthesized_column_list(Optional)?;
-                Ok(Some(TableAlias { name, columns }))
+                Ok(Some(TableAlias {
+                    name,
+                    columns,
+                    span: self.span_from(start),
+                }))
             }
             None => Ok(None),
         }
@@ -1295,7 +2656,10 @@ impl Parser {
     pub fn parse_identifier(&mut self) -> Result<SQLIdent, ParserError> {
         match self.next_token() {
             Some(Token::SQLWord(w)) => Ok(w.as_sql_ident()),
-            unexpected => self.expected("identifier", unexpected),
+            unexpected => {
+                self.prev_token();
+                self.expected("identifier", unexpected)
+            }
         }
     }
 
@@ -1343,17 +2707,30 @@ impl Parser {
     }
 
     pub fn parse_delete(&mut self) -> Result<SQLStatement, ParserError> {
+        let start = self.prev_span();
         self.expect_keyword("FROM")?;
         let table_name = self.parse_object_name()?;
+        let alias = self.parse_optional_alias(self.dialect.reserved_for_table_alias())?;
+        let (using, joins) = if self.parse_keyword("USING") {
+            (Some(self.parse_table_factor()?), self.parse_joins()?)
+        } else {
+            (None, vec![])
+        };
         let selection = if self.parse_keyword("WHERE") {
             Some(self.parse_expr()?)
         } else {
             None
         };
+        let returning = self.parse_returning()?;
 
         Ok(SQLStatement::SQLDelete {
             table_name,
+            alias,
+            using,
+            joins,
             selection,
+            returning,
+            span: self.span_from(start),
         })
     }
 
@@ -1362,6 +2739,7 @@ impl Parser {
     /// by `ORDER BY`. Unlike some other parse_... methods, this one doesn't
     /// expect the initial keyword to be already consumed
     pub fn parse_query(&mut self) -> Result<SQLQuery, ParserError> {
+        let start = self.peek_span();
         let ctes = if self.parse_keyword("WITH") {
             // TODO: optional RECURSIVE
             self.parse_cte_list()?
@@ -1377,17 +2755,29 @@ impl Parser {
             vec![]
         };
 
+        let mut offset = None;
         let limit = if self.parse_keyword("LIMIT") {
-            self.parse_limit()?
+            match self.parse_limit()? {
+                // MySQL's `LIMIT <offset>, <count>`
+                Some(value)
+                    if self.dialect.supports_limit_comma() && self.consume_token(&Token::Comma) =>
+                {
+                    offset = Some(Offset {
+                        span: value.span(),
+                        value,
+                        rows: false,
+                    });
+                    Some(self.parse_row_count()?)
+                }
+                limit => limit,
+            }
         } else {
             None
         };
 
-        let offset = if self.parse_keyword("OFFSET") {
-            Some(self.parse_offset()?)
-        } else {
-            None
-        };
+        if offset.is_none() && self.parse_keyword("OFFSET") {
+            offset = Some(self.parse_offset()?);
+        }
 
         let fetch = if self.parse_keyword("FETCH") {
             Some(self.parse_fetch()?)
@@ -1402,6 +2792,7 @@ impl Parser {
             order_by,
             offset,
             fetch,
+            span: self.span_from(start),
         })
     }
 
@@ -1410,16 +2801,19 @@ impl Parser {
     fn parse_cte_list(&mut self) -> Result<Vec<Cte>, ParserError> {
         let mut cte = vec![];
         loop {
+            let start = self.peek_span();
             let alias = self.parse_identifier()?;
             let renamed_columns = self.parse_parenthesized_column_list(Optional)?;
             self.expect_keyword("AS")?;
             self.expect_token(&Token::LParen)?;
+            let query = self.parse_query()?;
+            self.expect_token(&Token::RParen)?;
             cte.push(Cte {
                 alias,
-                query: self.parse_query()?,
+                query,
                 renamed_columns,
+                span: self.span_from(start),
             });
-            self.expect_token(&Token::RParen)?;
             if !self.consume_token(&Token::Comma) {
                 break;
             }
@@ -1438,6 +2832,7 @@ impl Parser {
     fn parse_query_body(&mut self, precedence: u8) -> Result<SQLSetExpr, ParserError> {
         // We parse the expression using a Pratt parser, as in `parse_expr()`.
         // Start by parsing a restricted SELECT or a `(subquery)`:
+        let start = self.peek_span();
         let mut expr = if self.parse_keyword("SELECT") {
             SQLSetExpr::Select(Box::new(self.parse_select()?))
         } else if self.consume_token(&Token::LParen) {
@@ -1472,6 +2867,7 @@ impl Parser {
                 op: op.unwrap(),
                 all: self.parse_keyword("ALL"),
                 right: Box::new(self.parse_query_body(next_precedence)?),
+                span: self.span_from(start),
             };
         }
 
@@ -1490,35 +2886,42 @@ impl Parser {
     /// Parse a restricted `SELECT` statement (no CTEs / `UNION` / `ORDER BY`),
     /// assuming the initial `SELECT` was already consumed
     pub fn parse_select(&mut self) -> Result<SQLSelect, ParserError> {
+        let start = self.prev_span();
         let all = self.parse_keyword("ALL");
         let distinct = self.parse_keyword("DISTINCT");
         if all && distinct {
-            return parser_err!("Cannot specify both ALL and DISTINCT in SELECT");
+            return parser_err!(
+                InvalidSyntax,
+                self.prev_span(),
+                "Cannot specify both ALL and DISTINCT in SELECT"
+            );
         }
-        let projection = self.parse_select_list()?;
+        let projection = self.parse_clause(vec![], Parser::parse_select_list)?;
 
         let (relation, joins) = if self.parse_keyword("FROM") {
-            let relation = Some(self.parse_table_factor()?);
-            let joins = self.parse_joins()?;
-            (relation, joins)
+            self.parse_clause((None, vec![]), |parser| {
+                let relation = Some(parser.parse_table_factor()?);
+                let joins = parser.parse_joins()?;
+                Ok((relation, joins))
+            })?
         } else {
             (None, vec![])
         };
 
         let selection = if self.parse_keyword("WHERE") {
-            Some(self.parse_expr()?)
+            self.parse_clause(None, |parser| parser.parse_expr().map(Some))?
         } else {
             None
         };
 
         let group_by = if self.parse_keywords(vec!["GROUP", "BY"]) {
-            self.parse_expr_list()?
+            self.parse_clause(vec![], Parser::parse_expr_list)?
         } else {
             vec![]
         };
 
         let having = if self.parse_keyword("HAVING") {
-            Some(self.parse_expr()?)
+            self.parse_clause(None, |parser| parser.parse_expr().map(Some))?
         } else {
             None
         };
@@ -1531,20 +2934,23 @@ impl Parser {
             joins,
             group_by,
             having,
+            span: self.span_from(start),
         })
     }
 
     /// A table name or a parenthesized subquery, followed by optional `[AS] alias`
     pub fn parse_table_factor(&mut self) -> Result<TableFactor, ParserError> {
+        let start = self.peek_span();
         let lateral = self.parse_keyword("LATERAL");
         if self.consume_token(&Token::LParen) {
             let subquery = Box::new(self.parse_query()?);
             self.expect_token(&Token::RParen)?;
-            let alias = self.parse_optional_table_alias(keywords::RESERVED_FOR_TABLE_ALIAS)?;
+            let alias = self.parse_optional_table_alias(self.dialect.reserved_for_table_alias())?;
             Ok(TableFactor::Derived {
                 lateral,
                 subquery,
                 alias,
+                span: self.span_from(start),
             })
         } else if lateral {
             self.expected("subquery after LATERAL", self.peek_token())
@@ -1556,7 +2962,7 @@ impl Parser {
             } else {
                 vec![]
             };
-            let alias = self.parse_optional_table_alias(keywords::RESERVED_FOR_TABLE_ALIAS)?;
+            let alias = self.parse_optional_table_alias(self.dialect.reserved_for_table_alias())?;
             // MSSQL-specific table hints:
             let mut with_hints = vec![];
             if self.parse_keyword("WITH") {
@@ -1573,6 +2979,7 @@ impl Parser {
                 alias,
                 args,
                 with_hints,
+                span: self.span_from(start),
             })
         }
     }
@@ -1587,19 +2994,26 @@ impl Parser {
             let columns = self.parse_parenthesized_column_list(Mandatory)?;
             Ok(JoinConstraint::Using(columns))
         } else {
-            self.expected("ON, or USING after JOIN", self.peek_token())
+            self.expected_one_of(
+                "ON, or USING after JOIN",
+                &["ON", "USING"],
+                self.peek_token(),
+            )
         }
     }
 
     fn parse_joins(&mut self) -> Result<Vec<Join>, ParserError> {
         let mut joins = vec![];
         loop {
+            let start = self.peek_span();
             let join = match &self.peek_token() {
                 Some(Token::Comma) => {
                     self.next_token();
+                    let start = self.peek_span();
                     Join {
                         relation: self.parse_table_factor()?,
                         join_operator: JoinOperator::Implicit,
+                        span: self.span_from(start),
                     }
                 }
                 Some(Token::SQLWord(kw)) if kw.keyword == "CROSS" => {
@@ -1608,6 +3022,7 @@ impl Parser {
                     Join {
                         relation: self.parse_table_factor()?,
                         join_operator: JoinOperator::Cross,
+                        span: self.span_from(start),
                     }
                 }
                 _ => {
@@ -1645,6 +3060,7 @@ impl Parser {
                     Join {
                         relation,
                         join_operator: join_operator_type(join_constraint),
+                        span: self.span_from(start),
                     }
                 }
             };
@@ -1655,42 +3071,247 @@ impl Parser {
 
     /// Parse an INSERT statement
     pub fn parse_insert(&mut self) -> Result<SQLStatement, ParserError> {
+        let start = self.prev_span();
+        let or = if self.parse_keyword("OR") {
+            Some(
+                match self
+                    .expect_one_of_keywords(&["ROLLBACK", "ABORT", "FAIL", "IGNORE", "REPLACE"])?
+                {
+                    "ROLLBACK" => SQLiteOnConflict::Rollback,
+                    "ABORT" => SQLiteOnConflict::Abort,
+                    "FAIL" => SQLiteOnConflict::Fail,
+                    "IGNORE" => SQLiteOnConflict::Ignore,
+                    "REPLACE" => SQLiteOnConflict::Replace,
+                    _ => unreachable!(),
+                },
+            )
+        } else {
+            None
+        };
         self.expect_keyword("INTO")?;
         let table_name = self.parse_object_name()?;
         let columns = self.parse_parenthesized_column_list(Optional)?;
         let source = Box::new(self.parse_query()?);
+        let on = if self.parse_keywords(vec!["ON", "DUPLICATE", "KEY", "UPDATE"]) {
+            let start = self.prev_span();
+            let assignments = self.parse_assignments()?;
+            Some(OnInsert::DuplicateKeyUpdate(
+                assignments,
+                self.span_from(start),
+            ))
+        } else if self.parse_keywords(vec!["ON", "CONFLICT"]) {
+            Some(OnInsert::OnConflict(self.parse_on_conflict()?))
+        } else {
+            None
+        };
+        let returning = self.parse_returning()?;
         Ok(SQLStatement::SQLInsert {
+            replace_into: false,
+            or,
             table_name,
             columns,
             source,
+            on,
+            returning,
+            span: self.span_from(start),
+        })
+    }
+
+    /// Parse the rest of PostgreSQL's `ON CONFLICT` clause of an `INSERT`,
+    /// assuming `ON CONFLICT` has already been consumed
+    pub fn parse_on_conflict(&mut self) -> Result<OnConflict, ParserError> {
+        let start = self.prev_span();
+        let conflict_target = if self.parse_keywords(vec!["ON", "CONSTRAINT"]) {
+            Some(ConflictTarget::OnConstraint(self.parse_object_name()?))
+        } else if self.peek_token() == Some(Token::LParen) {
+            let columns = self.parse_parenthesized_column_list(Mandatory)?;
+            let selection = if self.parse_keyword("WHERE") {
+                Some(self.parse_expr()?)
+            } else {
+                None
+            };
+            Some(ConflictTarget::Columns { columns, selection })
+        } else {
+            None
+        };
+        self.expect_keyword("DO")?;
+        let action = if self.parse_keyword("NOTHING") {
+            OnConflictAction::DoNothing
+        } else {
+            self.expect_keyword("UPDATE")?;
+            self.expect_keyword("SET")?;
+            let assignments = self.parse_assignments()?;
+            let selection = if self.parse_keyword("WHERE") {
+                Some(self.parse_expr()?)
+            } else {
+                None
+            };
+            OnConflictAction::DoUpdate {
+                assignments,
+                selection,
+            }
+        };
+        Ok(OnConflict {
+            conflict_target,
+            action,
+            span: self.span_from(start),
         })
     }
 
+    /// Parse an optional `RETURNING <select items>` clause, returning an
+    /// empty list if there is none
+    pub fn parse_returning(&mut self) -> Result<Vec<SQLSelectItem>, ParserError> {
+        if self.parse_keyword("RETURNING") {
+            self.parse_select_list()
+        } else {
+            Ok(vec![])
+        }
+    }
+
+    /// Parse MySQL's `REPLACE INTO`, which otherwise looks like `INSERT INTO`
+    pub fn parse_replace(&mut self) -> Result<SQLStatement, ParserError> {
+        let mut statement = self.parse_insert()?;
+        if let SQLStatement::SQLInsert { replace_into, .. } = &mut statement {
+            *replace_into = true;
+        }
+        Ok(statement)
+    }
+
     pub fn parse_update(&mut self) -> Result<SQLStatement, ParserError> {
+        let start = self.prev_span();
         let table_name = self.parse_object_name()?;
+        let alias = self.parse_optional_alias(self.dialect.reserved_for_table_alias())?;
         self.expect_keyword("SET")?;
-        let mut assignments = vec![];
-        loop {
-            let id = self.parse_identifier()?;
-            self.expect_token(&Token::Eq)?;
-            let value = self.parse_expr()?;
-            assignments.push(SQLAssignment { id, value });
-            if !self.consume_token(&Token::Comma) {
-                break;
-            }
-        }
+        let assignments = self.parse_assignments()?;
+        let (from, joins) = if self.parse_keyword("FROM") {
+            (Some(self.parse_table_factor()?), self.parse_joins()?)
+        } else {
+            (None, vec![])
+        };
         let selection = if self.parse_keyword("WHERE") {
             Some(self.parse_expr()?)
         } else {
             None
         };
+        let returning = self.parse_returning()?;
         Ok(SQLStatement::SQLUpdate {
             table_name,
+            alias,
             assignments,
+            from,
+            joins,
             selection,
+            returning,
+            span: self.span_from(start),
+        })
+    }
+
+    /// Parse a `MERGE` statement, assuming the `MERGE` keyword has already
+    /// been consumed
+    pub fn parse_merge(&mut self) -> Result<SQLStatement, ParserError> {
+        let start = self.prev_span();
+        let into = self.parse_keyword("INTO");
+        let table = self.parse_table_factor()?;
+        self.expect_keyword("USING")?;
+        let source = self.parse_table_factor()?;
+        self.expect_keyword("ON")?;
+        let on = Box::new(self.parse_expr()?);
+        let mut clauses = vec![];
+        while self.peek_token().is_some() && self.peek_token() != Some(Token::SemiColon) {
+            clauses.push(self.parse_merge_clause()?);
+        }
+        if clauses.is_empty() {
+            return self.expected("WHEN", self.peek_token());
+        }
+        Ok(SQLStatement::SQLMerge {
+            into,
+            table,
+            source,
+            on,
+            clauses,
+            span: self.span_from(start),
+        })
+    }
+
+    /// Parse a `WHEN [ NOT ] MATCHED ... THEN ...` clause of a `MERGE`
+    pub fn parse_merge_clause(&mut self) -> Result<MergeClause, ParserError> {
+        let start = self.peek_span();
+        self.expect_keyword("WHEN")?;
+        let kind = if self.parse_keyword("MATCHED") {
+            MergeClauseKind::Matched
+        } else {
+            self.expect_keyword("NOT")?;
+            self.expect_keyword("MATCHED")?;
+            if self.parse_keyword("BY") {
+                match self.expect_one_of_keywords(&["TARGET", "SOURCE"])? {
+                    "TARGET" => MergeClauseKind::NotMatchedByTarget,
+                    "SOURCE" => MergeClauseKind::NotMatchedBySource,
+                    _ => unreachable!(),
+                }
+            } else {
+                MergeClauseKind::NotMatched
+            }
+        };
+        let predicate = if self.parse_keyword("AND") {
+            Some(self.parse_expr()?)
+        } else {
+            None
+        };
+        self.expect_keyword("THEN")?;
+        let action = match kind {
+            // Rows only present in the source can only be inserted...
+            MergeClauseKind::NotMatched | MergeClauseKind::NotMatchedByTarget => {
+                self.expect_keyword("INSERT")?;
+                let columns = self.parse_parenthesized_column_list(Optional)?;
+                self.expect_keyword("VALUES")?;
+                self.expect_token(&Token::LParen)?;
+                let values = self.parse_expr_list()?;
+                self.expect_token(&Token::RParen)?;
+                MergeAction::Insert { columns, values }
+            }
+            // ...while existing target rows can only be updated or deleted
+            MergeClauseKind::Matched | MergeClauseKind::NotMatchedBySource => {
+                match self.expect_one_of_keywords(&["UPDATE", "DELETE"])? {
+                    "UPDATE" => {
+                        self.expect_keyword("SET")?;
+                        MergeAction::Update {
+                            assignments: self.parse_assignments()?,
+                        }
+                    }
+                    "DELETE" => MergeAction::Delete,
+                    _ => unreachable!(),
+                }
+            }
+        };
+        Ok(MergeClause {
+            kind,
+            predicate,
+            action,
+            span: self.span_from(start),
         })
     }
 
+    /// Parse a comma-delimited list of `column = expr` assignments, as in
+    /// `UPDATE ... SET`
+    pub fn parse_assignments(&mut self) -> Result<Vec<SQLAssignment>, ParserError> {
+        let mut assignments = vec![];
+        loop {
+            let start = self.peek_span();
+            let id = self.parse_list_of_ids(&Token::Period)?;
+            self.expect_token(&Token::Eq)?;
+            let value = self.parse_expr()?;
+            assignments.push(SQLAssignment {
+                id,
+                value,
+                span: self.span_from(start),
+            });
+            if !self.consume_token(&Token::Comma) {
+                break;
+            }
+        }
+        Ok(assignments)
+    }
+
     /// Parse a comma-delimited list of SQL expressions
     pub fn parse_expr_list(&mut self) -> Result<Vec<ASTNode>, ParserError> {
         let mut expr_list: Vec<ASTNode> = vec![];
@@ -1718,16 +3339,23 @@ impl Parser {
         let mut projections: Vec<SQLSelectItem> = vec![];
         loop {
             let expr = self.parse_expr()?;
-            if let ASTNode::SQLWildcard = expr {
-                projections.push(SQLSelectItem::Wildcard);
-            } else if let ASTNode::SQLQualifiedWildcard(prefix) = expr {
-                projections.push(SQLSelectItem::QualifiedWildcard(SQLObjectName(prefix)));
+            if let ASTNode::SQLWildcard(span) = expr {
+                projections.push(SQLSelectItem::Wildcard(span));
+            } else if let ASTNode::SQLQualifiedWildcard(prefix, span) = expr {
+                projections.push(SQLSelectItem::QualifiedWildcard(
+                    SQLObjectName(prefix),
+                    span,
+                ));
             } else {
                 // `expr` is a regular SQL expression and can be followed by an alias
                 if let Some(alias) =
-                    self.parse_optional_alias(keywords::RESERVED_FOR_COLUMN_ALIAS)?
+                    self.parse_optional_alias(self.dialect.reserved_for_column_alias())?
                 {
-                    projections.push(SQLSelectItem::ExpressionWithAlias { expr, alias });
+                    projections.push(SQLSelectItem::ExpressionWithAlias {
+                        span: self.span_from(expr.span()),
+                        expr,
+                        alias,
+                    });
                 } else {
                     projections.push(SQLSelectItem::UnnamedExpression(expr));
                 }
@@ -1753,8 +3381,20 @@ impl Parser {
             } else {
                 None
             };
+            let nulls_first = if self.parse_keywords(vec!["NULLS", "FIRST"]) {
+                Some(true)
+            } else if self.parse_keywords(vec!["NULLS", "LAST"]) {
+                Some(false)
+            } else {
+                None
+            };
 
-            expr_list.push(SQLOrderByExpr { expr, asc });
+            expr_list.push(SQLOrderByExpr {
+                span: self.span_from(expr.span()),
+                expr,
+                asc,
+                nulls_first,
+            });
             if !self.consume_token(&Token::Comma) {
                 break;
             }
@@ -1764,26 +3404,43 @@ impl Parser {
 
     /// Parse a LIMIT clause
     pub fn parse_limit(&mut self) -> Result<Option<ASTNode>, ParserError> {
-        self.parse_statement()?;
         if self.parse_keyword("ALL") {
             Ok(None)
         } else {
-            self.parse_literal_uint()
-                .map(|n| Some(ASTNode::SQLValue(Value::Long(n))))
+            Ok(Some(self.parse_row_count()?))
+        }
+    }
+
+    /// Parse the number of rows in a LIMIT or OFFSET clause: an unsigned
+    /// integer or a query parameter
+    fn parse_row_count(&mut self) -> Result<ASTNode, ParserError> {
+        match self.peek_token() {
+            Some(Token::Placeholder(s)) => {
+                self.next_token();
+                Ok(ASTNode::SQLPlaceholder(s, self.prev_span()))
+            }
+            _ => {
+                let n = self.parse_literal_uint()?;
+                Ok(ASTNode::SQLValue(Value::Long(n), self.prev_span()))
+            }
         }
     }
 
     /// Parse an OFFSET clause
-    pub fn parse_offset(&mut self) -> Result<ASTNode, ParserError> {
-        let value = self
-            .parse_literal_uint()
-            .map(|n| ASTNode::SQLValue(Value::Long(n)))?;
-        self.expect_one_of_keywords(&["ROW", "ROWS"])?;
-        Ok(value)
+    pub fn parse_offset(&mut self) -> Result<Offset, ParserError> {
+        let start = self.prev_span();
+        let value = self.parse_row_count()?;
+        let rows = self.parse_one_of_keywords(&["ROW", "ROWS"]).is_some();
+        Ok(Offset {
+            value,
+            rows,
+            span: self.span_from(start),
+        })
     }
 
     /// Parse a FETCH clause
     pub fn parse_fetch(&mut self) -> Result<Fetch, ParserError> {
+        let start = self.prev_span();
         self.expect_one_of_keywords(&["FIRST", "NEXT"])?;
         let (quantity, percent) = if self.parse_one_of_keywords(&["ROW", "ROWS"]).is_some() {
             (None, false)
@@ -1798,16 +3455,22 @@ impl Parser {
         } else if self.parse_keywords(vec!["WITH", "TIES"]) {
             true
         } else {
-            return self.expected("one of ONLY or WITH TIES", self.peek_token());
+            return self.expected_one_of(
+                "one of ONLY or WITH TIES",
+                &["ONLY", "WITH TIES"],
+                self.peek_token(),
+            );
         };
         Ok(Fetch {
             with_ties,
             percent,
             quantity,
+            span: self.span_from(start),
         })
     }
 
     pub fn parse_values(&mut self) -> Result<SQLValues, ParserError> {
+        let start = self.prev_span();
         let mut values = vec![];
         loop {
             self.expect_token(&Token::LParen)?;
@@ -1818,25 +3481,145 @@ impl Parser {
                 _ => break,
             };
         }
-        Ok(SQLValues(values))
+        Ok(SQLValues(values, self.span_from(start)))
+    }
+
+    /// Parse `BEGIN [ TRANSACTION | TRAN | WORK ] [ <mode>, ... ]`, assuming the
+    /// `BEGIN` keyword has already been consumed
+    pub fn parse_begin(&mut self) -> Result<SQLStatement, ParserError> {
+        let start = self.prev_span();
+        let _ = self.parse_one_of_keywords(&["TRANSACTION", "TRAN", "WORK"]);
+        Ok(SQLStatement::SQLStartTransaction {
+            begin: true,
+            modes: self.parse_transaction_modes()?,
+            span: self.span_from(start),
+        })
     }
 
-    pub fn parse_transaction(&mut self) -> Result<SQLStatement, ParserError> {
-        let mut stmts = Vec::new();
-        while self.consume_token(&Token::SemiColon) {
-            ;
+    /// Parse `START TRANSACTION [ <mode>, ... ]`, assuming the `START`
+    /// keyword has already been consumed
+    pub fn parse_start_transaction(&mut self) -> Result<SQLStatement, ParserError> {
+        let start = self.prev_span();
+        self.expect_keyword("TRANSACTION")?;
+        Ok(SQLStatement::SQLStartTransaction {
+            begin: false,
+            modes: self.parse_transaction_modes()?,
+            span: self.span_from(start),
+        })
+    }
+
+    /// Parse `SET TRANSACTION <mode>, ...`, assuming the `SET` keyword has
+    /// already been consumed
+    pub fn parse_set_transaction(&mut self) -> Result<SQLStatement, ParserError> {
+        let start = self.prev_span();
+        self.expect_keyword("TRANSACTION")?;
+        let modes = self.parse_transaction_modes()?;
+        if modes.is_empty() {
+            return self.expected("transaction mode", self.peek_token());
         }
-        while let Some(Token::SQLWord(w)) = self.peek_token() {
-            if w.keyword == "COMMIT" {
+        Ok(SQLStatement::SQLSetTransaction {
+            modes,
+            span: self.span_from(start),
+        })
+    }
+
+    /// Parse a possibly empty list of transaction modes. As in PostgreSQL,
+    /// the commas between the modes are optional.
+    pub fn parse_transaction_modes(&mut self) -> Result<Vec<TransactionMode>, ParserError> {
+        let mut modes = vec![];
+        let mut required = false;
+        loop {
+            let mode = if self.parse_keywords(vec!["ISOLATION", "LEVEL"]) {
+                let level = if self.parse_keywords(vec!["READ", "UNCOMMITTED"]) {
+                    TransactionIsolationLevel::ReadUncommitted
+                } else if self.parse_keywords(vec!["READ", "COMMITTED"]) {
+                    TransactionIsolationLevel::ReadCommitted
+                } else if self.parse_keywords(vec!["REPEATABLE", "READ"]) {
+                    TransactionIsolationLevel::RepeatableRead
+                } else if self.parse_keyword("SERIALIZABLE") {
+                    TransactionIsolationLevel::Serializable
+                } else {
+                    return self.expected("isolation level", self.peek_token());
+                };
+                TransactionMode::IsolationLevel(level)
+            } else if self.parse_keywords(vec!["READ", "ONLY"]) {
+                TransactionMode::AccessMode(TransactionAccessMode::ReadOnly)
+            } else if self.parse_keywords(vec!["READ", "WRITE"]) {
+                TransactionMode::AccessMode(TransactionAccessMode::ReadWrite)
+            } else if required {
+                return self.expected("transaction mode", self.peek_token());
+            } else {
                 break;
-            }
-            let stmt = self.parse_statement()?;
-            stmts.push(Box::new(stmt));
+            };
+            modes.push(mode);
+            // a comma must be followed by another mode
+            required = self.consume_token(&Token::Comma);
         }
-        while self.consume_token(&Token::SemiColon) {
-            ;
+        Ok(modes)
+    }
+
+    /// Parse `COMMIT [ TRANSACTION | TRAN | WORK ] [ AND [ NO ] CHAIN ]`, assuming
+    /// the `COMMIT` keyword has already been consumed
+    pub fn parse_commit(&mut self) -> Result<SQLStatement, ParserError> {
+        let start = self.prev_span();
+        let chain = self.parse_commit_rollback_chain()?;
+        Ok(SQLStatement::SQLCommit {
+            chain,
+            span: self.span_from(start),
+        })
+    }
+
+    /// Parse `ROLLBACK [ TRANSACTION | TRAN | WORK ] [ AND [ NO ] CHAIN ]
+    /// [ TO [ SAVEPOINT ] <name> ]`, assuming the `ROLLBACK` keyword has
+    /// already been consumed
+    pub fn parse_rollback(&mut self) -> Result<SQLStatement, ParserError> {
+        let start = self.prev_span();
+        let chain = self.parse_commit_rollback_chain()?;
+        let savepoint = if self.parse_keyword("TO") {
+            let _ = self.parse_keyword("SAVEPOINT");
+            Some(self.parse_identifier()?)
+        } else {
+            None
+        };
+        Ok(SQLStatement::SQLRollback {
+            chain,
+            savepoint,
+            span: self.span_from(start),
+        })
+    }
+
+    /// Parse the `[ TRANSACTION | WORK ] [ AND [ NO ] CHAIN ]` following
+    /// `COMMIT` or `ROLLBACK`, returning whether `AND CHAIN` was specified
+    fn parse_commit_rollback_chain(&mut self) -> Result<bool, ParserError> {
+        let _ = self.parse_one_of_keywords(&["TRANSACTION", "TRAN", "WORK"]);
+        if self.parse_keyword("AND") {
+            let chain = !self.parse_keyword("NO");
+            self.expect_keyword("CHAIN")?;
+            Ok(chain)
+        } else {
+            Ok(false)
         }
-        Ok(SQLStatement::SQLTransaction(stmts))
+    }
+
+    /// Parse `SAVEPOINT <name>`, assuming the `SAVEPOINT` keyword has
+    /// already been consumed
+    pub fn parse_savepoint(&mut self) -> Result<SQLStatement, ParserError> {
+        let start = self.prev_span();
+        Ok(SQLStatement::SQLSavepoint {
+            name: self.parse_identifier()?,
+            span: self.span_from(start),
+        })
+    }
+
+    /// Parse `RELEASE [ SAVEPOINT ] <name>`, assuming the `RELEASE` keyword
+    /// has already been consumed
+    pub fn parse_release(&mut self) -> Result<SQLStatement, ParserError> {
+        let start = self.prev_span();
+        let _ = self.parse_keyword("SAVEPOINT");
+        Ok(SQLStatement::SQLReleaseSavepoint {
+            name: self.parse_identifier()?,
+            span: self.span_from(start),
+        })
     }
 }
 
@@ -1850,7 +3633,6 @@ impl SQLWord {
 mod tests {
     use super::*;
     use crate::test_utils::all_dialects;
-    use crate::sqlast::ASTNode::SQLFunction;
 
     #[test]
     fn test_prev_index() {
@@ -1875,35 +3657,25 @@ mod tests {
 
     #[test]
     fn test_transaction() {
+        // Each statement of a transaction is parsed separately
         let sql = "BEGIN;\nSELECT version();\nCOMMIT;";
-        all_dialects().run_parser_method(sql, |parser| {
-            assert_eq!(parser.parse_statement(), Ok(SQLStatement::SQLTransaction(vec![
-                Box::new(SQLStatement::SQLQuery(Box::new(SQLQuery {
-                    ctes: vec![],
-                    body: SQLSetExpr::Select(Box::new(
-                        SQLSelect {
-                            distinct: false,
-                            projection: vec![
-                                SQLSelectItem::UnnamedExpression(SQLFunction {
-                                    name: SQLObjectName(vec!["version".to_owned()]),
-                                    args: vec![],
-                                    over: None,
-                                    distinct: false,
-                                })
-                            ],
-                            relation: None,
-                            joins: vec![],
-                            selection: None,
-                            group_by: vec![],
-                            having: None,
-                        }
-                    )),
-                    fetch: None,
-                    offset: None,
-                    order_by: vec![],
-                    limit: None,
-                }))),
-            ])));
-        });
+        let statements = Parser::parse_sql(&GenericSqlDialect {}, sql.to_string()).unwrap();
+        assert_eq!(3, statements.len());
+        assert_eq!(
+            SQLStatement::SQLStartTransaction {
+                begin: true,
+                modes: vec![],
+                span: Span::empty(),
+            },
+            statements[0]
+        );
+        assert_eq!("SELECT version()", statements[1].to_string());
+        assert_eq!(
+            SQLStatement::SQLCommit {
+                chain: false,
+                span: Span::empty(),
+            },
+            statements[2]
+        );
     }
 }
@@ -18,14 +18,19 @@
 //!
 //! The tokens then form the input for the parser, which outputs an Abstract Syntax Tree (AST).
 
+use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::iter::Peekable;
 use std::str::Chars;
 
 use super::dialect::keywords::ALL_KEYWORDS;
 use super::dialect::Dialect;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 /// SQL Token enumeration
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Token {
     /// A keyword (like SELECT) or an optionally quoted SQL identifier
     SQLWord(SQLWord),
@@ -35,10 +40,19 @@ pub enum Token {
     Char(char),
     /// Single quoted string: i.e: 'string'
     SingleQuotedString(String),
+    /// Double quoted string: i.e: "string" (in dialects where `"` does not
+    /// delimit an identifier, e.g. MySQL)
+    DoubleQuotedString(String),
     /// "National" string literal: i.e: N'string'
     NationalStringLiteral(String),
     /// Hexadecimal string literal: i.e.: X'deadbeef'
     HexStringLiteral(String),
+    /// Dollar-quoted string literal: i.e. $$string$$ or $tag$string$tag$
+    /// (PostgreSQL)
+    DollarQuotedString(DollarQuotedString),
+    /// A query parameter, including its prefix: i.e. `?`, `?1`, `:name`,
+    /// `@name` or `$name`, depending on the dialect
+    Placeholder(String),
     /// Comma
     Comma,
     /// Whitespace (space, tab, etc)
@@ -47,6 +61,8 @@ pub enum Token {
     Eq,
     /// Not Equals operator `<>` (or `!=` in some dialects)
     Neq,
+    /// NULL-safe equality operator `<=>` (MySQL)
+    Spaceship,
     /// Less Than operator `<`
     Lt,
     /// Greater han operator `>`
@@ -91,40 +107,44 @@ pub enum Token {
     RBrace,
 }
 
-impl ToString for Token {
-    fn to_string(&self) -> String {
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            Token::SQLWord(ref w) => w.to_string(),
-            Token::Number(ref n) => n.to_string(),
-            Token::Char(ref c) => c.to_string(),
-            Token::SingleQuotedString(ref s) => format!("'{}'", s),
-            Token::NationalStringLiteral(ref s) => format!("N'{}'", s),
-            Token::HexStringLiteral(ref s) => format!("X'{}'", s),
-            Token::Comma => ",".to_string(),
-            Token::Whitespace(ws) => ws.to_string(),
-            Token::Eq => "=".to_string(),
-            Token::Neq => "<>".to_string(),
-            Token::Lt => "<".to_string(),
-            Token::Gt => ">".to_string(),
-            Token::LtEq => "<=".to_string(),
-            Token::GtEq => ">=".to_string(),
-            Token::Plus => "+".to_string(),
-            Token::Minus => "-".to_string(),
-            Token::Mult => "*".to_string(),
-            Token::Div => "/".to_string(),
-            Token::Mod => "%".to_string(),
-            Token::LParen => "(".to_string(),
-            Token::RParen => ")".to_string(),
-            Token::Period => ".".to_string(),
-            Token::Colon => ":".to_string(),
-            Token::DoubleColon => "::".to_string(),
-            Token::SemiColon => ";".to_string(),
-            Token::Backslash => "\\".to_string(),
-            Token::LBracket => "[".to_string(),
-            Token::RBracket => "]".to_string(),
-            Token::Ampersand => "&".to_string(),
-            Token::LBrace => "{".to_string(),
-            Token::RBrace => "}".to_string(),
+            Token::SQLWord(ref w) => write!(f, "{}", w),
+            Token::Number(ref n) => f.write_str(n),
+            Token::Char(ref c) => write!(f, "{}", c),
+            Token::SingleQuotedString(ref s) => write!(f, "'{}'", s.replace('\'', "''")),
+            Token::DoubleQuotedString(ref s) => write!(f, "\"{}\"", s),
+            Token::NationalStringLiteral(ref s) => write!(f, "N'{}'", s.replace('\'', "''")),
+            Token::HexStringLiteral(ref s) => write!(f, "X'{}'", s),
+            Token::DollarQuotedString(ref s) => write!(f, "{}", s),
+            Token::Placeholder(ref s) => f.write_str(s),
+            Token::Comma => f.write_str(","),
+            Token::Whitespace(ws) => write!(f, "{}", ws),
+            Token::Eq => f.write_str("="),
+            Token::Neq => f.write_str("<>"),
+            Token::Spaceship => f.write_str("<=>"),
+            Token::Lt => f.write_str("<"),
+            Token::Gt => f.write_str(">"),
+            Token::LtEq => f.write_str("<="),
+            Token::GtEq => f.write_str(">="),
+            Token::Plus => f.write_str("+"),
+            Token::Minus => f.write_str("-"),
+            Token::Mult => f.write_str("*"),
+            Token::Div => f.write_str("/"),
+            Token::Mod => f.write_str("%"),
+            Token::LParen => f.write_str("("),
+            Token::RParen => f.write_str(")"),
+            Token::Period => f.write_str("."),
+            Token::Colon => f.write_str(":"),
+            Token::DoubleColon => f.write_str("::"),
+            Token::SemiColon => f.write_str(";"),
+            Token::Backslash => f.write_str("\\"),
+            Token::LBracket => f.write_str("["),
+            Token::RBracket => f.write_str("]"),
+            Token::Ampersand => f.write_str("&"),
+            Token::LBrace => f.write_str("{"),
+            Token::RBrace => f.write_str("}"),
         }
     }
 }
@@ -134,11 +154,20 @@ impl Token {
         Token::make_word(keyword, None)
     }
     pub fn make_word(word: &str, quote_style: Option<char>) -> Self {
+        Token::make_word_with_keywords(word, quote_style, ALL_KEYWORDS)
+    }
+    /// Like `make_word`, but only recognizing the given `keywords` (see
+    /// `Dialect::keywords`)
+    pub fn make_word_with_keywords(
+        word: &str,
+        quote_style: Option<char>,
+        keywords: &[&str],
+    ) -> Self {
         let word_uppercase = word.to_uppercase();
         //TODO: need to reintroduce FnvHashSet at some point .. iterating over keywords is
         // not fast but I want the simplicity for now while I experiment with pluggable
         // dialects
-        let is_keyword = quote_style == None && ALL_KEYWORDS.contains(&word_uppercase.as_str());
+        let is_keyword = quote_style.is_none() && keywords.contains(&word_uppercase.as_str());
         Token::SQLWord(SQLWord {
             value: word.to_string(),
             quote_style,
@@ -153,6 +182,7 @@ impl Token {
 
 /// A keyword (like SELECT) or an optionally quoted SQL identifier
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SQLWord {
     /// The value of the token, without the enclosing quotes, and with the
     /// escape sequences (if any) processed (TODO: escapes are not handled)
@@ -166,13 +196,13 @@ pub struct SQLWord {
     pub keyword: String,
 }
 
-impl ToString for SQLWord {
-    fn to_string(&self) -> String {
+impl fmt::Display for SQLWord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self.quote_style {
             Some(s) if s == '"' || s == '[' || s == '`' => {
-                format!("{}{}{}", s, self.value, SQLWord::matching_end_quote(s))
+                write!(f, "{}{}{}", s, self.value, SQLWord::matching_end_quote(s))
             }
-            None => self.value.clone(),
+            None => f.write_str(&self.value),
             _ => panic!("Unexpected quote_style!"),
         }
     }
@@ -188,30 +218,198 @@ impl SQLWord {
     }
 }
 
+/// The contents of a dollar-quoted string (`$tag$value$tag$`)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DollarQuotedString {
+    pub value: String,
+    /// The tag between the dollar signs, `None` for `$$value$$`
+    pub tag: Option<String>,
+}
+
+impl fmt::Display for DollarQuotedString {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let tag = self.tag.as_ref().map_or("", String::as_str);
+        write!(f, "${}${}${}$", tag, self.value, tag)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Whitespace {
     Space,
     Newline,
     Tab,
-    SingleLineComment(String),
+    /// A comment running to the end of the line, introduced by `prefix`
+    /// (`--`, or `#` in MySQL)
+    SingleLineComment {
+        comment: String,
+        prefix: String,
+    },
     MultiLineComment(String),
 }
 
-impl ToString for Whitespace {
-    fn to_string(&self) -> String {
+impl fmt::Display for Whitespace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            Whitespace::Space => " ".to_string(),
-            Whitespace::Newline => "\n".to_string(),
-            Whitespace::Tab => "\t".to_string(),
-            Whitespace::SingleLineComment(s) => format!("--{}", s),
-            Whitespace::MultiLineComment(s) => format!("/*{}*/", s),
+            Whitespace::Space => f.write_str(" "),
+            Whitespace::Newline => f.write_str("\n"),
+            Whitespace::Tab => f.write_str("\t"),
+            Whitespace::SingleLineComment { comment, prefix } => {
+                write!(f, "{}{}", prefix, comment)
+            }
+            Whitespace::MultiLineComment(s) => write!(f, "/*{}*/", s),
+        }
+    }
+}
+
+/// A position in the source text: a byte offset together with the 1-based
+/// line and column (counted in characters) it corresponds to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Location {
+    /// Byte offset from the start of the query
+    pub offset: usize,
+    /// Line number, starting from 1 (0 means "unknown")
+    pub line: u64,
+    /// Column number, starting from 1 (0 means "unknown")
+    pub column: u64,
+}
+
+/// The source range a token or an AST node was parsed from, `start`
+/// inclusive and `end` exclusive.
+///
+/// Spans carry no meaning for the SQL itself, so all spans compare equal and
+/// hash identically: two ASTs parsed from differently formatted but
+/// otherwise identical SQL are still `==`. Compare `start`/`end` directly
+/// when the location matters.
+#[derive(Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Span {
+    pub start: Location,
+    pub end: Location,
+}
+
+impl Span {
+    pub fn new(start: Location, end: Location) -> Self {
+        Span { start, end }
+    }
+
+    /// A span with unknown location, used for nodes that were not produced
+    /// by the parser (e.g. ones constructed by hand).
+    pub fn empty() -> Self {
+        Span::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start.line == 0
+    }
+
+    /// The smallest span covering both `self` and `other`. An empty span is
+    /// ignored, so that `union` can be used to fold a list of spans.
+    pub fn union(&self, other: &Span) -> Span {
+        if self.is_empty() {
+            *other
+        } else if other.is_empty() {
+            *self
+        } else {
+            Span {
+                start: std::cmp::min(self.start, other.start),
+                end: std::cmp::max(self.end, other.end),
+            }
         }
     }
 }
 
+impl PartialEq for Span {
+    fn eq(&self, _other: &Span) -> bool {
+        true
+    }
+}
+
+impl Eq for Span {}
+
+impl Hash for Span {
+    fn hash<H: Hasher>(&self, _state: &mut H) {}
+}
+
+impl fmt::Debug for Span {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Span({}:{}..{}:{})",
+            self.start.line, self.start.column, self.end.line, self.end.column
+        )
+    }
+}
+
+/// A token together with the span of the source text it was produced from
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TokenWithSpan {
+    pub token: Token,
+    pub span: Span,
+}
+
+impl TokenWithSpan {
+    pub fn new(token: Token, span: Span) -> Self {
+        TokenWithSpan { token, span }
+    }
+
+    /// Wrap a token that did not come from the tokenizer (and therefore has
+    /// no location)
+    pub fn wrap(token: Token) -> Self {
+        TokenWithSpan::new(token, Span::empty())
+    }
+}
+
 /// Tokenizer error
 #[derive(Debug, PartialEq)]
-pub struct TokenizerError(String);
+pub struct TokenizerError {
+    pub message: String,
+    /// Where in the input the error was detected
+    pub location: Location,
+}
+
+/// A `Peekable<Chars>` that keeps track of the location of the next
+/// character as the input is consumed.
+struct State<'a> {
+    peekable: Peekable<Chars<'a>>,
+    offset: usize,
+    line: u64,
+    column: u64,
+}
+
+impl<'a> State<'a> {
+    fn next(&mut self) -> Option<char> {
+        match self.peekable.next() {
+            None => None,
+            Some(ch) => {
+                self.offset += ch.len_utf8();
+                // A lone `\r` is a line break too, see `Whitespace::Newline`
+                if ch == '\n' || (ch == '\r' && self.peekable.peek() != Some(&'\n')) {
+                    self.line += 1;
+                    self.column = 1;
+                } else {
+                    self.column += 1;
+                }
+                Some(ch)
+            }
+        }
+    }
+
+    fn peek(&mut self) -> Option<&char> {
+        self.peekable.peek()
+    }
+
+    fn location(&self) -> Location {
+        Location {
+            offset: self.offset,
+            line: self.line,
+            column: self.column,
+        }
+    }
+}
 
 /// SQL Tokenizer
 pub struct Tokenizer<'a> {
@@ -234,32 +432,71 @@ impl<'a> Tokenizer<'a> {
 
     /// Tokenize the statement and produce a vector of tokens
     pub fn tokenize(&mut self) -> Result<Vec<Token>, TokenizerError> {
-        let mut peekable = self.query.chars().peekable();
+        Ok(self
+            .tokenize_with_span()?
+            .into_iter()
+            .map(|t| t.token)
+            .collect())
+    }
 
-        let mut tokens: Vec<Token> = vec![];
+    /// Tokenize the statement and produce a vector of tokens, each annotated
+    /// with the span of the source text it was produced from
+    pub fn tokenize_with_span(&mut self) -> Result<Vec<TokenWithSpan>, TokenizerError> {
+        let (tokens, mut errors) = self.tokenize_and_collect_errors(false);
+        match errors.pop() {
+            Some(error) => Err(error),
+            None => Ok(tokens),
+        }
+    }
 
-        while let Some(token) = self.next_token(&mut peekable)? {
-            match &token {
-                Token::Whitespace(Whitespace::Newline) => {
-                    self.line += 1;
-                    self.col = 1;
-                }
+    /// Like `tokenize_with_span`, but rather than stopping at the first
+    /// error, skip the offending input and carry on. Returns the tokens
+    /// produced along with all the errors encountered.
+    pub fn tokenize_with_recovery(&mut self) -> (Vec<TokenWithSpan>, Vec<TokenizerError>) {
+        self.tokenize_and_collect_errors(true)
+    }
 
-                Token::Whitespace(Whitespace::Tab) => self.col += 4,
-                Token::SQLWord(w) if w.quote_style == None => self.col += w.value.len() as u64,
-                Token::SQLWord(w) if w.quote_style != None => self.col += w.value.len() as u64 + 2,
-                Token::Number(s) => self.col += s.len() as u64,
-                Token::SingleQuotedString(s) => self.col += s.len() as u64,
-                _ => self.col += 1,
-            }
+    fn tokenize_and_collect_errors(
+        &mut self,
+        recover: bool,
+    ) -> (Vec<TokenWithSpan>, Vec<TokenizerError>) {
+        let query = self.query.clone();
+        let mut state = State {
+            peekable: query.chars().peekable(),
+            offset: 0,
+            line: self.line,
+            column: self.col,
+        };
 
-            tokens.push(token);
+        let mut tokens: Vec<TokenWithSpan> = vec![];
+        let mut errors = vec![];
+
+        let mut start = state.location();
+        loop {
+            match self.next_token(&mut state) {
+                Ok(Some(token)) => {
+                    let end = state.location();
+                    tokens.push(TokenWithSpan::new(token, Span::new(start, end)));
+                    start = end;
+                }
+                Ok(None) => break,
+                Err(error) => {
+                    // the erroneous input has been consumed by `next_token`
+                    errors.push(error);
+                    if !recover {
+                        break;
+                    }
+                    start = state.location();
+                }
+            }
         }
-        Ok(tokens)
+        self.line = state.line;
+        self.col = state.column;
+        (tokens, errors)
     }
 
     /// Get the next token or return None
-    fn next_token(&self, chars: &mut Peekable<Chars<'_>>) -> Result<Option<Token>, TokenizerError> {
+    fn next_token(&self, chars: &mut State) -> Result<Option<Token>, TokenizerError> {
         //println!("next_token: {:?}", chars.peek());
         match chars.peek() {
             Some(&ch) => match ch {
@@ -285,7 +522,7 @@ impl<'a> Tokenizer<'a> {
                         _ => {
                             // regular identifier starting with an "N"
                             let s = self.tokenize_word('N', chars);
-                            Ok(Some(Token::make_word(&s, None)))
+                            Ok(Some(self.make_word(&s)))
                         }
                     }
                 }
@@ -302,21 +539,35 @@ impl<'a> Tokenizer<'a> {
                         _ => {
                             // regular identifier starting with an "X"
                             let s = self.tokenize_word(x, chars);
-                            Ok(Some(Token::make_word(&s, None)))
+                            Ok(Some(self.make_word(&s)))
                         }
                     }
                 }
+                // dollar-quoted string, or a `$1` query parameter
+                '$' if self.dialect.supports_dollar_quoted_strings() => {
+                    chars.next(); // consume the `$`
+                    self.tokenize_dollar_quoted_string(chars)
+                }
+                // query parameter
+                ch if self.dialect.is_placeholder_start(ch) => {
+                    chars.next(); // consume the prefix
+                    self.tokenize_placeholder(ch.to_string(), chars)
+                }
                 // identifier or keyword
                 ch if self.dialect.is_identifier_start(ch) => {
                     chars.next(); // consume the first char
                     let s = self.tokenize_word(ch, chars);
-                    Ok(Some(Token::make_word(&s, None)))
+                    Ok(Some(self.make_word(&s)))
                 }
                 // string
                 '\'' => {
                     let s = self.tokenize_single_quoted_string(chars);
                     Ok(Some(Token::SingleQuotedString(s)))
                 }
+                '"' if self.dialect.supports_double_quoted_strings() => {
+                    let s = self.tokenize_quoted_string(chars, '"');
+                    Ok(Some(Token::DoubleQuotedString(s)))
+                }
                 // delimited (quoted) identifier
                 quote_start if self.dialect.is_delimited_identifier_start(quote_start) => {
                     let mut s = String::new();
@@ -354,27 +605,16 @@ impl<'a> Tokenizer<'a> {
                     match chars.peek() {
                         Some('-') => {
                             chars.next(); // consume the second '-', starting a single-line comment
-                            let mut s = String::new();
-                            loop {
-                                match chars.next() {
-                                    Some(ch) if ch != '\n' => {
-                                        s.push(ch);
-                                    }
-                                    other => {
-                                        if other.is_some() {
-                                            s.push('\n');
-                                        }
-                                        break Ok(Some(Token::Whitespace(
-                                            Whitespace::SingleLineComment(s),
-                                        )));
-                                    }
-                                }
-                            }
+                            Ok(Some(self.tokenize_single_line_comment(chars, "--")))
                         }
                         // a regular '-' operator
                         _ => Ok(Some(Token::Minus)),
                     }
                 }
+                '#' if self.dialect.supports_hash_comments() => {
+                    chars.next(); // consume the '#', starting a single-line comment
+                    Ok(Some(self.tokenize_single_line_comment(chars, "#")))
+                }
                 '/' => {
                     chars.next(); // consume the '/'
                     match chars.peek() {
@@ -392,52 +632,47 @@ impl<'a> Tokenizer<'a> {
                 '=' => self.consume_and_return(chars, Token::Eq),
                 '.' => self.consume_and_return(chars, Token::Period),
                 '!' => {
+                    let location = chars.location();
                     chars.next(); // consume
                     match chars.peek() {
-                        Some(&ch) => match ch {
-                            '=' => self.consume_and_return(chars, Token::Neq),
-                            _ => Err(TokenizerError(format!(
+                        Some(&'=') => self.consume_and_return(chars, Token::Neq),
+                        _ => Err(TokenizerError {
+                            message: format!(
                                 "Tokenizer Error at Line: {}, Col: {}",
-                                self.line, self.col
-                            ))),
-                        },
-                        None => Err(TokenizerError(format!(
-                            "Tokenizer Error at Line: {}, Col: {}",
-                            self.line, self.col
-                        ))),
+                                location.line, location.column
+                            ),
+                            location,
+                        }),
                     }
                 }
                 '<' => {
                     chars.next(); // consume
                     match chars.peek() {
-                        Some(&ch) => match ch {
-                            '=' => self.consume_and_return(chars, Token::LtEq),
-                            '>' => self.consume_and_return(chars, Token::Neq),
-                            _ => Ok(Some(Token::Lt)),
-                        },
-                        None => Ok(Some(Token::Lt)),
+                        Some(&'=') => {
+                            chars.next(); // consume
+                            match chars.peek() {
+                                Some(&'>') => self.consume_and_return(chars, Token::Spaceship),
+                                _ => Ok(Some(Token::LtEq)),
+                            }
+                        }
+                        Some(&'>') => self.consume_and_return(chars, Token::Neq),
+                        _ => Ok(Some(Token::Lt)),
                     }
                 }
                 '>' => {
                     chars.next(); // consume
                     match chars.peek() {
-                        Some(&ch) => match ch {
-                            '=' => self.consume_and_return(chars, Token::GtEq),
-                            _ => Ok(Some(Token::Gt)),
-                        },
-                        None => Ok(Some(Token::Gt)),
+                        Some(&'=') => self.consume_and_return(chars, Token::GtEq),
+                        _ => Ok(Some(Token::Gt)),
                     }
                 }
                 // colon
                 ':' => {
                     chars.next();
                     match chars.peek() {
-                        Some(&ch) => match ch {
-                            // double colon
-                            ':' => self.consume_and_return(chars, Token::DoubleColon),
-                            _ => Ok(Some(Token::Colon)),
-                        },
-                        None => Ok(Some(Token::Colon)),
+                        // double colon
+                        Some(&':') => self.consume_and_return(chars, Token::DoubleColon),
+                        _ => Ok(Some(Token::Colon)),
                     }
                 }
                 ';' => self.consume_and_return(chars, Token::SemiColon),
@@ -454,8 +689,13 @@ impl<'a> Tokenizer<'a> {
         }
     }
 
+    /// Make an unquoted word token, recognizing the dialect's keywords
+    fn make_word(&self, word: &str) -> Token {
+        Token::make_word_with_keywords(word, None, self.dialect.keywords())
+    }
+
     /// Tokenize an identifier or keyword, after the first char is already consumed.
-    fn tokenize_word(&self, first_char: char, chars: &mut Peekable<Chars<'_>>) -> String {
+    fn tokenize_word(&self, first_char: char, chars: &mut State) -> String {
         let mut s = String::new();
         s.push(first_char);
         while let Some(&ch) = chars.peek() {
@@ -469,26 +709,139 @@ impl<'a> Tokenizer<'a> {
         s
     }
 
+    /// Tokenize a query parameter, after the start of it (its prefix and
+    /// possibly more), `s`, is already consumed
+    fn tokenize_placeholder(
+        &self,
+        mut s: String,
+        chars: &mut State,
+    ) -> Result<Option<Token>, TokenizerError> {
+        let prefix = s.chars().next().unwrap();
+        while let Some(&ch) = chars.peek() {
+            // `$` may be part of an identifier, but not of a parameter name
+            if ch != '$' && (self.dialect.is_identifier_part(ch) || ch.is_ascii_digit()) {
+                chars.next(); // consume
+                s.push(ch);
+            } else {
+                break;
+            }
+        }
+        match prefix {
+            // only `?` is a parameter on its own
+            '?' => Ok(Some(Token::Placeholder(s))),
+            ':' if s.len() == 1 => match chars.peek() {
+                Some(&':') => self.consume_and_return(chars, Token::DoubleColon),
+                _ => Ok(Some(Token::Colon)),
+            },
+            _ if s.len() == 1 => Ok(Some(Token::Char(prefix))),
+            _ => Ok(Some(Token::Placeholder(s))),
+        }
+    }
+
+    /// Tokenize a `$tag$...$tag$` string, after the opening `$` is already
+    /// consumed. If the `$` doesn't start a tag, it is the prefix of a query
+    /// parameter instead.
+    fn tokenize_dollar_quoted_string(
+        &self,
+        chars: &mut State,
+    ) -> Result<Option<Token>, TokenizerError> {
+        // A tag follows the rules for an unquoted identifier, except that it
+        // can't contain `$`
+        let mut tag = String::new();
+        if matches!(chars.peek(), Some(&ch) if self.dialect.is_identifier_start(ch)) {
+            while let Some(&ch) = chars.peek() {
+                if ch == '$' || !self.dialect.is_identifier_part(ch) {
+                    break;
+                }
+                chars.next(); // consume
+                tag.push(ch);
+            }
+        }
+        if chars.peek() != Some(&'$') {
+            return self.tokenize_placeholder(format!("${}", tag), chars);
+        }
+        chars.next(); // consume the `$` ending the opening delimiter
+
+        let delimiter = format!("${}$", tag);
+        let mut value = String::new();
+        loop {
+            match chars.next() {
+                Some(ch) => {
+                    value.push(ch);
+                    if value.ends_with(&delimiter) {
+                        value.truncate(value.len() - delimiter.len());
+                        break;
+                    }
+                }
+                None => {
+                    return Err(TokenizerError {
+                        message: "Unexpected EOF while in a dollar-quoted string".to_string(),
+                        location: chars.location(),
+                    });
+                }
+            }
+        }
+        Ok(Some(Token::DollarQuotedString(DollarQuotedString {
+            value,
+            tag: if tag.is_empty() { None } else { Some(tag) },
+        })))
+    }
+
+    /// Read a single-line comment, after its `prefix` is already consumed.
+    /// The terminating newline (if any) is kept as part of the comment.
+    fn tokenize_single_line_comment(&self, chars: &mut State, prefix: &str) -> Token {
+        let mut comment = String::new();
+        while let Some(ch) = chars.next() {
+            comment.push(ch);
+            if ch == '\n' {
+                break;
+            }
+        }
+        Token::Whitespace(Whitespace::SingleLineComment {
+            comment,
+            prefix: prefix.to_string(),
+        })
+    }
+
     /// Read a single quoted string, starting with the opening quote.
-    fn tokenize_single_quoted_string(&self, chars: &mut Peekable<Chars<'_>>) -> String {
-        //TODO: handle escaped quotes in string
-        //TODO: handle newlines in string
+    fn tokenize_single_quoted_string(&self, chars: &mut State) -> String {
+        self.tokenize_quoted_string(chars, '\'')
+    }
+
+    /// Read a string delimited by `quote`, starting with the opening quote.
+    /// A doubled quote stands for the quote character itself, and so does a
+    /// backslash escape in dialects that support them.
+    fn tokenize_quoted_string(&self, chars: &mut State, quote: char) -> String {
         //TODO: handle EOF before terminating quote
         //TODO: handle 'string' <white space> 'string continuation'
         let mut s = String::new();
         chars.next(); // consume the opening quote
         while let Some(&ch) = chars.peek() {
             match ch {
-                '\'' => {
+                c if c == quote => {
                     chars.next(); // consume
-                    let escaped_quote = chars.peek().map(|c| *c == '\'').unwrap_or(false);
+                    let escaped_quote = chars.peek().map(|c| *c == quote).unwrap_or(false);
                     if escaped_quote {
-                        s.push('\'');
+                        s.push(quote);
                         chars.next();
                     } else {
                         break;
                     }
                 }
+                '\\' if self.dialect.supports_backslash_escapes() => {
+                    // Escape sequences are kept as written (so that the
+                    // string prints back the same), except for escaped
+                    // quotes, which are stored as plain quote characters
+                    chars.next(); // consume the backslash
+                    match chars.next() {
+                        Some(c @ '\'') | Some(c @ '"') => s.push(c),
+                        Some(c) => {
+                            s.push('\\');
+                            s.push(c);
+                        }
+                        None => break,
+                    }
+                }
                 _ => {
                     chars.next(); // consume
                     s.push(ch);
@@ -500,7 +853,7 @@ impl<'a> Tokenizer<'a> {
 
     fn tokenize_multiline_comment(
         &self,
-        chars: &mut Peekable<Chars<'_>>,
+        chars: &mut State,
     ) -> Result<Option<Token>, TokenizerError> {
         let mut s = String::new();
         let mut maybe_closing_comment = false;
@@ -521,9 +874,10 @@ impl<'a> Tokenizer<'a> {
                     }
                 }
                 None => {
-                    break Err(TokenizerError(
-                        "Unexpected EOF while in a multi-line comment".to_string(),
-                    ));
+                    break Err(TokenizerError {
+                        message: "Unexpected EOF while in a multi-line comment".to_string(),
+                        location: chars.location(),
+                    });
                 }
             }
         }
@@ -531,7 +885,7 @@ impl<'a> Tokenizer<'a> {
 
     fn consume_and_return(
         &self,
-        chars: &mut Peekable<Chars<'_>>,
+        chars: &mut State,
         t: Token,
     ) -> Result<Option<Token>, TokenizerError> {
         chars.next();
@@ -541,7 +895,7 @@ impl<'a> Tokenizer<'a> {
 
 #[cfg(test)]
 mod tests {
-    use super::super::dialect::GenericSqlDialect;
+    use super::super::dialect::{GenericSqlDialect, PostgreSqlDialect};
     use super::*;
 
     #[test]
@@ -706,6 +1060,74 @@ mod tests {
         compare(expected, tokens);
     }
 
+    #[test]
+    fn tokenize_placeholders() {
+        let sql = String::from("?, $1, :name, a::int, b : c");
+        let dialect = GenericSqlDialect {};
+        let mut tokenizer = Tokenizer::new(&dialect, &sql);
+        let tokens = tokenizer.tokenize().unwrap();
+
+        let expected = vec![
+            Token::Placeholder("?".to_string()),
+            Token::Comma,
+            Token::Whitespace(Whitespace::Space),
+            Token::Placeholder("$1".to_string()),
+            Token::Comma,
+            Token::Whitespace(Whitespace::Space),
+            Token::Placeholder(":name".to_string()),
+            Token::Comma,
+            Token::Whitespace(Whitespace::Space),
+            Token::make_word("a", None),
+            Token::DoubleColon,
+            Token::make_keyword("int"),
+            Token::Comma,
+            Token::Whitespace(Whitespace::Space),
+            Token::make_word("b", None),
+            Token::Whitespace(Whitespace::Space),
+            Token::Colon,
+            Token::Whitespace(Whitespace::Space),
+            Token::make_word("c", None),
+        ];
+
+        compare(expected, tokens);
+    }
+
+    #[test]
+    fn tokenize_dollar_quoted_strings() {
+        let sql = String::from("$$it's$$, $fn$ $$ $f $fn$, $1, $a");
+        let dialect = PostgreSqlDialect {};
+        let mut tokenizer = Tokenizer::new(&dialect, &sql);
+        let tokens = tokenizer.tokenize().unwrap();
+
+        let expected = vec![
+            Token::DollarQuotedString(DollarQuotedString {
+                value: "it's".to_string(),
+                tag: None,
+            }),
+            Token::Comma,
+            Token::Whitespace(Whitespace::Space),
+            Token::DollarQuotedString(DollarQuotedString {
+                value: " $$ $f ".to_string(),
+                tag: Some("fn".to_string()),
+            }),
+            Token::Comma,
+            Token::Whitespace(Whitespace::Space),
+            Token::Placeholder("$1".to_string()),
+            Token::Comma,
+            Token::Whitespace(Whitespace::Space),
+            Token::Placeholder("$a".to_string()),
+        ];
+
+        compare(expected, tokens);
+
+        let sql = String::from("SELECT $tag$a$ta$");
+        let mut tokenizer = Tokenizer::new(&dialect, &sql);
+        assert_eq!(
+            "Unexpected EOF while in a dollar-quoted string",
+            tokenizer.tokenize().unwrap_err().message
+        );
+    }
+
     #[test]
     fn tokenize_comment() {
         let sql = String::from("0--this is a comment\n1");
@@ -715,9 +1137,10 @@ mod tests {
         let tokens = tokenizer.tokenize().unwrap();
         let expected = vec![
             Token::Number("0".to_string()),
-            Token::Whitespace(Whitespace::SingleLineComment(
-                "this is a comment\n".to_string(),
-            )),
+            Token::Whitespace(Whitespace::SingleLineComment {
+                comment: "this is a comment\n".to_string(),
+                prefix: "--".to_string(),
+            }),
             Token::Number("1".to_string()),
         ];
         compare(expected, tokens);
@@ -730,9 +1153,10 @@ mod tests {
         let dialect = GenericSqlDialect {};
         let mut tokenizer = Tokenizer::new(&dialect, &sql);
         let tokens = tokenizer.tokenize().unwrap();
-        let expected = vec![Token::Whitespace(Whitespace::SingleLineComment(
-            "this is a comment".to_string(),
-        ))];
+        let expected = vec![Token::Whitespace(Whitespace::SingleLineComment {
+            comment: "this is a comment".to_string(),
+            prefix: "--".to_string(),
+        })];
         compare(expected, tokens);
     }
 
@@ -788,6 +1212,35 @@ mod tests {
         compare(expected, tokens);
     }
 
+    #[test]
+    fn tokenize_with_span() {
+        let sql = String::from("SELECT a,\n  'é' FROM t");
+        let dialect = GenericSqlDialect {};
+        let mut tokenizer = Tokenizer::new(&dialect, &sql);
+        let tokens = tokenizer.tokenize_with_span().unwrap();
+        let locations: Vec<_> = tokens
+            .iter()
+            .filter(|t| !matches!(t.token, Token::Whitespace(_)))
+            .map(|t| {
+                let (start, end) = (t.span.start, t.span.end);
+                (
+                    &sql[start.offset..end.offset],
+                    (start.line, start.column),
+                    (end.line, end.column),
+                )
+            })
+            .collect();
+        let expected = vec![
+            ("SELECT", (1, 1), (1, 7)),
+            ("a", (1, 8), (1, 9)),
+            (",", (1, 9), (1, 10)),
+            ("'é'", (2, 3), (2, 6)),
+            ("FROM", (2, 7), (2, 11)),
+            ("t", (2, 12), (2, 13)),
+        ];
+        assert_eq!(expected, locations);
+    }
+
     fn compare(expected: Vec<Token>, actual: Vec<Token>) {
         //println!("------------------------------");
         //println!("tokens   = {:?}", actual);
@@ -795,5 +1248,4 @@ mod tests {
         //println!("------------------------------");
         assert_eq!(expected, actual);
     }
-
 }
@@ -14,9 +14,9 @@ pub struct TestedDialects {
 impl TestedDialects {
     /// Run the given function for all of `self.dialects`, assert that they
     /// return the same result, and return that result.
-    pub fn one_of_identical_results<F, T: Debug + PartialEq>(&self, f: F) -> T
+    pub fn one_of_identical_results<'a, F, T: Debug + PartialEq>(&'a self, f: F) -> T
     where
-        F: Fn(&dyn Dialect) -> T,
+        F: Fn(&'a dyn Dialect) -> T,
     {
         let parse_results = self.dialects.iter().map(|dialect| (dialect, f(&**dialect)));
         parse_results
@@ -34,14 +34,14 @@ impl TestedDialects {
             .1
     }
 
-    pub fn run_parser_method<F, T: Debug + PartialEq>(&self, sql: &str, f: F) -> T
+    pub fn run_parser_method<'a, F, T: Debug + PartialEq>(&'a self, sql: &str, f: F) -> T
     where
-        F: Fn(&mut Parser) -> T,
+        F: Fn(&mut Parser<'a>) -> T,
     {
         self.one_of_identical_results(|dialect| {
             let mut tokenizer = Tokenizer::new(dialect, sql);
-            let tokens = tokenizer.tokenize().unwrap();
-            f(&mut Parser::new(tokens))
+            let tokens = tokenizer.tokenize_with_span().unwrap();
+            f(&mut Parser::new_with_dialect(dialect, tokens))
         })
     }
 
@@ -55,7 +55,7 @@ impl TestedDialects {
     /// that converting AST back to string equals to `canonical` (unless an
     /// empty canonical string is provided).
     pub fn one_statement_parses_to(&self, sql: &str, canonical: &str) -> SQLStatement {
-        let mut statements = self.parse_sql_statements(&sql).unwrap();
+        let mut statements = self.parse_sql_statements(sql).unwrap();
         assert_eq!(statements.len(), 1);
 
         let only_statement = statements.pop().unwrap();
@@ -114,6 +114,11 @@ pub fn only<T>(v: &[T]) -> &T {
     v.first().unwrap()
 }
 
+/// The options of a column definition, without their names and spans
+pub fn column_options(column: &SQLColumnDef) -> Vec<ColumnOption> {
+    column.options.iter().map(|o| o.option.clone()).collect()
+}
+
 pub fn expr_from_projection(item: &SQLSelectItem) -> &ASTNode {
     match item {
         SQLSelectItem::UnnamedExpression(expr) => expr,
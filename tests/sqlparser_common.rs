@@ -8,16 +8,18 @@
 
 use matches::assert_matches;
 
+use sqlparser::dialect::GenericSqlDialect;
 use sqlparser::sqlast::*;
 use sqlparser::sqlparser::*;
-use sqlparser::test_utils::{all_dialects, expr_from_projection, only};
+use sqlparser::sqltokenizer::Token;
+use sqlparser::test_utils::{all_dialects, column_options, expr_from_projection, only};
 
 #[test]
 fn parse_insert_values() {
     let row = vec![
-        ASTNode::SQLValue(Value::Long(1)),
-        ASTNode::SQLValue(Value::Long(2)),
-        ASTNode::SQLValue(Value::Long(3)),
+        ASTNode::SQLValue(Value::Long(1), Span::empty()),
+        ASTNode::SQLValue(Value::Long(2), Span::empty()),
+        ASTNode::SQLValue(Value::Long(3), Span::empty()),
     ];
     let rows1 = vec![row.clone()];
     let rows2 = vec![row.clone(), row];
@@ -58,7 +60,7 @@ fn parse_insert_values() {
                 assert_eq!(table_name.to_string(), expected_table_name);
                 assert_eq!(columns, expected_columns);
                 match &source.body {
-                    SQLSetExpr::Values(SQLValues(values)) => {
+                    SQLSetExpr::Values(SQLValues(values, _)) => {
                         assert_eq!(values.as_slice(), expected_rows)
                     }
                     _ => unreachable!(),
@@ -75,10 +77,7 @@ fn parse_insert_values() {
 fn parse_insert_invalid() {
     let sql = "INSERT public.customer (id, name, active) VALUES (1, 2, 3)";
     let res = parse_sql_statements(sql);
-    assert_eq!(
-        ParserError::ParserError("Expected INTO, found: public".to_string()),
-        res.unwrap_err()
-    );
+    assert_eq!("Expected INTO, found: public", res.unwrap_err().message);
 }
 
 #[test]
@@ -96,20 +95,26 @@ fn parse_update() {
                 assignments,
                 vec![
                     SQLAssignment {
-                        id: "a".into(),
-                        value: ASTNode::SQLValue(Value::Long(1)),
+                        id: vec!["a".into()],
+                        value: ASTNode::SQLValue(Value::Long(1), Span::empty()),
+                        span: Span::empty(),
                     },
                     SQLAssignment {
-                        id: "b".into(),
-                        value: ASTNode::SQLValue(Value::Long(2)),
+                        id: vec!["b".into()],
+                        value: ASTNode::SQLValue(Value::Long(2), Span::empty()),
+                        span: Span::empty(),
                     },
                     SQLAssignment {
-                        id: "c".into(),
-                        value: ASTNode::SQLValue(Value::Long(3)),
+                        id: vec!["c".into()],
+                        value: ASTNode::SQLValue(Value::Long(3), Span::empty()),
+                        span: Span::empty(),
                     },
                 ]
             );
-            assert_eq!(selection.unwrap(), ASTNode::SQLIdentifier("d".into()));
+            assert_eq!(
+                selection.unwrap(),
+                ASTNode::SQLIdentifier("d".into(), Span::empty())
+            );
         }
         _ => unreachable!(),
     }
@@ -118,19 +123,247 @@ fn parse_update() {
 
     let sql = "UPDATE t WHERE 1";
     let res = parse_sql_statements(sql);
-    assert_eq!(
-        ParserError::ParserError("Expected SET, found: WHERE".to_string()),
-        res.unwrap_err()
-    );
+    assert_eq!("Expected SET, found: WHERE", res.unwrap_err().message);
 
     let sql = "UPDATE t SET a = 1 extrabadstuff";
     let res = parse_sql_statements(sql);
     assert_eq!(
-        ParserError::ParserError("Expected end of statement, found: extrabadstuff".to_string()),
-        res.unwrap_err()
+        "Expected end of statement, found: extrabadstuff",
+        res.unwrap_err().message
     );
 }
 
+#[test]
+fn parse_update_with_from() {
+    let sql = "UPDATE t AS a SET a.x = b.x, y = 1 FROM u AS b JOIN v ON b.id = v.id, w \
+               WHERE a.id = b.id";
+    match verified_stmt(sql) {
+        SQLStatement::SQLUpdate {
+            table_name,
+            alias,
+            assignments,
+            from,
+            joins,
+            selection,
+            ..
+        } => {
+            assert_eq!("t", table_name.to_string());
+            assert_eq!(Some("a".to_string()), alias);
+            assert_eq!(
+                vec![
+                    vec!["a".to_string(), "x".to_string()],
+                    vec!["y".to_string()]
+                ],
+                assignments.into_iter().map(|a| a.id).collect::<Vec<_>>()
+            );
+            assert_eq!("u AS b", from.unwrap().to_string());
+            assert_eq!(2, joins.len());
+            assert_eq!(JoinOperator::Implicit, joins[1].join_operator);
+            assert_eq!(verified_expr("a.id = b.id"), selection.unwrap());
+        }
+        _ => unreachable!(),
+    }
+
+    one_statement_parses_to(
+        "UPDATE t a SET x = 1 FROM u b WHERE a.id = b.id",
+        "UPDATE t AS a SET x = 1 FROM u AS b WHERE a.id = b.id",
+    );
+    let res = parse_sql_statements("UPDATE t SET a. = 1");
+    assert_eq!("Expected identifier, found: =", res.unwrap_err().message);
+}
+
+#[test]
+fn parse_merge() {
+    let sql = "MERGE INTO t AS tgt USING s ON tgt.id = s.id \
+               WHEN MATCHED AND s.deleted THEN DELETE \
+               WHEN MATCHED THEN UPDATE SET a = s.a \
+               WHEN NOT MATCHED THEN INSERT (id, a) VALUES (s.id, s.a)";
+    match verified_stmt(sql) {
+        SQLStatement::SQLMerge {
+            into,
+            table,
+            source,
+            on,
+            clauses,
+            ..
+        } => {
+            assert!(into);
+            assert_eq!("t AS tgt", table.to_string());
+            assert_eq!("s", source.to_string());
+            assert_eq!(verified_expr("tgt.id = s.id"), *on);
+            assert_eq!(
+                clauses,
+                vec![
+                    MergeClause {
+                        kind: MergeClauseKind::Matched,
+                        predicate: Some(verified_expr("s.deleted")),
+                        action: MergeAction::Delete,
+                        span: Span::empty(),
+                    },
+                    MergeClause {
+                        kind: MergeClauseKind::Matched,
+                        predicate: None,
+                        action: MergeAction::Update {
+                            assignments: vec![SQLAssignment {
+                                id: vec!["a".into()],
+                                value: verified_expr("s.a"),
+                                span: Span::empty(),
+                            }],
+                        },
+                        span: Span::empty(),
+                    },
+                    MergeClause {
+                        kind: MergeClauseKind::NotMatched,
+                        predicate: None,
+                        action: MergeAction::Insert {
+                            columns: vec!["id".into(), "a".into()],
+                            values: vec![verified_expr("s.id"), verified_expr("s.a")],
+                        },
+                        span: Span::empty(),
+                    },
+                ]
+            );
+        }
+        _ => unreachable!(),
+    }
+
+    match verified_stmt(
+        "MERGE t USING (SELECT * FROM u) AS s ON t.id = s.id WHEN NOT MATCHED THEN INSERT VALUES (s.id)",
+    ) {
+        SQLStatement::SQLMerge { into, source, .. } => {
+            assert!(!into);
+            match source {
+                TableFactor::Derived { alias, .. } => assert_eq!("s", alias.unwrap().name),
+                _ => unreachable!(),
+            }
+        }
+        _ => unreachable!(),
+    }
+
+    let res = parse_sql_statements("MERGE INTO t USING s ON t.id = s.id");
+    assert_eq!("Expected WHEN, found: EOF", res.unwrap_err().message);
+    let res = parse_sql_statements(
+        "MERGE INTO t USING s ON t.id = s.id WHEN NOT MATCHED THEN UPDATE SET a = 1",
+    );
+    assert_eq!("Expected INSERT, found: UPDATE", res.unwrap_err().message);
+    let res = parse_sql_statements(
+        "MERGE INTO t USING s ON t.id = s.id WHEN MATCHED THEN INSERT VALUES (1)",
+    );
+    assert_eq!(
+        "Expected one of UPDATE or DELETE, found: INSERT",
+        res.unwrap_err().message
+    );
+}
+
+#[test]
+fn parse_start_transaction() {
+    match verified_stmt("START TRANSACTION READ ONLY, ISOLATION LEVEL SERIALIZABLE") {
+        SQLStatement::SQLStartTransaction { begin, modes, .. } => {
+            assert!(!begin);
+            assert_eq!(
+                vec![
+                    TransactionMode::AccessMode(TransactionAccessMode::ReadOnly),
+                    TransactionMode::IsolationLevel(TransactionIsolationLevel::Serializable),
+                ],
+                modes
+            );
+        }
+        _ => unreachable!(),
+    }
+    verified_stmt("START TRANSACTION");
+    verified_stmt("BEGIN");
+    verified_stmt("BEGIN ISOLATION LEVEL READ UNCOMMITTED, READ WRITE");
+    one_statement_parses_to("BEGIN TRANSACTION", "BEGIN");
+    one_statement_parses_to("BEGIN WORK READ ONLY", "BEGIN READ ONLY");
+    // the commas between modes are optional
+    one_statement_parses_to(
+        "START TRANSACTION ISOLATION LEVEL REPEATABLE READ READ ONLY",
+        "START TRANSACTION ISOLATION LEVEL REPEATABLE READ, READ ONLY",
+    );
+
+    let res = parse_sql_statements("START TRANSACTION ISOLATION LEVEL READ");
+    assert_eq!(
+        "Expected isolation level, found: READ",
+        res.unwrap_err().message
+    );
+    let res = parse_sql_statements("START TRANSACTION READ ONLY,");
+    assert_eq!(
+        "Expected transaction mode, found: EOF",
+        res.unwrap_err().message
+    );
+    let res = parse_sql_statements("START READ ONLY");
+    assert_eq!(
+        "Expected TRANSACTION, found: READ",
+        res.unwrap_err().message
+    );
+}
+
+#[test]
+fn parse_set_transaction() {
+    match verified_stmt("SET TRANSACTION ISOLATION LEVEL READ COMMITTED, READ WRITE") {
+        SQLStatement::SQLSetTransaction { modes, .. } => assert_eq!(
+            vec![
+                TransactionMode::IsolationLevel(TransactionIsolationLevel::ReadCommitted),
+                TransactionMode::AccessMode(TransactionAccessMode::ReadWrite),
+            ],
+            modes
+        ),
+        _ => unreachable!(),
+    }
+    let res = parse_sql_statements("SET TRANSACTION");
+    assert_eq!(
+        "Expected transaction mode, found: EOF",
+        res.unwrap_err().message
+    );
+}
+
+#[test]
+fn parse_commit_and_rollback() {
+    verified_stmt("COMMIT");
+    verified_stmt("ROLLBACK");
+    one_statement_parses_to("COMMIT WORK AND NO CHAIN", "COMMIT");
+    match verified_stmt("COMMIT AND CHAIN") {
+        SQLStatement::SQLCommit { chain, .. } => assert!(chain),
+        _ => unreachable!(),
+    }
+    match one_statement_parses_to("ROLLBACK TRANSACTION AND CHAIN", "ROLLBACK AND CHAIN") {
+        SQLStatement::SQLRollback {
+            chain, savepoint, ..
+        } => {
+            assert!(chain);
+            assert_eq!(None, savepoint);
+        }
+        _ => unreachable!(),
+    }
+    match one_statement_parses_to("ROLLBACK WORK TO sp1", "ROLLBACK TO SAVEPOINT sp1") {
+        SQLStatement::SQLRollback { savepoint, .. } => {
+            assert_eq!(Some("sp1".to_string()), savepoint)
+        }
+        _ => unreachable!(),
+    }
+
+    let res = parse_sql_statements("COMMIT AND");
+    assert_eq!("Expected CHAIN, found: EOF", res.unwrap_err().message);
+}
+
+#[test]
+fn parse_savepoints() {
+    match verified_stmt("SAVEPOINT sp1") {
+        SQLStatement::SQLSavepoint { name, .. } => assert_eq!("sp1", name),
+        _ => unreachable!(),
+    }
+    match one_statement_parses_to("RELEASE sp1", "RELEASE SAVEPOINT sp1") {
+        SQLStatement::SQLReleaseSavepoint { name, .. } => assert_eq!("sp1", name),
+        _ => unreachable!(),
+    }
+    verified_stmt("RELEASE SAVEPOINT \"my savepoint\"");
+
+    let statements =
+        parse_sql_statements("BEGIN; SAVEPOINT a; INSERT INTO t VALUES (1); ROLLBACK TO a; COMMIT")
+            .unwrap();
+    assert_eq!(5, statements.len());
+}
+
 #[test]
 fn parse_invalid_table_name() {
     let ast = all_dialects().run_parser_method("db.public..customer", Parser::parse_object_name);
@@ -170,9 +403,10 @@ fn parse_where_delete_statement() {
 
             assert_eq!(
                 SQLBinaryExpr {
-                    left: Box::new(SQLIdentifier("name".to_string())),
+                    left: Box::new(SQLIdentifier("name".to_string(), Span::empty())),
                     op: Eq,
-                    right: Box::new(SQLValue(Value::Long(5))),
+                    right: Box::new(SQLValue(Value::Long(5), Span::empty())),
+                    span: Span::empty(),
                 },
                 selection.unwrap(),
             );
@@ -185,29 +419,38 @@ fn parse_where_delete_statement() {
 fn parse_simple_select() {
     let sql = "SELECT id, fname, lname FROM customer WHERE id = 1 LIMIT 5";
     let select = verified_only_select(sql);
-    assert_eq!(false, select.distinct);
+    assert!(!select.distinct);
     assert_eq!(3, select.projection.len());
     let select = verified_query(sql);
-    assert_eq!(Some(ASTNode::SQLValue(Value::Long(5))), select.limit);
+    assert_eq!(
+        Some(ASTNode::SQLValue(Value::Long(5), Span::empty())),
+        select.limit
+    );
 }
 
 #[test]
 fn parse_select_with_limit_but_no_where() {
     let sql = "SELECT id, fname, lname FROM customer LIMIT 5";
     let select = verified_only_select(sql);
-    assert_eq!(false, select.distinct);
+    assert!(!select.distinct);
     assert_eq!(3, select.projection.len());
     let select = verified_query(sql);
-    assert_eq!(Some(ASTNode::SQLValue(Value::Long(5))), select.limit);
+    assert_eq!(
+        Some(ASTNode::SQLValue(Value::Long(5), Span::empty())),
+        select.limit
+    );
 }
 
 #[test]
 fn parse_select_distinct() {
     let sql = "SELECT DISTINCT name FROM customer";
     let select = verified_only_select(sql);
-    assert_eq!(true, select.distinct);
+    assert!(select.distinct);
     assert_eq!(
-        &SQLSelectItem::UnnamedExpression(ASTNode::SQLIdentifier("name".to_string())),
+        &SQLSelectItem::UnnamedExpression(ASTNode::SQLIdentifier(
+            "name".to_string(),
+            Span::empty()
+        )),
         only(&select.projection)
     );
 }
@@ -221,8 +464,8 @@ fn parse_select_all() {
 fn parse_select_all_distinct() {
     let result = parse_sql_statements("SELECT ALL DISTINCT name FROM customer");
     assert_eq!(
-        ParserError::ParserError("Cannot specify both ALL and DISTINCT in SELECT".to_string()),
-        result.unwrap_err(),
+        "Cannot specify both ALL and DISTINCT in SELECT",
+        result.unwrap_err().message
     );
 }
 
@@ -230,22 +473,25 @@ fn parse_select_all_distinct() {
 fn parse_select_wildcard() {
     let sql = "SELECT * FROM foo";
     let select = verified_only_select(sql);
-    assert_eq!(&SQLSelectItem::Wildcard, only(&select.projection));
+    assert_eq!(
+        &SQLSelectItem::Wildcard(Span::empty()),
+        only(&select.projection)
+    );
 
     let sql = "SELECT foo.* FROM foo";
     let select = verified_only_select(sql);
     assert_eq!(
-        &SQLSelectItem::QualifiedWildcard(SQLObjectName(vec!["foo".to_string()])),
+        &SQLSelectItem::QualifiedWildcard(SQLObjectName(vec!["foo".to_string()]), Span::empty()),
         only(&select.projection)
     );
 
     let sql = "SELECT myschema.mytable.* FROM myschema.mytable";
     let select = verified_only_select(sql);
     assert_eq!(
-        &SQLSelectItem::QualifiedWildcard(SQLObjectName(vec![
-            "myschema".to_string(),
-            "mytable".to_string(),
-        ])),
+        &SQLSelectItem::QualifiedWildcard(
+            SQLObjectName(vec!["myschema".to_string(), "mytable".to_string(),]),
+            Span::empty()
+        ),
         only(&select.projection)
     );
 }
@@ -266,31 +512,35 @@ fn parse_column_aliases() {
             ref op, ref right, ..
         },
         ref alias,
+        ..
     } = only(&select.projection)
     {
         assert_eq!(&SQLOperator::Plus, op);
-        assert_eq!(&ASTNode::SQLValue(Value::Long(1)), right.as_ref());
+        assert_eq!(
+            &ASTNode::SQLValue(Value::Long(1), Span::empty()),
+            right.as_ref()
+        );
         assert_eq!("newname", alias);
     } else {
         panic!("Expected ExpressionWithAlias")
     }
 
     // alias without AS is parsed correctly:
-    one_statement_parses_to("SELECT a.col + 1 newname FROM foo AS a", &sql);
+    one_statement_parses_to("SELECT a.col + 1 newname FROM foo AS a", sql);
 }
 
 #[test]
 fn test_eof_after_as() {
     let res = parse_sql_statements("SELECT foo AS");
     assert_eq!(
-        ParserError::ParserError("Expected an identifier after AS, found: EOF".to_string()),
-        res.unwrap_err()
+        "Expected an identifier after AS, found: EOF",
+        res.unwrap_err().message
     );
 
     let res = parse_sql_statements("SELECT 1 FROM foo AS");
     assert_eq!(
-        ParserError::ParserError("Expected an identifier after AS, found: EOF".to_string()),
-        res.unwrap_err()
+        "Expected an identifier after AS, found: EOF",
+        res.unwrap_err().message
     );
 }
 
@@ -301,9 +551,10 @@ fn parse_select_count_wildcard() {
     assert_eq!(
         &ASTNode::SQLFunction(SQLFunction {
             name: SQLObjectName(vec!["COUNT".to_string()]),
-            args: vec![ASTNode::SQLWildcard],
+            args: vec![ASTNode::SQLWildcard(Span::empty())],
             over: None,
             distinct: false,
+            span: Span::empty(),
         }),
         expr_from_projection(only(&select.projection))
     );
@@ -318,10 +569,12 @@ fn parse_select_count_distinct() {
             name: SQLObjectName(vec!["COUNT".to_string()]),
             args: vec![ASTNode::SQLUnary {
                 operator: SQLOperator::Plus,
-                expr: Box::new(ASTNode::SQLIdentifier("x".to_string()))
+                expr: Box::new(ASTNode::SQLIdentifier("x".to_string(), Span::empty())),
+                span: Span::empty()
             }],
             over: None,
             distinct: true,
+            span: Span::empty(),
         }),
         expr_from_projection(only(&select.projection))
     );
@@ -334,10 +587,8 @@ fn parse_select_count_distinct() {
     let sql = "SELECT COUNT(ALL DISTINCT + x) FROM customer";
     let res = parse_sql_statements(sql);
     assert_eq!(
-        ParserError::ParserError(
-            "Cannot specify both ALL and DISTINCT in function: COUNT".to_string()
-        ),
-        res.unwrap_err()
+        "Cannot specify both ALL and DISTINCT in function: COUNT",
+        res.unwrap_err().message
     );
 }
 
@@ -352,8 +603,8 @@ fn parse_not() {
 fn parse_invalid_infix_not() {
     let res = parse_sql_statements("SELECT c FROM t WHERE c NOT (");
     assert_eq!(
-        ParserError::ParserError("Expected IN or BETWEEN after NOT, found: (".to_string()),
-        res.unwrap_err(),
+        "Expected IN or BETWEEN after NOT, found: (",
+        res.unwrap_err().message
     );
 }
 
@@ -390,11 +641,13 @@ fn parse_escaped_single_quote_string_predicate() {
     let ast = verified_only_select(sql);
     assert_eq!(
         Some(SQLBinaryExpr {
-            left: Box::new(SQLIdentifier("salary".to_string())),
+            left: Box::new(SQLIdentifier("salary".to_string(), Span::empty())),
             op: NotEq,
-            right: Box::new(SQLValue(Value::SingleQuotedString(
-                "Jim's salary".to_string()
-            )))
+            right: Box::new(SQLValue(
+                Value::SingleQuotedString("Jim's salary".to_string()),
+                Span::empty()
+            )),
+            span: Span::empty()
         }),
         ast.selection,
     );
@@ -407,13 +660,15 @@ fn parse_compound_expr_1() {
     let sql = "a + b * c";
     assert_eq!(
         SQLBinaryExpr {
-            left: Box::new(SQLIdentifier("a".to_string())),
+            left: Box::new(SQLIdentifier("a".to_string(), Span::empty())),
             op: Plus,
             right: Box::new(SQLBinaryExpr {
-                left: Box::new(SQLIdentifier("b".to_string())),
+                left: Box::new(SQLIdentifier("b".to_string(), Span::empty())),
                 op: Multiply,
-                right: Box::new(SQLIdentifier("c".to_string()))
-            })
+                right: Box::new(SQLIdentifier("c".to_string(), Span::empty())),
+                span: Span::empty()
+            }),
+            span: Span::empty()
         },
         verified_expr(sql)
     );
@@ -427,12 +682,14 @@ fn parse_compound_expr_2() {
     assert_eq!(
         SQLBinaryExpr {
             left: Box::new(SQLBinaryExpr {
-                left: Box::new(SQLIdentifier("a".to_string())),
+                left: Box::new(SQLIdentifier("a".to_string(), Span::empty())),
                 op: Multiply,
-                right: Box::new(SQLIdentifier("b".to_string()))
+                right: Box::new(SQLIdentifier("b".to_string(), Span::empty())),
+                span: Span::empty()
             }),
             op: Plus,
-            right: Box::new(SQLIdentifier("c".to_string()))
+            right: Box::new(SQLIdentifier("c".to_string(), Span::empty())),
+            span: Span::empty()
         },
         verified_expr(sql)
     );
@@ -447,13 +704,16 @@ fn parse_unary_math() {
         SQLBinaryExpr {
             left: Box::new(SQLUnary {
                 operator: Minus,
-                expr: Box::new(SQLIdentifier("a".to_string())),
+                expr: Box::new(SQLIdentifier("a".to_string(), Span::empty())),
+                span: Span::empty(),
             }),
             op: Plus,
             right: Box::new(SQLUnary {
                 operator: Minus,
-                expr: Box::new(SQLIdentifier("b".to_string())),
+                expr: Box::new(SQLIdentifier("b".to_string(), Span::empty())),
+                span: Span::empty(),
             }),
+            span: Span::empty(),
         },
         verified_expr(sql)
     );
@@ -464,7 +724,10 @@ fn parse_is_null() {
     use self::ASTNode::*;
     let sql = "a IS NULL";
     assert_eq!(
-        SQLIsNull(Box::new(SQLIdentifier("a".to_string()))),
+        SQLIsNull(
+            Box::new(SQLIdentifier("a".to_string(), Span::empty())),
+            Span::empty()
+        ),
         verified_expr(sql)
     );
 }
@@ -474,7 +737,10 @@ fn parse_is_not_null() {
     use self::ASTNode::*;
     let sql = "a IS NOT NULL";
     assert_eq!(
-        SQLIsNotNull(Box::new(SQLIdentifier("a".to_string()))),
+        SQLIsNotNull(
+            Box::new(SQLIdentifier("a".to_string(), Span::empty())),
+            Span::empty()
+        ),
         verified_expr(sql)
     );
 }
@@ -484,17 +750,23 @@ fn parse_not_precedence() {
     use self::ASTNode::*;
     // NOT has higher precedence than OR/AND, so the following must parse as (NOT true) OR true
     let sql = "NOT true OR true";
-    assert_matches!(verified_expr(sql), SQLBinaryExpr {
-        op: SQLOperator::Or,
-        ..
-    });
+    assert_matches!(
+        verified_expr(sql),
+        SQLBinaryExpr {
+            op: SQLOperator::Or,
+            ..
+        }
+    );
 
     // But NOT has lower precedence than comparison operators, so the following parses as NOT (a IS NULL)
     let sql = "NOT a IS NULL";
-    assert_matches!(verified_expr(sql), SQLUnary {
-        operator: SQLOperator::Not,
-        ..
-    });
+    assert_matches!(
+        verified_expr(sql),
+        SQLUnary {
+            operator: SQLOperator::Not,
+            ..
+        }
+    );
 
     // NOT has lower precedence than BETWEEN, so the following parses as NOT (1 NOT BETWEEN 1 AND 2)
     let sql = "NOT 1 NOT BETWEEN 1 AND 2";
@@ -503,11 +775,13 @@ fn parse_not_precedence() {
         SQLUnary {
             operator: SQLOperator::Not,
             expr: Box::new(SQLBetween {
-                expr: Box::new(SQLValue(Value::Long(1))),
-                low: Box::new(SQLValue(Value::Long(1))),
-                high: Box::new(SQLValue(Value::Long(2))),
+                expr: Box::new(SQLValue(Value::Long(1), Span::empty())),
+                low: Box::new(SQLValue(Value::Long(1), Span::empty())),
+                high: Box::new(SQLValue(Value::Long(2), Span::empty())),
                 negated: true,
+                span: Span::empty(),
             }),
+            span: Span::empty(),
         },
     );
 
@@ -518,10 +792,18 @@ fn parse_not_precedence() {
         SQLUnary {
             operator: SQLOperator::Not,
             expr: Box::new(SQLBinaryExpr {
-                left: Box::new(SQLValue(Value::SingleQuotedString("a".into()))),
+                left: Box::new(SQLValue(
+                    Value::SingleQuotedString("a".into()),
+                    Span::empty()
+                )),
                 op: SQLOperator::NotLike,
-                right: Box::new(SQLValue(Value::SingleQuotedString("b".into()))),
+                right: Box::new(SQLValue(
+                    Value::SingleQuotedString("b".into()),
+                    Span::empty()
+                )),
+                span: Span::empty(),
             }),
+            span: Span::empty(),
         },
     );
 
@@ -532,10 +814,15 @@ fn parse_not_precedence() {
         SQLUnary {
             operator: SQLOperator::Not,
             expr: Box::new(SQLInList {
-                expr: Box::new(SQLIdentifier("a".into())),
-                list: vec![SQLValue(Value::SingleQuotedString("a".into()))],
+                expr: Box::new(SQLIdentifier("a".into(), Span::empty())),
+                list: vec![SQLValue(
+                    Value::SingleQuotedString("a".into()),
+                    Span::empty()
+                )],
                 negated: true,
+                span: Span::empty(),
             }),
+            span: Span::empty(),
         },
     );
 }
@@ -550,15 +837,17 @@ fn parse_like() {
         let select = verified_only_select(sql);
         assert_eq!(
             ASTNode::SQLBinaryExpr {
-                left: Box::new(ASTNode::SQLIdentifier("name".to_string())),
+                left: Box::new(ASTNode::SQLIdentifier("name".to_string(), Span::empty())),
                 op: if negated {
                     SQLOperator::NotLike
                 } else {
                     SQLOperator::Like
                 },
-                right: Box::new(ASTNode::SQLValue(Value::SingleQuotedString(
-                    "%a".to_string()
-                ))),
+                right: Box::new(ASTNode::SQLValue(
+                    Value::SingleQuotedString("%a".to_string()),
+                    Span::empty()
+                )),
+                span: Span::empty(),
             },
             select.selection.unwrap()
         );
@@ -571,17 +860,22 @@ fn parse_like() {
         );
         let select = verified_only_select(sql);
         assert_eq!(
-            ASTNode::SQLIsNull(Box::new(ASTNode::SQLBinaryExpr {
-                left: Box::new(ASTNode::SQLIdentifier("name".to_string())),
-                op: if negated {
-                    SQLOperator::NotLike
-                } else {
-                    SQLOperator::Like
-                },
-                right: Box::new(ASTNode::SQLValue(Value::SingleQuotedString(
-                    "%a".to_string()
-                ))),
-            })),
+            ASTNode::SQLIsNull(
+                Box::new(ASTNode::SQLBinaryExpr {
+                    left: Box::new(ASTNode::SQLIdentifier("name".to_string(), Span::empty())),
+                    op: if negated {
+                        SQLOperator::NotLike
+                    } else {
+                        SQLOperator::Like
+                    },
+                    right: Box::new(ASTNode::SQLValue(
+                        Value::SingleQuotedString("%a".to_string()),
+                        Span::empty()
+                    )),
+                    span: Span::empty(),
+                }),
+                Span::empty()
+            ),
             select.selection.unwrap()
         );
     }
@@ -599,12 +893,13 @@ fn parse_in_list() {
         let select = verified_only_select(sql);
         assert_eq!(
             ASTNode::SQLInList {
-                expr: Box::new(ASTNode::SQLIdentifier("segment".to_string())),
+                expr: Box::new(ASTNode::SQLIdentifier("segment".to_string(), Span::empty())),
                 list: vec![
-                    ASTNode::SQLValue(Value::SingleQuotedString("HIGH".to_string())),
-                    ASTNode::SQLValue(Value::SingleQuotedString("MED".to_string())),
+                    ASTNode::SQLValue(Value::SingleQuotedString("HIGH".to_string()), Span::empty()),
+                    ASTNode::SQLValue(Value::SingleQuotedString("MED".to_string()), Span::empty()),
                 ],
                 negated,
+                span: Span::empty(),
             },
             select.selection.unwrap()
         );
@@ -619,9 +914,10 @@ fn parse_in_subquery() {
     let select = verified_only_select(sql);
     assert_eq!(
         ASTNode::SQLInSubquery {
-            expr: Box::new(ASTNode::SQLIdentifier("segment".to_string())),
+            expr: Box::new(ASTNode::SQLIdentifier("segment".to_string(), Span::empty())),
             subquery: Box::new(verified_query("SELECT segm FROM bar")),
             negated: false,
+            span: Span::empty(),
         },
         select.selection.unwrap()
     );
@@ -637,10 +933,11 @@ fn parse_between() {
         let select = verified_only_select(sql);
         assert_eq!(
             ASTNode::SQLBetween {
-                expr: Box::new(ASTNode::SQLIdentifier("age".to_string())),
-                low: Box::new(ASTNode::SQLValue(Value::Long(25))),
-                high: Box::new(ASTNode::SQLValue(Value::Long(32))),
+                expr: Box::new(ASTNode::SQLIdentifier("age".to_string(), Span::empty())),
+                low: Box::new(ASTNode::SQLValue(Value::Long(25), Span::empty())),
+                high: Box::new(ASTNode::SQLValue(Value::Long(32), Span::empty())),
                 negated,
+                span: Span::empty(),
             },
             select.selection.unwrap()
         );
@@ -656,20 +953,26 @@ fn parse_between_with_expr() {
     let sql = "SELECT * FROM t WHERE 1 BETWEEN 1 + 2 AND 3 + 4 IS NULL";
     let select = verified_only_select(sql);
     assert_eq!(
-        ASTNode::SQLIsNull(Box::new(ASTNode::SQLBetween {
-            expr: Box::new(ASTNode::SQLValue(Value::Long(1))),
-            low: Box::new(SQLBinaryExpr {
-                left: Box::new(ASTNode::SQLValue(Value::Long(1))),
-                op: Plus,
-                right: Box::new(ASTNode::SQLValue(Value::Long(2))),
-            }),
-            high: Box::new(SQLBinaryExpr {
-                left: Box::new(ASTNode::SQLValue(Value::Long(3))),
-                op: Plus,
-                right: Box::new(ASTNode::SQLValue(Value::Long(4))),
+        ASTNode::SQLIsNull(
+            Box::new(ASTNode::SQLBetween {
+                expr: Box::new(ASTNode::SQLValue(Value::Long(1), Span::empty())),
+                low: Box::new(SQLBinaryExpr {
+                    left: Box::new(ASTNode::SQLValue(Value::Long(1), Span::empty())),
+                    op: Plus,
+                    right: Box::new(ASTNode::SQLValue(Value::Long(2), Span::empty())),
+                    span: Span::empty(),
+                }),
+                high: Box::new(SQLBinaryExpr {
+                    left: Box::new(ASTNode::SQLValue(Value::Long(3), Span::empty())),
+                    op: Plus,
+                    right: Box::new(ASTNode::SQLValue(Value::Long(4), Span::empty())),
+                    span: Span::empty(),
+                }),
+                negated: false,
+                span: Span::empty(),
             }),
-            negated: false,
-        })),
+            Span::empty()
+        ),
         select.selection.unwrap()
     );
 
@@ -678,21 +981,25 @@ fn parse_between_with_expr() {
     assert_eq!(
         ASTNode::SQLBinaryExpr {
             left: Box::new(ASTNode::SQLBinaryExpr {
-                left: Box::new(ASTNode::SQLValue(Value::Long(1))),
+                left: Box::new(ASTNode::SQLValue(Value::Long(1), Span::empty())),
                 op: SQLOperator::Eq,
-                right: Box::new(ASTNode::SQLValue(Value::Long(1))),
+                right: Box::new(ASTNode::SQLValue(Value::Long(1), Span::empty())),
+                span: Span::empty(),
             }),
             op: SQLOperator::And,
             right: Box::new(ASTNode::SQLBetween {
                 expr: Box::new(ASTNode::SQLBinaryExpr {
-                    left: Box::new(ASTNode::SQLValue(Value::Long(1))),
+                    left: Box::new(ASTNode::SQLValue(Value::Long(1), Span::empty())),
                     op: SQLOperator::Plus,
-                    right: Box::new(ASTNode::SQLIdentifier("x".to_string())),
+                    right: Box::new(ASTNode::SQLIdentifier("x".to_string(), Span::empty())),
+                    span: Span::empty(),
                 }),
-                low: Box::new(ASTNode::SQLValue(Value::Long(1))),
-                high: Box::new(ASTNode::SQLValue(Value::Long(2))),
+                low: Box::new(ASTNode::SQLValue(Value::Long(1), Span::empty())),
+                high: Box::new(ASTNode::SQLValue(Value::Long(2), Span::empty())),
                 negated: false,
+                span: Span::empty(),
             }),
+            span: Span::empty(),
         },
         select.selection.unwrap(),
     )
@@ -705,16 +1012,22 @@ fn parse_select_order_by() {
         assert_eq!(
             vec![
                 SQLOrderByExpr {
-                    expr: ASTNode::SQLIdentifier("lname".to_string()),
+                    expr: ASTNode::SQLIdentifier("lname".to_string(), Span::empty()),
                     asc: Some(true),
+                    nulls_first: None,
+                    span: Span::empty(),
                 },
                 SQLOrderByExpr {
-                    expr: ASTNode::SQLIdentifier("fname".to_string()),
+                    expr: ASTNode::SQLIdentifier("fname".to_string(), Span::empty()),
                     asc: Some(false),
+                    nulls_first: None,
+                    span: Span::empty(),
                 },
                 SQLOrderByExpr {
-                    expr: ASTNode::SQLIdentifier("id".to_string()),
+                    expr: ASTNode::SQLIdentifier("id".to_string(), Span::empty()),
                     asc: None,
+                    nulls_first: None,
+                    span: Span::empty(),
                 },
             ],
             select.order_by
@@ -734,17 +1047,24 @@ fn parse_select_order_by_limit() {
     assert_eq!(
         vec![
             SQLOrderByExpr {
-                expr: ASTNode::SQLIdentifier("lname".to_string()),
+                expr: ASTNode::SQLIdentifier("lname".to_string(), Span::empty()),
                 asc: Some(true),
+                nulls_first: None,
+                span: Span::empty(),
             },
             SQLOrderByExpr {
-                expr: ASTNode::SQLIdentifier("fname".to_string()),
+                expr: ASTNode::SQLIdentifier("fname".to_string(), Span::empty()),
                 asc: Some(false),
+                nulls_first: None,
+                span: Span::empty(),
             },
         ],
         select.order_by
     );
-    assert_eq!(Some(ASTNode::SQLValue(Value::Long(2))), select.limit);
+    assert_eq!(
+        Some(ASTNode::SQLValue(Value::Long(2), Span::empty())),
+        select.limit
+    );
 }
 
 #[test]
@@ -753,8 +1073,8 @@ fn parse_select_group_by() {
     let select = verified_only_select(sql);
     assert_eq!(
         vec![
-            ASTNode::SQLIdentifier("lname".to_string()),
-            ASTNode::SQLIdentifier("fname".to_string()),
+            ASTNode::SQLIdentifier("lname".to_string(), Span::empty()),
+            ASTNode::SQLIdentifier("fname".to_string(), Span::empty()),
         ],
         select.group_by
     );
@@ -774,8 +1094,9 @@ fn parse_cast() {
     let select = verified_only_select(sql);
     assert_eq!(
         &ASTNode::SQLCast {
-            expr: Box::new(ASTNode::SQLIdentifier("id".to_string())),
-            data_type: SQLType::BigInt
+            expr: Box::new(ASTNode::SQLIdentifier("id".to_string(), Span::empty())),
+            data_type: SQLType::BigInt,
+            span: Span::empty()
         },
         expr_from_projection(only(&select.projection))
     );
@@ -804,7 +1125,8 @@ fn parse_extract() {
     assert_eq!(
         &ASTNode::SQLExtract {
             field: SQLDateTimeField::Year,
-            expr: Box::new(ASTNode::SQLIdentifier("d".to_string())),
+            expr: Box::new(ASTNode::SQLIdentifier("d".to_string(), Span::empty())),
+            span: Span::empty(),
         },
         expr_from_projection(only(&select.projection)),
     );
@@ -819,10 +1141,8 @@ fn parse_extract() {
 
     let res = parse_sql_statements("SELECT EXTRACT(MILLISECOND FROM d)");
     assert_eq!(
-        ParserError::ParserError(
-            "Expected Date/time field inside of EXTRACT function, found: MILLISECOND".to_string()
-        ),
-        res.unwrap_err()
+        "Expected Date/time field inside of EXTRACT function, found: MILLISECOND",
+        res.unwrap_err().message
     );
 }
 
@@ -836,8 +1156,8 @@ fn parse_create_table() {
         sql,
         "CREATE TABLE uk_cities (\
          name character varying(100) NOT NULL, \
-         lat double, \
-         lng double)",
+         lat double NULL, \
+         lng double NULL)",
     );
     match ast {
         SQLStatement::SQLCreateTable {
@@ -848,6 +1168,7 @@ fn parse_create_table() {
             external: false,
             file_format: None,
             location: None,
+            ..
         } => {
             assert_eq!("uk_cities", name.to_string());
             assert_eq!(3, columns.len());
@@ -856,17 +1177,17 @@ fn parse_create_table() {
             let c_name = &columns[0];
             assert_eq!("name", c_name.name);
             assert_eq!(SQLType::Varchar(Some(100)), c_name.data_type);
-            assert_eq!(false, c_name.allow_null);
+            assert_eq!(vec![ColumnOption::NotNull], column_options(c_name));
 
             let c_lat = &columns[1];
             assert_eq!("lat", c_lat.name);
             assert_eq!(SQLType::Double, c_lat.data_type);
-            assert_eq!(true, c_lat.allow_null);
+            assert_eq!(vec![ColumnOption::Null], column_options(c_lat));
 
             let c_lng = &columns[2];
             assert_eq!("lng", c_lng.name);
             assert_eq!(SQLType::Double, c_lng.data_type);
-            assert_eq!(true, c_lng.allow_null);
+            assert_eq!(vec![ColumnOption::Null], column_options(c_lng));
 
             assert_eq!(with_options, vec![]);
         }
@@ -883,11 +1204,13 @@ fn parse_create_table_with_options() {
                 vec![
                     SQLOption {
                         name: "foo".into(),
-                        value: Value::SingleQuotedString("bar".into())
+                        value: Value::SingleQuotedString("bar".into()),
+                        span: Span::empty()
                     },
                     SQLOption {
                         name: "a".into(),
-                        value: Value::Long(123)
+                        value: Value::Long(123),
+                        span: Span::empty()
                     },
                 ],
                 with_options
@@ -897,6 +1220,144 @@ fn parse_create_table_with_options() {
     }
 }
 
+#[test]
+fn parse_create_table_column_options() {
+    let sql = "CREATE TABLE t (\
+               id int NOT NULL PRIMARY KEY, \
+               parent_id int CONSTRAINT fk REFERENCES other(id) ON DELETE CASCADE, \
+               qty int DEFAULT 1 CHECK (qty > 0) UNIQUE, \
+               name text COLLATE \"de_DE\" NULL, \
+               serial bigint GENERATED ALWAYS AS IDENTITY)";
+    match verified_stmt(sql) {
+        SQLStatement::SQLCreateTable { columns, .. } => {
+            assert_eq!(
+                vec![
+                    ColumnOption::NotNull,
+                    ColumnOption::Unique { is_primary: true }
+                ],
+                column_options(&columns[0])
+            );
+            assert_eq!(
+                vec![ColumnOption::ForeignKey {
+                    foreign_table: SQLObjectName(vec!["other".to_string()]),
+                    referred_columns: vec!["id".to_string()],
+                    actions: vec![ForeignKeyAction::OnDelete(ReferentialAction::Cascade)],
+                }],
+                column_options(&columns[1])
+            );
+            assert_eq!(Some("fk".to_string()), columns[1].options[0].name);
+            assert_eq!(
+                vec![
+                    ColumnOption::Default(ASTNode::SQLValue(Value::Long(1), Span::empty())),
+                    ColumnOption::Check(verified_expr("qty > 0")),
+                    ColumnOption::Unique { is_primary: false },
+                ],
+                column_options(&columns[2])
+            );
+            assert_eq!(
+                vec![
+                    ColumnOption::Collate(SQLObjectName(vec!["\"de_DE\"".to_string()])),
+                    ColumnOption::Null,
+                ],
+                column_options(&columns[3])
+            );
+            assert_eq!(
+                vec![ColumnOption::GeneratedAsIdentity { always: true }],
+                column_options(&columns[4])
+            );
+        }
+        _ => unreachable!(),
+    }
+
+    // The actions round-trip in the order written
+    match verified_stmt(
+        "CREATE TABLE t (a int REFERENCES u ON UPDATE SET DEFAULT ON DELETE RESTRICT)",
+    ) {
+        SQLStatement::SQLCreateTable { columns, .. } => assert_eq!(
+            vec![ColumnOption::ForeignKey {
+                foreign_table: SQLObjectName(vec!["u".to_string()]),
+                referred_columns: vec![],
+                actions: vec![
+                    ForeignKeyAction::OnUpdate(ReferentialAction::SetDefault),
+                    ForeignKeyAction::OnDelete(ReferentialAction::Restrict),
+                ],
+            }],
+            column_options(&columns[0])
+        ),
+        _ => unreachable!(),
+    }
+    verified_stmt("CREATE TABLE t (a int REFERENCES u ON DELETE NO ACTION ON UPDATE SET NULL)");
+
+    let res = parse_sql_statements("CREATE TABLE t (a int CONSTRAINT c, b int)");
+    assert_eq!(
+        "Expected column option after CONSTRAINT <name>, found: ,",
+        res.unwrap_err().message
+    );
+    let res = parse_sql_statements("CREATE TABLE t (a int REFERENCES u ON DELETE SET)");
+    assert_eq!(
+        "Expected one of RESTRICT, CASCADE, SET NULL, NO ACTION or SET DEFAULT, found: SET",
+        res.unwrap_err().message
+    );
+}
+
+#[test]
+fn parse_create_table_if_not_exists_and_persistence() {
+    match verified_stmt("CREATE TABLE IF NOT EXISTS t (a int)") {
+        SQLStatement::SQLCreateTable {
+            persistence,
+            if_not_exists,
+            name,
+            ..
+        } => {
+            assert_eq!(None, persistence);
+            assert!(if_not_exists);
+            assert_eq!("t", name.to_string());
+        }
+        _ => unreachable!(),
+    }
+    for (sql, expected) in &[
+        ("CREATE TEMP TABLE t (a int)", TablePersistence::Temp),
+        (
+            "CREATE TEMPORARY TABLE IF NOT EXISTS t (a int)",
+            TablePersistence::Temporary,
+        ),
+        (
+            "CREATE UNLOGGED TABLE t (a int)",
+            TablePersistence::Unlogged,
+        ),
+    ] {
+        match verified_stmt(sql) {
+            SQLStatement::SQLCreateTable { persistence, .. } => {
+                assert_eq!(Some(*expected), persistence)
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    let res = parse_sql_statements("CREATE TEMPORARY VIEW v AS SELECT 1");
+    assert_eq!("Expected TABLE, found: VIEW", res.unwrap_err().message);
+}
+
+#[test]
+fn parse_create_table_as() {
+    let sql = "CREATE TABLE t AS SELECT a, b FROM u WHERE c > 1";
+    match verified_stmt(sql) {
+        SQLStatement::SQLCreateTable {
+            name,
+            columns,
+            query,
+            ..
+        } => {
+            assert_eq!("t", name.to_string());
+            assert!(columns.is_empty());
+            assert_eq!("SELECT a, b FROM u WHERE c > 1", query.unwrap().to_string());
+        }
+        _ => unreachable!(),
+    }
+    verified_stmt("CREATE TEMP TABLE IF NOT EXISTS t AS WITH x AS (SELECT 1) SELECT * FROM x");
+    verified_stmt("CREATE TABLE t (a int, b text) AS SELECT 1, 'x'");
+}
+
 #[test]
 fn parse_create_table_trailing_comma() {
     let sql = "CREATE TABLE foo (bar int,)";
@@ -914,8 +1375,8 @@ fn parse_create_external_table() {
         sql,
         "CREATE EXTERNAL TABLE uk_cities (\
          name character varying(100) NOT NULL, \
-         lat double, \
-         lng double) \
+         lat double NULL, \
+         lng double NULL) \
          STORED AS TEXTFILE LOCATION '/tmp/example.csv'",
     );
     match ast {
@@ -927,6 +1388,7 @@ fn parse_create_external_table() {
             external,
             file_format,
             location,
+            ..
         } => {
             assert_eq!("uk_cities", name.to_string());
             assert_eq!(3, columns.len());
@@ -935,17 +1397,17 @@ fn parse_create_external_table() {
             let c_name = &columns[0];
             assert_eq!("name", c_name.name);
             assert_eq!(SQLType::Varchar(Some(100)), c_name.data_type);
-            assert_eq!(false, c_name.allow_null);
+            assert_eq!(vec![ColumnOption::NotNull], column_options(c_name));
 
             let c_lat = &columns[1];
             assert_eq!("lat", c_lat.name);
             assert_eq!(SQLType::Double, c_lat.data_type);
-            assert_eq!(true, c_lat.allow_null);
+            assert_eq!(vec![ColumnOption::Null], column_options(c_lat));
 
             let c_lng = &columns[2];
             assert_eq!("lng", c_lng.name);
             assert_eq!(SQLType::Double, c_lng.data_type);
-            assert_eq!(true, c_lng.allow_null);
+            assert_eq!(vec![ColumnOption::Null], column_options(c_lng));
 
             assert!(external);
             assert_eq!(FileFormat::TEXTFILE, file_format.unwrap());
@@ -981,11 +1443,15 @@ fn parse_alter_table_constraints() {
     fn check_one(constraint_text: &str) {
         match verified_stmt(&format!("ALTER TABLE tab ADD {}", constraint_text)) {
             SQLStatement::SQLAlterTable {
-                name,
-                operation: AlterTableOperation::AddConstraint(constraint),
+                name, operations, ..
             } => {
                 assert_eq!("tab", name.to_string());
-                assert_eq!(constraint_text, constraint.to_string());
+                match only(&operations) {
+                    AlterTableOperation::AddConstraint(constraint) => {
+                        assert_eq!(constraint_text, constraint.to_string());
+                    }
+                    _ => unreachable!(),
+                }
             }
             _ => unreachable!(),
         }
@@ -993,22 +1459,131 @@ fn parse_alter_table_constraints() {
     }
 }
 
+#[test]
+fn parse_alter_table_columns() {
+    let sql = "ALTER TABLE tab ADD COLUMN foo int NOT NULL, DROP COLUMN IF EXISTS bar CASCADE, \
+               RENAME COLUMN baz TO qux, RENAME TO tab2";
+    match verified_stmt(sql) {
+        SQLStatement::SQLAlterTable { operations, .. } => {
+            assert_eq!(4, operations.len());
+            match &operations[0] {
+                AlterTableOperation::AddColumn {
+                    column_keyword,
+                    column_def,
+                    ..
+                } => {
+                    assert!(column_keyword);
+                    assert_eq!("foo", column_def.name);
+                    assert_eq!(SQLType::Int, column_def.data_type);
+                }
+                _ => unreachable!(),
+            }
+            assert_eq!(
+                AlterTableOperation::DropColumn {
+                    column_name: "bar".to_string(),
+                    if_exists: true,
+                    cascade: true,
+                    span: Span::empty(),
+                },
+                operations[1]
+            );
+            assert_eq!(
+                AlterTableOperation::RenameColumn {
+                    old_column_name: "baz".to_string(),
+                    new_column_name: "qux".to_string(),
+                    span: Span::empty(),
+                },
+                operations[2]
+            );
+            assert_eq!(
+                AlterTableOperation::RenameTable {
+                    table_name: SQLObjectName(vec!["tab2".to_string()]),
+                    span: Span::empty(),
+                },
+                operations[3]
+            );
+        }
+        _ => unreachable!(),
+    }
+
+    verified_stmt("ALTER TABLE tab ADD foo text");
+    verified_stmt("ALTER TABLE tab DROP CONSTRAINT foo_check");
+    one_statement_parses_to(
+        "ALTER TABLE tab DROP foo, RENAME foo TO bar",
+        "ALTER TABLE tab DROP COLUMN foo, RENAME COLUMN foo TO bar",
+    );
+
+    let res = parse_sql_statements("ALTER TABLE tab RENAME foo bar");
+    assert_eq!("Expected TO, found: bar", res.unwrap_err().message);
+    let res = parse_sql_statements("ALTER TABLE tab MODIFY foo int");
+    assert_eq!(
+        "Expected ADD, DROP, RENAME or ALTER after ALTER TABLE, found: MODIFY",
+        res.unwrap_err().message
+    );
+}
+
+#[test]
+fn parse_alter_table_alter_column() {
+    let check_one = |sql: &str, expected_op: AlterColumnOperation| match verified_stmt(sql) {
+        SQLStatement::SQLAlterTable { operations, .. } => match only(&operations) {
+            AlterTableOperation::AlterColumn {
+                column_name, op, ..
+            } => {
+                assert_eq!("foo", column_name);
+                assert_eq!(&expected_op, op);
+            }
+            _ => unreachable!(),
+        },
+        _ => unreachable!(),
+    };
+    check_one(
+        "ALTER TABLE tab ALTER COLUMN foo SET NOT NULL",
+        AlterColumnOperation::SetNotNull,
+    );
+    check_one(
+        "ALTER TABLE tab ALTER COLUMN foo DROP NOT NULL",
+        AlterColumnOperation::DropNotNull,
+    );
+    check_one(
+        "ALTER TABLE tab ALTER COLUMN foo SET DEFAULT 'x'",
+        AlterColumnOperation::SetDefault {
+            value: ASTNode::SQLValue(Value::SingleQuotedString("x".to_string()), Span::empty()),
+        },
+    );
+    check_one(
+        "ALTER TABLE tab ALTER COLUMN foo DROP DEFAULT",
+        AlterColumnOperation::DropDefault,
+    );
+    check_one(
+        "ALTER TABLE tab ALTER COLUMN foo SET DATA TYPE bigint",
+        AlterColumnOperation::SetDataType {
+            data_type: SQLType::BigInt,
+        },
+    );
+    one_statement_parses_to(
+        "ALTER TABLE tab ALTER foo TYPE text, ALTER foo SET NOT NULL",
+        "ALTER TABLE tab ALTER COLUMN foo SET DATA TYPE text, ALTER COLUMN foo SET NOT NULL",
+    );
+
+    let res = parse_sql_statements("ALTER TABLE tab ALTER COLUMN foo SET bar");
+    assert_eq!(
+        "Expected SET/DROP NOT NULL, SET DEFAULT, DROP DEFAULT or SET DATA TYPE after ALTER COLUMN, found: SET",
+        res.unwrap_err().message
+    );
+}
+
 #[test]
 fn parse_bad_constraint() {
     let res = parse_sql_statements("ALTER TABLE tab ADD");
     assert_eq!(
-        ParserError::ParserError(
-            "Expected a constraint in ALTER TABLE .. ADD, found: EOF".to_string()
-        ),
-        res.unwrap_err()
+        "Expected column name or constraint definition, found: EOF",
+        res.unwrap_err().message
     );
 
     let res = parse_sql_statements("CREATE TABLE tab (foo int,");
     assert_eq!(
-        ParserError::ParserError(
-            "Expected column name or constraint definition, found: EOF".to_string()
-        ),
-        res.unwrap_err()
+        "Expected column name or constraint definition, found: EOF",
+        res.unwrap_err().message
     );
 }
 
@@ -1019,9 +1594,10 @@ fn parse_scalar_function_in_projection() {
     assert_eq!(
         &ASTNode::SQLFunction(SQLFunction {
             name: SQLObjectName(vec!["sqrt".to_string()]),
-            args: vec![ASTNode::SQLIdentifier("id".to_string())],
+            args: vec![ASTNode::SQLIdentifier("id".to_string(), Span::empty())],
             over: None,
             distinct: false,
+            span: Span::empty(),
         }),
         expr_from_projection(only(&select.projection))
     );
@@ -1046,12 +1622,16 @@ fn parse_window_functions() {
             over: Some(SQLWindowSpec {
                 partition_by: vec![],
                 order_by: vec![SQLOrderByExpr {
-                    expr: ASTNode::SQLIdentifier("dt".to_string()),
-                    asc: Some(false)
+                    expr: ASTNode::SQLIdentifier("dt".to_string(), Span::empty()),
+                    asc: Some(false),
+                    nulls_first: None,
+                    span: Span::empty()
                 }],
                 window_frame: None,
+                span: Span::empty(),
             }),
             distinct: false,
+            span: Span::empty(),
         }),
         expr_from_projection(&select.projection[0])
     );
@@ -1070,15 +1650,21 @@ fn parse_literal_string() {
     let select = verified_only_select(sql);
     assert_eq!(3, select.projection.len());
     assert_eq!(
-        &ASTNode::SQLValue(Value::SingleQuotedString("one".to_string())),
+        &ASTNode::SQLValue(Value::SingleQuotedString("one".to_string()), Span::empty()),
         expr_from_projection(&select.projection[0])
     );
     assert_eq!(
-        &ASTNode::SQLValue(Value::NationalStringLiteral("national string".to_string())),
+        &ASTNode::SQLValue(
+            Value::NationalStringLiteral("national string".to_string()),
+            Span::empty()
+        ),
         expr_from_projection(&select.projection[1])
     );
     assert_eq!(
-        &ASTNode::SQLValue(Value::HexStringLiteral("deadBEEF".to_string())),
+        &ASTNode::SQLValue(
+            Value::HexStringLiteral("deadBEEF".to_string()),
+            Span::empty()
+        ),
         expr_from_projection(&select.projection[2])
     );
 
@@ -1090,7 +1676,7 @@ fn parse_literal_date() {
     let sql = "SELECT DATE '1999-01-01'";
     let select = verified_only_select(sql);
     assert_eq!(
-        &ASTNode::SQLValue(Value::Date("1999-01-01".into())),
+        &ASTNode::SQLValue(Value::Date("1999-01-01".into()), Span::empty()),
         expr_from_projection(only(&select.projection)),
     );
 }
@@ -1100,7 +1686,7 @@ fn parse_literal_time() {
     let sql = "SELECT TIME '01:23:34'";
     let select = verified_only_select(sql);
     assert_eq!(
-        &ASTNode::SQLValue(Value::Time("01:23:34".into())),
+        &ASTNode::SQLValue(Value::Time("01:23:34".into()), Span::empty()),
         expr_from_projection(only(&select.projection)),
     );
 }
@@ -1110,7 +1696,10 @@ fn parse_literal_timestamp() {
     let sql = "SELECT TIMESTAMP '1999-01-01 01:23:34'";
     let select = verified_only_select(sql);
     assert_eq!(
-        &ASTNode::SQLValue(Value::Timestamp("1999-01-01 01:23:34".into())),
+        &ASTNode::SQLValue(
+            Value::Timestamp("1999-01-01 01:23:34".into()),
+            Span::empty()
+        ),
         expr_from_projection(only(&select.projection)),
     );
 }
@@ -1131,7 +1720,7 @@ fn parse_simple_math_expr_minus() {
 fn parse_delimited_identifiers() {
     // check that quoted identifiers in any position remain quoted after serialization
     let select = verified_only_select(
-        r#"SELECT "alias"."bar baz", "myfun"(), "simple id" AS "column alias" FROM "a table" AS "alias""#
+        r#"SELECT "alias"."bar baz", "myfun"(), "simple id" AS "column alias" FROM "a table" AS "alias""#,
     );
     // check FROM
     match select.relation.unwrap() {
@@ -1140,6 +1729,7 @@ fn parse_delimited_identifiers() {
             alias,
             args,
             with_hints,
+            ..
         } => {
             assert_eq!(vec![r#""a table""#.to_string()], name.0);
             assert_eq!(r#""alias""#, alias.unwrap().name);
@@ -1151,7 +1741,10 @@ fn parse_delimited_identifiers() {
     // check SELECT
     assert_eq!(3, select.projection.len());
     assert_eq!(
-        &ASTNode::SQLCompoundIdentifier(vec![r#""alias""#.to_string(), r#""bar baz""#.to_string()]),
+        &ASTNode::SQLCompoundIdentifier(
+            vec![r#""alias""#.to_string(), r#""bar baz""#.to_string()],
+            Span::empty()
+        ),
         expr_from_projection(&select.projection[0]),
     );
     assert_eq!(
@@ -1160,12 +1753,16 @@ fn parse_delimited_identifiers() {
             args: vec![],
             over: None,
             distinct: false,
+            span: Span::empty(),
         }),
         expr_from_projection(&select.projection[1]),
     );
     match &select.projection[2] {
-        SQLSelectItem::ExpressionWithAlias { expr, alias } => {
-            assert_eq!(&ASTNode::SQLIdentifier(r#""simple id""#.to_string()), expr);
+        SQLSelectItem::ExpressionWithAlias { expr, alias, .. } => {
+            assert_eq!(
+                &ASTNode::SQLIdentifier(r#""simple id""#.to_string(), Span::empty()),
+                expr
+            );
             assert_eq!(r#""column alias""#, alias);
         }
         _ => panic!("Expected ExpressionWithAlias"),
@@ -1183,17 +1780,26 @@ fn parse_parens() {
     let sql = "(a + b) - (c + d)";
     assert_eq!(
         SQLBinaryExpr {
-            left: Box::new(SQLNested(Box::new(SQLBinaryExpr {
-                left: Box::new(SQLIdentifier("a".to_string())),
-                op: Plus,
-                right: Box::new(SQLIdentifier("b".to_string()))
-            }))),
+            left: Box::new(SQLNested(
+                Box::new(SQLBinaryExpr {
+                    left: Box::new(SQLIdentifier("a".to_string(), Span::empty())),
+                    op: Plus,
+                    right: Box::new(SQLIdentifier("b".to_string(), Span::empty())),
+                    span: Span::empty()
+                }),
+                Span::empty()
+            )),
             op: Minus,
-            right: Box::new(SQLNested(Box::new(SQLBinaryExpr {
-                left: Box::new(SQLIdentifier("c".to_string())),
-                op: Plus,
-                right: Box::new(SQLIdentifier("d".to_string()))
-            })))
+            right: Box::new(SQLNested(
+                Box::new(SQLBinaryExpr {
+                    left: Box::new(SQLIdentifier("c".to_string(), Span::empty())),
+                    op: Plus,
+                    right: Box::new(SQLIdentifier("d".to_string(), Span::empty())),
+                    span: Span::empty()
+                }),
+                Span::empty()
+            )),
+            span: Span::empty()
         },
         verified_expr(sql)
     );
@@ -1209,26 +1815,33 @@ fn parse_searched_case_expression() {
         &SQLCase {
             operand: None,
             conditions: vec![
-                SQLIsNull(Box::new(SQLIdentifier("bar".to_string()))),
+                SQLIsNull(
+                    Box::new(SQLIdentifier("bar".to_string(), Span::empty())),
+                    Span::empty()
+                ),
                 SQLBinaryExpr {
-                    left: Box::new(SQLIdentifier("bar".to_string())),
+                    left: Box::new(SQLIdentifier("bar".to_string(), Span::empty())),
                     op: Eq,
-                    right: Box::new(SQLValue(Value::Long(0)))
+                    right: Box::new(SQLValue(Value::Long(0), Span::empty())),
+                    span: Span::empty()
                 },
                 SQLBinaryExpr {
-                    left: Box::new(SQLIdentifier("bar".to_string())),
+                    left: Box::new(SQLIdentifier("bar".to_string(), Span::empty())),
                     op: GtEq,
-                    right: Box::new(SQLValue(Value::Long(0)))
+                    right: Box::new(SQLValue(Value::Long(0), Span::empty())),
+                    span: Span::empty()
                 }
             ],
             results: vec![
-                SQLValue(Value::SingleQuotedString("null".to_string())),
-                SQLValue(Value::SingleQuotedString("=0".to_string())),
-                SQLValue(Value::SingleQuotedString(">=0".to_string()))
+                SQLValue(Value::SingleQuotedString("null".to_string()), Span::empty()),
+                SQLValue(Value::SingleQuotedString("=0".to_string()), Span::empty()),
+                SQLValue(Value::SingleQuotedString(">=0".to_string()), Span::empty())
             ],
-            else_result: Some(Box::new(SQLValue(Value::SingleQuotedString(
-                "<0".to_string()
-            ))))
+            else_result: Some(Box::new(SQLValue(
+                Value::SingleQuotedString("<0".to_string()),
+                Span::empty()
+            ))),
+            span: Span::empty()
         },
         expr_from_projection(only(&select.projection)),
     );
@@ -1242,12 +1855,17 @@ fn parse_simple_case_expression() {
     use self::ASTNode::{SQLCase, SQLIdentifier, SQLValue};
     assert_eq!(
         &SQLCase {
-            operand: Some(Box::new(SQLIdentifier("foo".to_string()))),
-            conditions: vec![SQLValue(Value::Long(1))],
-            results: vec![SQLValue(Value::SingleQuotedString("Y".to_string())),],
-            else_result: Some(Box::new(SQLValue(Value::SingleQuotedString(
-                "N".to_string()
-            ))))
+            operand: Some(Box::new(SQLIdentifier("foo".to_string(), Span::empty()))),
+            conditions: vec![SQLValue(Value::Long(1), Span::empty())],
+            results: vec![SQLValue(
+                Value::SingleQuotedString("Y".to_string()),
+                Span::empty()
+            ),],
+            else_result: Some(Box::new(SQLValue(
+                Value::SingleQuotedString("N".to_string()),
+                Span::empty()
+            ))),
+            span: Span::empty()
         },
         expr_from_projection(only(&select.projection)),
     );
@@ -1270,8 +1888,10 @@ fn parse_implicit_join() {
                 alias: None,
                 args: vec![],
                 with_hints: vec![],
+                span: Span::empty(),
             },
-            join_operator: JoinOperator::Implicit
+            join_operator: JoinOperator::Implicit,
+            span: Span::empty()
         },
         only(&select.joins),
     );
@@ -1288,8 +1908,10 @@ fn parse_cross_join() {
                 alias: None,
                 args: vec![],
                 with_hints: vec![],
+                span: Span::empty(),
             },
-            join_operator: JoinOperator::Cross
+            join_operator: JoinOperator::Cross,
+            span: Span::empty()
         },
         only(&select.joins),
     );
@@ -1299,6 +1921,7 @@ fn table_alias(name: impl Into<String>) -> Option<TableAlias> {
     Some(TableAlias {
         name: name.into(),
         columns: vec![],
+        span: Span::empty(),
     })
 }
 
@@ -1315,12 +1938,15 @@ fn parse_joins_on() {
                 alias,
                 args: vec![],
                 with_hints: vec![],
+                span: Span::empty(),
             },
             join_operator: f(JoinConstraint::On(ASTNode::SQLBinaryExpr {
-                left: Box::new(ASTNode::SQLIdentifier("c1".into())),
+                left: Box::new(ASTNode::SQLIdentifier("c1".into(), Span::empty())),
                 op: SQLOperator::Eq,
-                right: Box::new(ASTNode::SQLIdentifier("c2".into())),
+                right: Box::new(ASTNode::SQLIdentifier("c2".into(), Span::empty())),
+                span: Span::empty(),
             })),
+            span: Span::empty(),
         }
     }
     // Test parsing of aliases
@@ -1368,8 +1994,10 @@ fn parse_joins_using() {
                 alias,
                 args: vec![],
                 with_hints: vec![],
+                span: Span::empty(),
             },
             join_operator: f(JoinConstraint::Using(vec!["c1".into()])),
+            span: Span::empty(),
         }
     }
     // Test parsing of aliases
@@ -1413,8 +2041,10 @@ fn parse_natural_join() {
                 alias: None,
                 args: vec![],
                 with_hints: vec![],
+                span: Span::empty(),
             },
             join_operator: f(JoinConstraint::Natural),
+            span: Span::empty(),
         }
     }
     assert_eq!(
@@ -1436,8 +2066,8 @@ fn parse_natural_join() {
 
     let sql = "SELECT * FROM t1 natural";
     assert_eq!(
-        ParserError::ParserError("Expected a join type after NATURAL, found: EOF".to_string()),
-        parse_sql_statements(sql).unwrap_err(),
+        "Expected a join type after NATURAL, found: EOF",
+        parse_sql_statements(sql).unwrap_err().message
     );
 }
 
@@ -1476,17 +2106,16 @@ fn parse_ctes() {
     );
 
     fn assert_ctes_in_select(expected: &[&str], sel: &SQLQuery) {
-        let mut i = 0;
-        for exp in expected {
+        for (i, exp) in expected.iter().enumerate() {
             let Cte {
                 query,
                 alias,
                 renamed_columns,
+                ..
             } = &sel.ctes[i];
             assert_eq!(*exp, query.to_string());
             assert_eq!(if i == 0 { "a" } else { "b" }, alias);
             assert!(renamed_columns.is_empty());
-            i += 1;
         }
     }
 
@@ -1496,7 +2125,7 @@ fn parse_ctes() {
     let sql = &format!("SELECT ({})", with);
     let select = verified_only_select(sql);
     match expr_from_projection(only(&select.projection)) {
-        ASTNode::SQLSubquery(ref subquery) => {
+        ASTNode::SQLSubquery(ref subquery, _) => {
             assert_ctes_in_select(&cte_sqls, subquery.as_ref());
         }
         _ => panic!("Expected subquery"),
@@ -1578,7 +2207,7 @@ fn parse_multiple_statements() {
         let res = parse_sql_statements(&(sql1.to_owned() + ";" + sql2_kw + sql2_rest));
         assert_eq!(
             vec![
-                one_statement_parses_to(&sql1, ""),
+                one_statement_parses_to(sql1, ""),
                 one_statement_parses_to(&(sql2_kw.to_owned() + sql2_rest), ""),
             ],
             res.unwrap()
@@ -1588,8 +2217,8 @@ fn parse_multiple_statements() {
         // Check that forgetting the semicolon results in an error:
         let res = parse_sql_statements(&(sql1.to_owned() + " " + sql2_kw + sql2_rest));
         assert_eq!(
-            ParserError::ParserError("Expected end of statement, found: ".to_string() + sql2_kw),
-            res.unwrap_err()
+            format!("{}{}", "Expected end of statement, found: ", sql2_kw),
+            res.unwrap_err().message
         );
     }
     test_with("SELECT foo", "SELECT", " bar");
@@ -1614,11 +2243,14 @@ fn parse_multiple_statements() {
 fn parse_scalar_subqueries() {
     use self::ASTNode::*;
     let sql = "(SELECT 1) + (SELECT 2)";
-    assert_matches!(verified_expr(sql), SQLBinaryExpr {
-        op: SQLOperator::Plus, ..
-        //left: box SQLSubquery { .. },
-        //right: box SQLSubquery { .. },
-    });
+    assert_matches!(
+        verified_expr(sql),
+        SQLBinaryExpr {
+            op: SQLOperator::Plus,
+            .. //left: box SQLSubquery { .. },
+               //right: box SQLSubquery { .. },
+        }
+    );
 }
 
 #[test]
@@ -1627,7 +2259,7 @@ fn parse_exists_subquery() {
     let sql = "SELECT * FROM t WHERE EXISTS (SELECT 1)";
     let select = verified_only_select(sql);
     assert_eq!(
-        ASTNode::SQLExists(Box::new(expected_inner.clone())),
+        ASTNode::SQLExists(Box::new(expected_inner.clone()), Span::empty()),
         select.selection.unwrap(),
     );
 
@@ -1636,7 +2268,8 @@ fn parse_exists_subquery() {
     assert_eq!(
         ASTNode::SQLUnary {
             operator: SQLOperator::Not,
-            expr: Box::new(ASTNode::SQLExists(Box::new(expected_inner))),
+            expr: Box::new(ASTNode::SQLExists(Box::new(expected_inner), Span::empty())),
+            span: Span::empty(),
         },
         select.selection.unwrap(),
     );
@@ -1646,18 +2279,14 @@ fn parse_exists_subquery() {
 
     let res = parse_sql_statements("SELECT EXISTS (");
     assert_eq!(
-        ParserError::ParserError(
-            "Expected SELECT or a subquery in the query body, found: EOF".to_string()
-        ),
-        res.unwrap_err(),
+        "Expected SELECT or a subquery in the query body, found: EOF",
+        res.unwrap_err().message
     );
 
     let res = parse_sql_statements("SELECT EXISTS (NULL)");
     assert_eq!(
-        ParserError::ParserError(
-            "Expected SELECT or a subquery in the query body, found: NULL".to_string()
-        ),
-        res.unwrap_err(),
+        "Expected SELECT or a subquery in the query body, found: NULL",
+        res.unwrap_err().message
     );
 }
 
@@ -1666,12 +2295,15 @@ fn parse_create_view() {
     let sql = "CREATE VIEW myschema.myview AS SELECT foo FROM bar";
     match verified_stmt(sql) {
         SQLStatement::SQLCreateView {
+            or_replace,
             name,
             columns,
             query,
             materialized,
             with_options,
+            ..
         } => {
+            assert!(!or_replace);
             assert_eq!("myschema.myview", name.to_string());
             assert_eq!(Vec::<SQLIdent>::new(), columns);
             assert_eq!("SELECT foo FROM bar", query.to_string());
@@ -1682,6 +2314,29 @@ fn parse_create_view() {
     }
 }
 
+#[test]
+fn parse_create_or_replace_view() {
+    let sql = "CREATE OR REPLACE VIEW v (a) AS SELECT 1";
+    match verified_stmt(sql) {
+        SQLStatement::SQLCreateView {
+            or_replace,
+            materialized,
+            ..
+        } => {
+            assert!(or_replace);
+            assert!(!materialized);
+        }
+        _ => unreachable!(),
+    }
+    verified_stmt("CREATE OR REPLACE MATERIALIZED VIEW v AS SELECT 1");
+
+    let res = parse_sql_statements("CREATE OR REPLACE TABLE t (a int)");
+    assert_eq!(
+        "Expected VIEW, FUNCTION or PROCEDURE after CREATE OR REPLACE, found: TABLE",
+        res.unwrap_err().message
+    );
+}
+
 #[test]
 fn parse_create_view_with_options() {
     let sql = "CREATE VIEW v WITH (foo = 'bar', a = 123) AS SELECT 1";
@@ -1691,11 +2346,13 @@ fn parse_create_view_with_options() {
                 vec![
                     SQLOption {
                         name: "foo".into(),
-                        value: Value::SingleQuotedString("bar".into())
+                        value: Value::SingleQuotedString("bar".into()),
+                        span: Span::empty()
                     },
                     SQLOption {
                         name: "a".into(),
-                        value: Value::Long(123)
+                        value: Value::Long(123),
+                        span: Span::empty()
                     },
                 ],
                 with_options
@@ -1715,6 +2372,7 @@ fn parse_create_view_with_columns() {
             with_options,
             query,
             materialized,
+            ..
         } => {
             assert_eq!("v", name.to_string());
             assert_eq!(columns, vec!["has".to_string(), "cols".to_string()]);
@@ -1736,6 +2394,7 @@ fn parse_create_materialized_view() {
             query,
             materialized,
             with_options,
+            ..
         } => {
             assert_eq!("myschema.myview", name.to_string());
             assert_eq!(Vec::<SQLIdent>::new(), columns);
@@ -1756,14 +2415,15 @@ fn parse_drop_table() {
             if_exists,
             names,
             cascade,
+            ..
         } => {
-            assert_eq!(false, if_exists);
+            assert!(!if_exists);
             assert_eq!(SQLObjectType::Table, object_type);
             assert_eq!(
                 vec!["foo"],
                 names.iter().map(|n| n.to_string()).collect::<Vec<_>>()
             );
-            assert_eq!(false, cascade);
+            assert!(!cascade);
         }
         _ => unreachable!(),
     }
@@ -1775,28 +2435,29 @@ fn parse_drop_table() {
             if_exists,
             names,
             cascade,
+            ..
         } => {
-            assert_eq!(true, if_exists);
+            assert!(if_exists);
             assert_eq!(SQLObjectType::Table, object_type);
             assert_eq!(
                 vec!["foo", "bar"],
                 names.iter().map(|n| n.to_string()).collect::<Vec<_>>()
             );
-            assert_eq!(true, cascade);
+            assert!(cascade);
         }
         _ => unreachable!(),
     }
 
     let sql = "DROP TABLE";
     assert_eq!(
-        ParserError::ParserError("Expected identifier, found: EOF".to_string()),
-        parse_sql_statements(sql).unwrap_err(),
+        "Expected identifier, found: EOF",
+        parse_sql_statements(sql).unwrap_err().message
     );
 
     let sql = "DROP TABLE IF EXISTS foo, bar CASCADE RESTRICT";
     assert_eq!(
-        ParserError::ParserError("Cannot specify both CASCADE and RESTRICT in DROP".to_string()),
-        parse_sql_statements(sql).unwrap_err(),
+        "Cannot specify both CASCADE and RESTRICT in DROP",
+        parse_sql_statements(sql).unwrap_err().message
     );
 }
 
@@ -1817,31 +2478,240 @@ fn parse_drop_view() {
     }
 }
 
+#[test]
+fn parse_create_index() {
+    let sql = "CREATE UNIQUE INDEX idx_name ON test (name, age DESC)";
+    match verified_stmt(sql) {
+        SQLStatement::SQLCreateIndex {
+            name,
+            table_name,
+            unique,
+            concurrently,
+            if_not_exists,
+            using,
+            columns,
+            include,
+            predicate,
+            ..
+        } => {
+            assert_eq!("idx_name", name.to_string());
+            assert_eq!("test", table_name.to_string());
+            assert!(unique);
+            assert!(!concurrently);
+            assert!(!if_not_exists);
+            assert_eq!(None, using);
+            assert_eq!(
+                vec![
+                    SQLOrderByExpr {
+                        expr: ASTNode::SQLIdentifier("name".to_string(), Span::empty()),
+                        asc: None,
+                        nulls_first: None,
+                        span: Span::empty(),
+                    },
+                    SQLOrderByExpr {
+                        expr: ASTNode::SQLIdentifier("age".to_string(), Span::empty()),
+                        asc: Some(false),
+                        nulls_first: None,
+                        span: Span::empty(),
+                    },
+                ],
+                columns
+            );
+            assert!(include.is_empty());
+            assert_eq!(None, predicate);
+        }
+        _ => unreachable!(),
+    }
+
+    let sql = "CREATE INDEX CONCURRENTLY IF NOT EXISTS s.idx ON s.t USING gin \
+               (lower(name) ASC NULLS LAST, id) INCLUDE (a, b) WHERE deleted_at IS NULL";
+    match verified_stmt(sql) {
+        SQLStatement::SQLCreateIndex {
+            name,
+            table_name,
+            unique,
+            concurrently,
+            if_not_exists,
+            using,
+            columns,
+            include,
+            predicate,
+            ..
+        } => {
+            assert_eq!("s.idx", name.to_string());
+            assert_eq!("s.t", table_name.to_string());
+            assert!(!unique);
+            assert!(concurrently);
+            assert!(if_not_exists);
+            assert_eq!(Some("gin".to_string()), using);
+            assert_eq!(Some(true), columns[0].asc);
+            assert_eq!(Some(false), columns[0].nulls_first);
+            assert_eq!(
+                vec!["lower(name) ASC NULLS LAST", "id"],
+                columns.iter().map(|c| c.to_string()).collect::<Vec<_>>()
+            );
+            assert_eq!(vec!["a", "b"], include);
+            assert_eq!(verified_expr("deleted_at IS NULL"), predicate.unwrap());
+        }
+        _ => unreachable!(),
+    }
+
+    let res = parse_sql_statements("CREATE INDEX idx ON t");
+    assert_eq!("Expected (, found: EOF", res.unwrap_err().message);
+    let res = parse_sql_statements("CREATE UNIQUE TABLE t (a int)");
+    assert_eq!(
+        "Expected TABLE, VIEW, INDEX, SCHEMA, DATABASE, SEQUENCE, FUNCTION or PROCEDURE after CREATE, found: UNIQUE",
+        res.unwrap_err().message
+    );
+}
+
+#[test]
+fn parse_drop_index() {
+    let sql = "DROP INDEX IF EXISTS idx_a, s.idx_b";
+    match verified_stmt(sql) {
+        SQLStatement::SQLDrop {
+            names,
+            object_type,
+            if_exists,
+            ..
+        } => {
+            assert_eq!(
+                vec!["idx_a", "s.idx_b"],
+                names.iter().map(|n| n.to_string()).collect::<Vec<_>>()
+            );
+            assert_eq!(SQLObjectType::Index, object_type);
+            assert!(if_exists);
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn parse_create_schema_and_database() {
+    match verified_stmt("CREATE SCHEMA IF NOT EXISTS reporting") {
+        SQLStatement::SQLCreateSchema {
+            schema_name,
+            if_not_exists,
+            ..
+        } => {
+            assert_eq!("reporting", schema_name.to_string());
+            assert!(if_not_exists);
+        }
+        _ => unreachable!(),
+    }
+    match verified_stmt("CREATE DATABASE analytics") {
+        SQLStatement::SQLCreateDatabase {
+            db_name,
+            if_not_exists,
+            ..
+        } => {
+            assert_eq!("analytics", db_name.to_string());
+            assert!(!if_not_exists);
+        }
+        _ => unreachable!(),
+    }
+
+    for (sql, expected_type) in &[
+        (
+            "DROP SCHEMA IF EXISTS reporting CASCADE",
+            SQLObjectType::Schema,
+        ),
+        ("DROP DATABASE analytics", SQLObjectType::Database),
+        ("DROP SEQUENCE s1, s2", SQLObjectType::Sequence),
+    ] {
+        match verified_stmt(sql) {
+            SQLStatement::SQLDrop { object_type, .. } => assert_eq!(expected_type, &object_type),
+            _ => unreachable!(),
+        }
+    }
+
+    let res = parse_sql_statements("DROP TRIGGER t");
+    assert_eq!(
+        "Expected TABLE, VIEW, INDEX, SCHEMA, DATABASE or SEQUENCE after DROP, found: TRIGGER",
+        res.unwrap_err().message
+    );
+}
+
+#[test]
+fn parse_order_by_nulls() {
+    let query = verified_query("SELECT a FROM t ORDER BY a NULLS FIRST, b DESC NULLS LAST");
+    assert_eq!(
+        vec![(None, Some(true)), (Some(false), Some(false))],
+        query
+            .order_by
+            .iter()
+            .map(|o| (o.asc, o.nulls_first))
+            .collect::<Vec<_>>()
+    );
+}
+
 #[test]
 fn parse_invalid_subquery_without_parens() {
     let res = parse_sql_statements("SELECT SELECT 1 FROM bar WHERE 1=1 FROM baz");
     assert_eq!(
-        ParserError::ParserError("Expected end of statement, found: 1".to_string()),
-        res.unwrap_err()
+        "Expected end of statement, found: 1",
+        res.unwrap_err().message
     );
 }
 
 #[test]
 fn parse_offset() {
     let ast = verified_query("SELECT foo FROM bar OFFSET 2 ROWS");
-    assert_eq!(ast.offset, Some(ASTNode::SQLValue(Value::Long(2))));
+    assert_eq!(
+        ast.offset,
+        Some(Offset {
+            value: ASTNode::SQLValue(Value::Long(2), Span::empty()),
+            rows: true,
+            span: Span::empty(),
+        })
+    );
     let ast = verified_query("SELECT foo FROM bar WHERE foo = 4 OFFSET 2 ROWS");
-    assert_eq!(ast.offset, Some(ASTNode::SQLValue(Value::Long(2))));
+    assert_eq!(
+        ast.offset,
+        Some(Offset {
+            value: ASTNode::SQLValue(Value::Long(2), Span::empty()),
+            rows: true,
+            span: Span::empty(),
+        })
+    );
     let ast = verified_query("SELECT foo FROM bar ORDER BY baz OFFSET 2 ROWS");
-    assert_eq!(ast.offset, Some(ASTNode::SQLValue(Value::Long(2))));
+    assert_eq!(
+        ast.offset,
+        Some(Offset {
+            value: ASTNode::SQLValue(Value::Long(2), Span::empty()),
+            rows: true,
+            span: Span::empty(),
+        })
+    );
     let ast = verified_query("SELECT foo FROM bar WHERE foo = 4 ORDER BY baz OFFSET 2 ROWS");
-    assert_eq!(ast.offset, Some(ASTNode::SQLValue(Value::Long(2))));
+    assert_eq!(
+        ast.offset,
+        Some(Offset {
+            value: ASTNode::SQLValue(Value::Long(2), Span::empty()),
+            rows: true,
+            span: Span::empty(),
+        })
+    );
     let ast = verified_query("SELECT foo FROM (SELECT * FROM bar OFFSET 2 ROWS) OFFSET 2 ROWS");
-    assert_eq!(ast.offset, Some(ASTNode::SQLValue(Value::Long(2))));
+    assert_eq!(
+        ast.offset,
+        Some(Offset {
+            value: ASTNode::SQLValue(Value::Long(2), Span::empty()),
+            rows: true,
+            span: Span::empty(),
+        })
+    );
     match ast.body {
         SQLSetExpr::Select(s) => match s.relation {
             Some(TableFactor::Derived { subquery, .. }) => {
-                assert_eq!(subquery.offset, Some(ASTNode::SQLValue(Value::Long(2))));
+                assert_eq!(
+                    subquery.offset,
+                    Some(Offset {
+                        value: ASTNode::SQLValue(Value::Long(2), Span::empty()),
+                        rows: true,
+                        span: Span::empty(),
+                    })
+                );
             }
             _ => panic!("Test broke"),
         },
@@ -1865,7 +2735,8 @@ fn parse_fetch() {
         Some(Fetch {
             with_ties: false,
             percent: false,
-            quantity: Some(ASTNode::SQLValue(Value::Long(2))),
+            quantity: Some(ASTNode::SQLValue(Value::Long(2), Span::empty())),
+            span: Span::empty(),
         })
     );
     let ast = verified_query("SELECT foo FROM bar FETCH FIRST ROWS ONLY");
@@ -1875,6 +2746,7 @@ fn parse_fetch() {
             with_ties: false,
             percent: false,
             quantity: None,
+            span: Span::empty(),
         })
     );
     let ast = verified_query("SELECT foo FROM bar WHERE foo = 4 FETCH FIRST 2 ROWS ONLY");
@@ -1883,7 +2755,8 @@ fn parse_fetch() {
         Some(Fetch {
             with_ties: false,
             percent: false,
-            quantity: Some(ASTNode::SQLValue(Value::Long(2))),
+            quantity: Some(ASTNode::SQLValue(Value::Long(2), Span::empty())),
+            span: Span::empty(),
         })
     );
     let ast = verified_query("SELECT foo FROM bar ORDER BY baz FETCH FIRST 2 ROWS ONLY");
@@ -1892,7 +2765,8 @@ fn parse_fetch() {
         Some(Fetch {
             with_ties: false,
             percent: false,
-            quantity: Some(ASTNode::SQLValue(Value::Long(2))),
+            quantity: Some(ASTNode::SQLValue(Value::Long(2), Span::empty())),
+            span: Span::empty(),
         })
     );
     let ast = verified_query(
@@ -1903,7 +2777,8 @@ fn parse_fetch() {
         Some(Fetch {
             with_ties: true,
             percent: false,
-            quantity: Some(ASTNode::SQLValue(Value::Long(2))),
+            quantity: Some(ASTNode::SQLValue(Value::Long(2), Span::empty())),
+            span: Span::empty(),
         })
     );
     let ast = verified_query("SELECT foo FROM bar FETCH FIRST 50 PERCENT ROWS ONLY");
@@ -1912,19 +2787,28 @@ fn parse_fetch() {
         Some(Fetch {
             with_ties: false,
             percent: true,
-            quantity: Some(ASTNode::SQLValue(Value::Long(50))),
+            quantity: Some(ASTNode::SQLValue(Value::Long(50), Span::empty())),
+            span: Span::empty(),
         })
     );
     let ast = verified_query(
         "SELECT foo FROM bar WHERE foo = 4 ORDER BY baz OFFSET 2 ROWS FETCH FIRST 2 ROWS ONLY",
     );
-    assert_eq!(ast.offset, Some(ASTNode::SQLValue(Value::Long(2))));
+    assert_eq!(
+        ast.offset,
+        Some(Offset {
+            value: ASTNode::SQLValue(Value::Long(2), Span::empty()),
+            rows: true,
+            span: Span::empty(),
+        })
+    );
     assert_eq!(
         ast.fetch,
         Some(Fetch {
             with_ties: false,
             percent: false,
-            quantity: Some(ASTNode::SQLValue(Value::Long(2))),
+            quantity: Some(ASTNode::SQLValue(Value::Long(2), Span::empty())),
+            span: Span::empty(),
         })
     );
     let ast = verified_query(
@@ -1935,7 +2819,8 @@ fn parse_fetch() {
         Some(Fetch {
             with_ties: false,
             percent: false,
-            quantity: Some(ASTNode::SQLValue(Value::Long(2))),
+            quantity: Some(ASTNode::SQLValue(Value::Long(2), Span::empty())),
+            span: Span::empty(),
         })
     );
     match ast.body {
@@ -1946,7 +2831,8 @@ fn parse_fetch() {
                     Some(Fetch {
                         with_ties: false,
                         percent: false,
-                        quantity: Some(ASTNode::SQLValue(Value::Long(2))),
+                        quantity: Some(ASTNode::SQLValue(Value::Long(2), Span::empty())),
+                        span: Span::empty(),
                     })
                 );
             }
@@ -1955,25 +2841,41 @@ fn parse_fetch() {
         _ => panic!("Test broke"),
     }
     let ast = verified_query("SELECT foo FROM (SELECT * FROM bar OFFSET 2 ROWS FETCH FIRST 2 ROWS ONLY) OFFSET 2 ROWS FETCH FIRST 2 ROWS ONLY");
-    assert_eq!(ast.offset, Some(ASTNode::SQLValue(Value::Long(2))));
+    assert_eq!(
+        ast.offset,
+        Some(Offset {
+            value: ASTNode::SQLValue(Value::Long(2), Span::empty()),
+            rows: true,
+            span: Span::empty(),
+        })
+    );
     assert_eq!(
         ast.fetch,
         Some(Fetch {
             with_ties: false,
             percent: false,
-            quantity: Some(ASTNode::SQLValue(Value::Long(2))),
+            quantity: Some(ASTNode::SQLValue(Value::Long(2), Span::empty())),
+            span: Span::empty(),
         })
     );
     match ast.body {
         SQLSetExpr::Select(s) => match s.relation {
             Some(TableFactor::Derived { subquery, .. }) => {
-                assert_eq!(subquery.offset, Some(ASTNode::SQLValue(Value::Long(2))));
+                assert_eq!(
+                    subquery.offset,
+                    Some(Offset {
+                        value: ASTNode::SQLValue(Value::Long(2), Span::empty()),
+                        rows: true,
+                        span: Span::empty(),
+                    })
+                );
                 assert_eq!(
                     subquery.fetch,
                     Some(Fetch {
                         with_ties: false,
                         percent: false,
-                        quantity: Some(ASTNode::SQLValue(Value::Long(2))),
+                        quantity: Some(ASTNode::SQLValue(Value::Long(2), Span::empty())),
+                        span: Span::empty(),
                     })
                 );
             }
@@ -2020,12 +2922,16 @@ fn lateral_derived() {
         assert_eq!(select.joins.len(), 1);
         assert_eq!(
             select.joins[0].join_operator,
-            JoinOperator::LeftOuter(JoinConstraint::On(ASTNode::SQLValue(Value::Boolean(true))))
+            JoinOperator::LeftOuter(JoinConstraint::On(ASTNode::SQLValue(
+                Value::Boolean(true),
+                Span::empty()
+            )))
         );
         if let TableFactor::Derived {
             lateral,
             ref subquery,
             alias: Some(ref alias),
+            ..
         } = select.joins[0].relation
         {
             assert_eq!(lateral_in, lateral);
@@ -2044,10 +2950,8 @@ fn lateral_derived() {
     let sql = "SELECT * FROM customer LEFT JOIN LATERAL generate_series(1, customer.id)";
     let res = parse_sql_statements(sql);
     assert_eq!(
-        ParserError::ParserError(
-            "Expected subquery after LATERAL, found: generate_series".to_string()
-        ),
-        res.unwrap_err()
+        "Expected subquery after LATERAL, found: generate_series",
+        res.unwrap_err().message
     );
 }
 
@@ -2066,6 +2970,214 @@ fn parse_sql_statements(sql: &str) -> Result<Vec<SQLStatement>, ParserError> {
     all_dialects().parse_sql_statements(sql)
 }
 
+#[test]
+fn parse_error_details() {
+    let sql = "SELECT a\nFROM t\nWHERE a IS 1";
+    let err = parse_sql_statements(sql).unwrap_err();
+    assert_eq!(ParserErrorKind::UnexpectedToken, err.kind);
+    assert_eq!("Expected NULL or NOT NULL after IS, found: 1", err.message);
+    assert_eq!(vec!["NULL", "NOT NULL"], err.expected);
+    assert_eq!(Some(Box::new(Token::Number("1".to_string()))), err.found);
+    assert_eq!(
+        (
+            Location {
+                offset: 27,
+                line: 3,
+                column: 12
+            },
+            Location {
+                offset: 28,
+                line: 3,
+                column: 13
+            }
+        ),
+        (err.span.start, err.span.end)
+    );
+    assert_eq!(
+        "sql parser error: Expected NULL or NOT NULL after IS, found: 1\n \
+         --> line 3, column 12\n  |\n3 | WHERE a IS 1\n  |            ^",
+        err.render(sql)
+    );
+
+    let sql = "SELECT * FROM customer WHERE";
+    let err = parse_sql_statements(sql).unwrap_err();
+    assert_eq!(ParserErrorKind::UnexpectedEof, err.kind);
+    assert_eq!(None, err.found);
+    assert_eq!(
+        "sql parser error: Expected an expression, found: EOF\n \
+         --> line 1, column 29\n  |\n1 | SELECT * FROM customer WHERE\n  |                             ^",
+        err.render(sql)
+    );
+
+    let err = parse_sql_statements("SELECT ALL DISTINCT a FROM t").unwrap_err();
+    assert_eq!(ParserErrorKind::InvalidSyntax, err.kind);
+    let err = parse_sql_statements("SELECT 1 ! 2").unwrap_err();
+    assert_eq!(ParserErrorKind::TokenizerError, err.kind);
+    assert_eq!(10, err.span.start.column);
+}
+
+#[test]
+fn display_writes_into_formatter() {
+    use std::fmt::Write;
+
+    let sql = "INSERT INTO t (a, b) VALUES (1, 'it''s'), (2, NULL)";
+    let stmt = verified_stmt(sql);
+    let mut buf = String::from("-- generated\n");
+    write!(buf, "{};", stmt).unwrap();
+    assert_eq!(format!("-- generated\n{};", sql), buf);
+    // padding and other formatting flags are ignored
+    assert_eq!(sql, format!("{:>100}", stmt));
+}
+
+#[test]
+fn parse_comments() {
+    let sql = "-- leading\nSELECT 1 /* inline */ + 2; -- trailing";
+    let (statements, comments) =
+        Parser::parse_sql_with_comments(&GenericSqlDialect {}, sql.to_string()).unwrap();
+    assert_eq!(parse_sql_statements(sql).unwrap(), statements);
+    assert_eq!(
+        vec![
+            (" leading".to_string(), false, 1, 1),
+            (" inline ".to_string(), true, 2, 10),
+            (" trailing".to_string(), false, 2, 28),
+        ],
+        comments
+            .iter()
+            .map(|c| (
+                c.text.clone(),
+                c.multiline,
+                c.span.start.line,
+                c.span.start.column
+            ))
+            .collect::<Vec<_>>()
+    );
+    assert_eq!("-- leading", comments[0].to_string());
+    assert_eq!("/* inline */", comments[1].to_string());
+}
+
+#[test]
+fn parse_with_recovery() {
+    let sql = "SELECT 1;\nSELEC 2;;\nSELECT a FROM t WHERE ;\nSELECT 3 4;\nSELECT b !";
+    let result = Parser::parse_sql_with_recovery(&GenericSqlDialect {}, sql.to_string());
+    let statements: Vec<_> = result
+        .statements
+        .iter()
+        .map(|s| s.as_ref().map(|s| s.to_string()))
+        .collect();
+    assert_eq!(
+        vec![
+            Some("SELECT 1".to_string()),
+            None,
+            Some("SELECT a FROM t".to_string()),
+            Some("SELECT 3".to_string()),
+            Some("SELECT b".to_string()),
+        ],
+        statements
+    );
+    let diagnostics: Vec<_> = result
+        .diagnostics
+        .iter()
+        .map(|d| (d.statement_index, d.error.message.as_str()))
+        .collect();
+    assert_eq!(
+        vec![
+            (
+                1,
+                "Expected a keyword at the beginning of a statement, found: SELEC"
+            ),
+            (2, "Expected an expression, found: ;"),
+            (3, "Expected end of statement, found: 4"),
+            (4, "Tokenizer Error at Line: 5, Col: 10"),
+        ],
+        diagnostics
+    );
+    assert_eq!(3, result.diagnostics[1].error.span.start.line);
+}
+
+#[test]
+fn parse_with_recovery_inside_select() {
+    let sql = "SELECT a, (b + ) FROM t JOIN u ON = 1 WHERE x IN (SELECT y FROM v WHERE) GROUP BY 1";
+    let result = Parser::parse_sql_with_recovery(&GenericSqlDialect {}, sql.to_string());
+    let errors: Vec<_> = result
+        .diagnostics
+        .iter()
+        .map(|d| (d.statement_index, d.error.message.as_str()))
+        .collect();
+    assert_eq!(
+        vec![
+            (0, "Expected an expression, found: )"),
+            (0, "Expected an expression, found: ="),
+            (0, "Expected an expression, found: )"),
+        ],
+        errors
+    );
+    let select = match only(&result.statements) {
+        Some(SQLStatement::SQLQuery(query)) => match &query.body {
+            SQLSetExpr::Select(select) => select.clone(),
+            _ => unreachable!(),
+        },
+        _ => unreachable!(),
+    };
+    assert!(select.projection.is_empty());
+    assert!(select.relation.is_none());
+    assert_eq!(
+        "x IN (SELECT y FROM v)",
+        select.selection.unwrap().to_string()
+    );
+    assert_eq!(1, select.group_by.len());
+
+    // without recovery, the first error is returned
+    assert_eq!(
+        "Expected an expression, found: )",
+        parse_sql_statements(sql).unwrap_err().message
+    );
+}
+
+#[test]
+fn parse_spans() {
+    let sql = "SELECT a, count(*) AS c\nFROM t AS x JOIN u ON x.id = u.id\nWHERE a IN (1, 2) AND NOT b\nORDER BY c DESC LIMIT 10";
+    let text = |span: Span| &sql[span.start.offset..span.end.offset];
+    let stmt = one_statement_parses_to(sql, "");
+    assert_eq!(sql, text(stmt.span()));
+    let query = match stmt {
+        SQLStatement::SQLQuery(query) => query,
+        _ => unreachable!(),
+    };
+    assert_eq!("c DESC", text(query.order_by[0].span));
+    assert_eq!("10", text(query.limit.as_ref().unwrap().span()));
+
+    let select = match &query.body {
+        SQLSetExpr::Select(select) => select,
+        _ => unreachable!(),
+    };
+    assert_eq!(
+        "SELECT a, count(*) AS c\nFROM t AS x JOIN u ON x.id = u.id\nWHERE a IN (1, 2) AND NOT b",
+        text(select.span)
+    );
+    assert_eq!("a", text(select.projection[0].span()));
+    assert_eq!("count(*) AS c", text(select.projection[1].span()));
+    assert_eq!("t AS x", text(select.relation.as_ref().unwrap().span()));
+    assert_eq!("JOIN u ON x.id = u.id", text(select.joins[0].span));
+
+    let selection = select.selection.as_ref().unwrap();
+    assert_eq!("a IN (1, 2) AND NOT b", text(selection.span()));
+    assert_eq!(
+        (Location {
+            offset: 64,
+            line: 3,
+            column: 7
+        }),
+        selection.span().start
+    );
+    match selection {
+        ASTNode::SQLBinaryExpr { left, right, .. } => {
+            assert_eq!("a IN (1, 2)", text(left.span()));
+            assert_eq!("NOT b", text(right.span()));
+        }
+        _ => unreachable!(),
+    }
+}
+
 fn one_statement_parses_to(sql: &str, canonical: &str) -> SQLStatement {
     all_dialects().one_statement_parses_to(sql, canonical)
 }
@@ -0,0 +1,280 @@
+#![warn(clippy::all)]
+//! Test the `Dialect` hooks that let a custom dialect extend the grammar.
+
+use sqlparser::dialect::{keywords, Dialect, GenericSqlDialect};
+use sqlparser::sqlast::*;
+use sqlparser::sqlparser::{Parser, ParserError};
+use sqlparser::sqltokenizer::Token;
+use sqlparser::test_utils::*;
+
+/// A dialect supporting:
+/// - `TRUNCATE <table>`, parsed as an unconditional DELETE,
+/// - `ANSWER`, parsed as the number 42,
+/// - `a ** b`, parsed as `POWER(a, b)` and binding tighter than `*`,
+/// - the `VARCHAR2(n)` data type, parsed as `character varying(n)`.
+#[derive(Debug)]
+struct CustomDialect {}
+
+impl Dialect for CustomDialect {
+    fn is_identifier_start(&self, ch: char) -> bool {
+        GenericSqlDialect {}.is_identifier_start(ch)
+    }
+
+    fn is_identifier_part(&self, ch: char) -> bool {
+        GenericSqlDialect {}.is_identifier_part(ch)
+    }
+
+    fn parse_statement(&self, parser: &mut Parser) -> Option<Result<SQLStatement, ParserError>> {
+        if !parse_word(parser, "TRUNCATE") {
+            return None;
+        }
+        Some(parser.parse_object_name().map(|table_name| {
+            SQLStatement::SQLDelete {
+                table_name,
+                alias: None,
+                using: None,
+                joins: vec![],
+                selection: None,
+                returning: vec![],
+                span: Span::empty(), // `Parser::parse_statement` fills this in
+            }
+        }))
+    }
+
+    fn parse_prefix(&self, parser: &mut Parser) -> Option<Result<ASTNode, ParserError>> {
+        let start = parser.peek_span();
+        if !parse_word(parser, "ANSWER") {
+            return None;
+        }
+        Some(Ok(ASTNode::SQLValue(
+            Value::Long(42),
+            parser.span_from(start),
+        )))
+    }
+
+    fn parse_infix(
+        &self,
+        parser: &mut Parser,
+        expr: &ASTNode,
+        precedence: u8,
+    ) -> Option<Result<ASTNode, ParserError>> {
+        if !is_power_operator(parser) {
+            return None;
+        }
+        parser.next_token();
+        parser.next_token();
+        Some(parser.parse_subexpr(precedence).map(|right| {
+            let span = expr.span().union(&right.span());
+            ASTNode::SQLFunction(SQLFunction {
+                name: SQLObjectName(vec!["POWER".to_string()]),
+                args: vec![expr.clone(), right],
+                over: None,
+                distinct: false,
+                span,
+            })
+        }))
+    }
+
+    fn get_next_precedence(&self, parser: &Parser) -> Option<Result<u8, ParserError>> {
+        if is_power_operator(parser) {
+            Some(Ok(50))
+        } else {
+            None
+        }
+    }
+
+    fn parse_data_type(&self, parser: &mut Parser) -> Option<Result<SQLType, ParserError>> {
+        if !parse_word(parser, "VARCHAR2") {
+            return None;
+        }
+        Some(
+            parser
+                .expect_token(&Token::LParen)
+                .and_then(|_| parser.parse_literal_uint())
+                .and_then(|n| {
+                    parser.expect_token(&Token::RParen)?;
+                    Ok(SQLType::Varchar(Some(n)))
+                }),
+        )
+    }
+}
+
+/// Like `Parser::parse_keyword`, but for words that aren't known keywords
+fn parse_word(parser: &mut Parser, expected: &str) -> bool {
+    match parser.peek_token() {
+        Some(Token::SQLWord(ref w))
+            if w.quote_style.is_none() && w.value.eq_ignore_ascii_case(expected) =>
+        {
+            parser.next_token();
+            true
+        }
+        _ => false,
+    }
+}
+
+fn is_power_operator(parser: &Parser) -> bool {
+    parser.peek_token() == Some(Token::Mult) && parser.peek_nth_token(1) == Some(Token::Mult)
+}
+
+#[test]
+fn parse_custom_statement() {
+    let sql = "TRUNCATE db.t";
+    match custom().one_statement_parses_to(sql, "DELETE FROM db.t") {
+        SQLStatement::SQLDelete {
+            table_name,
+            selection,
+            ..
+        } => {
+            assert_eq!("db.t", table_name.to_string());
+            assert_eq!(None, selection);
+        }
+        _ => unreachable!(),
+    }
+    let statements = Parser::parse_sql(&CustomDialect {}, sql.to_string()).unwrap();
+    let span = statements[0].span();
+    assert_eq!((0, 13), (span.start.offset, span.end.offset));
+
+    // Without the hook, TRUNCATE is not a statement
+    let res = generic().parse_sql_statements(sql);
+    assert_eq!(
+        "Expected a keyword at the beginning of a statement, found: TRUNCATE",
+        res.unwrap_err().message
+    );
+}
+
+#[test]
+fn parse_custom_prefix_and_infix() {
+    let select = match custom()
+        .one_statement_parses_to("SELECT 2 * 3 + ANSWER, 1", "SELECT 2 * 3 + 42, 1")
+    {
+        SQLStatement::SQLQuery(query) => match query.body {
+            SQLSetExpr::Select(select) => select,
+            _ => unreachable!(),
+        },
+        _ => unreachable!(),
+    };
+    assert_eq!(
+        &ASTNode::SQLValue(Value::Long(42), Span::empty()),
+        match expr_from_projection(&select.projection[0]) {
+            ASTNode::SQLBinaryExpr { right, .. } => right.as_ref(),
+            _ => unreachable!(),
+        }
+    );
+
+    // `**` binds tighter than `*`, and the right-hand side is a full subexpression
+    custom().one_statement_parses_to(
+        "SELECT 2 * a ** b + 1 FROM t",
+        "SELECT 2 * POWER(a, b) + 1 FROM t",
+    );
+    custom().one_statement_parses_to(
+        "SELECT a ** (b - 1) FROM t",
+        "SELECT POWER(a, (b - 1)) FROM t",
+    );
+
+    // The default grammar still applies when the hooks decline
+    let select = custom().verified_only_select("SELECT a * b FROM t WHERE \"ANSWER\" > 3");
+    assert_eq!(
+        &ASTNode::SQLIdentifier("\"ANSWER\"".to_string(), Span::empty()),
+        match select.selection.as_ref().unwrap() {
+            ASTNode::SQLBinaryExpr { left, .. } => left.as_ref(),
+            _ => unreachable!(),
+        }
+    );
+}
+
+#[test]
+fn parse_custom_data_type() {
+    custom().one_statement_parses_to(
+        "CREATE TABLE t (a VARCHAR2(10), b VARCHAR(20))",
+        "CREATE TABLE t (a character varying(10), b character varying(20))",
+    );
+    custom().one_statement_parses_to(
+        "SELECT CAST(a AS VARCHAR2(5)) FROM t",
+        "SELECT CAST(a AS character varying(5)) FROM t",
+    );
+
+    let res = custom().parse_sql_statements("CREATE TABLE t (a VARCHAR2)");
+    assert_eq!("Expected (, found: )", res.unwrap_err().message);
+}
+
+/// A dialect without the `LIMIT` keyword and with `USER` reserved
+#[derive(Debug)]
+struct NoLimitDialect {
+    keywords: Vec<&'static str>,
+    reserved_keywords: Vec<&'static str>,
+}
+
+impl NoLimitDialect {
+    fn new() -> Self {
+        NoLimitDialect {
+            keywords: keywords::ALL_KEYWORDS
+                .iter()
+                .cloned()
+                .filter(|&kw| kw != keywords::LIMIT)
+                .collect(),
+            reserved_keywords: keywords::RESERVED_KEYWORDS
+                .iter()
+                .cloned()
+                .filter(|&kw| kw != keywords::LIMIT)
+                .chain(vec![keywords::USER])
+                .collect(),
+        }
+    }
+}
+
+impl Dialect for NoLimitDialect {
+    fn is_identifier_start(&self, ch: char) -> bool {
+        GenericSqlDialect {}.is_identifier_start(ch)
+    }
+
+    fn is_identifier_part(&self, ch: char) -> bool {
+        GenericSqlDialect {}.is_identifier_part(ch)
+    }
+
+    fn keywords(&self) -> &[&str] {
+        &self.keywords
+    }
+
+    fn reserved_keywords(&self) -> &[&str] {
+        &self.reserved_keywords
+    }
+}
+
+#[test]
+fn parse_custom_keywords() {
+    let dialects = TestedDialects {
+        dialects: vec![Box::new(NoLimitDialect::new())],
+    };
+    // `limit` is just an identifier, so it can be used as an alias
+    let select = dialects.verified_only_select("SELECT limit FROM t AS limit");
+    assert_eq!(
+        &ASTNode::SQLIdentifier("limit".to_string(), Span::empty()),
+        expr_from_projection(&select.projection[0])
+    );
+    dialects.one_statement_parses_to("SELECT * FROM t limit", "SELECT * FROM t AS limit");
+    let res = dialects.parse_sql_statements("SELECT * FROM t LIMIT 1");
+    assert_eq!(
+        "Expected end of statement, found: 1",
+        res.unwrap_err().message
+    );
+
+    let dialect = NoLimitDialect::new();
+    assert!(dialect.identifier_requires_quoting("user"));
+    assert!(dialect.identifier_requires_quoting("select"));
+    assert!(dialect.identifier_requires_quoting("a-b"));
+    assert!(dialect.identifier_requires_quoting("1a"));
+    assert!(!dialect.identifier_requires_quoting("limit"));
+    assert!(!dialect.identifier_requires_quoting("a_1"));
+    assert_eq!("\"a\"\"b\"", dialect.quote_identifier("a\"b"));
+}
+
+fn custom() -> TestedDialects {
+    TestedDialects {
+        dialects: vec![Box::new(CustomDialect {})],
+    }
+}
+fn generic() -> TestedDialects {
+    TestedDialects {
+        dialects: vec![Box::new(GenericSqlDialect {})],
+    }
+}
@@ -11,11 +11,11 @@ fn parse_mssql_identifiers() {
     let sql = "SELECT @@version, _foo$123 FROM ##temp";
     let select = ms_and_generic().verified_only_select(sql);
     assert_eq!(
-        &ASTNode::SQLIdentifier("@@version".to_string()),
+        &ASTNode::SQLIdentifier("@@version".to_string(), Span::empty()),
         expr_from_projection(&select.projection[0]),
     );
     assert_eq!(
-        &ASTNode::SQLIdentifier("_foo$123".to_string()),
+        &ASTNode::SQLIdentifier("_foo$123".to_string(), Span::empty()),
         expr_from_projection(&select.projection[1]),
     );
     assert_eq!(2, select.projection.len());
@@ -40,6 +40,121 @@ fn parse_mssql_delimited_identifiers() {
     );
 }
 
+#[test]
+fn parse_mssql_merge() {
+    let sql = "MERGE INTO dbo.target AS t \
+               USING (SELECT id, name FROM dbo.staging) AS s ON t.id = s.id \
+               WHEN MATCHED AND t.name <> s.name THEN UPDATE SET name = s.name \
+               WHEN NOT MATCHED BY TARGET THEN INSERT (id, name) VALUES (s.id, s.name) \
+               WHEN NOT MATCHED BY SOURCE THEN DELETE";
+    match ms_and_generic().verified_stmt(sql) {
+        SQLStatement::SQLMerge { table, clauses, .. } => {
+            match table {
+                TableFactor::Table { name, alias, .. } => {
+                    assert_eq!("dbo.target", name.to_string());
+                    assert_eq!("t", alias.unwrap().name);
+                }
+                _ => unreachable!(),
+            }
+            assert_eq!(
+                vec![
+                    MergeClauseKind::Matched,
+                    MergeClauseKind::NotMatchedByTarget,
+                    MergeClauseKind::NotMatchedBySource,
+                ],
+                clauses.iter().map(|c| c.kind).collect::<Vec<_>>()
+            );
+            assert_eq!(MergeAction::Delete, clauses[2].action);
+        }
+        _ => unreachable!(),
+    }
+
+    // the statement terminator MSSQL requires after MERGE
+    ms().one_statement_parses_to(
+        "MERGE t USING s ON t.id = s.id WHEN MATCHED THEN DELETE;",
+        "MERGE t USING s ON t.id = s.id WHEN MATCHED THEN DELETE",
+    );
+    let res = ms().parse_sql_statements(
+        "MERGE t USING s ON t.id = s.id WHEN NOT MATCHED BY SOURCE THEN INSERT VALUES (1)",
+    );
+    assert_eq!(
+        "Expected one of UPDATE or DELETE, found: INSERT",
+        res.unwrap_err().message
+    );
+}
+
+#[test]
+fn parse_mssql_create_procedure() {
+    let sql = "CREATE PROCEDURE dbo.p @a INT = 1, @b INT OUTPUT WITH RECOMPILE AS \
+               BEGIN SELECT CASE WHEN @a = 1 THEN 'it''s' END; BEGIN TRAN; SET @b = 2; END";
+    let canonical = "CREATE PROCEDURE dbo.p(@a int = 1, @b int OUTPUT) WITH RECOMPILE AS \
+                     BEGIN SELECT CASE WHEN @a = 1 THEN 'it''s' END; BEGIN TRAN; SET @b = 2; END";
+    match ms().one_statement_parses_to(sql, canonical) {
+        SQLStatement::SQLCreateFunction {
+            procedure,
+            args,
+            attributes,
+            body,
+            ..
+        } => {
+            assert!(procedure);
+            let args = args.unwrap();
+            assert_eq!(Some("@b".to_string()), args[1].name);
+            assert!(args[1].output);
+            assert_eq!(
+                vec![FunctionAttribute::With(vec!["RECOMPILE".to_string()])],
+                attributes
+            );
+            assert_eq!(
+                FunctionBody::Raw(
+                    "BEGIN SELECT CASE WHEN @a = 1 THEN 'it''s' END; BEGIN TRAN; SET @b = 2; END"
+                        .to_string()
+                ),
+                body
+            );
+        }
+        _ => unreachable!(),
+    }
+
+    // Without BEGIN, the body ends at the end of the statement
+    let statements = ms()
+        .parse_sql_statements("CREATE PROC p AS SELECT 1; SELECT 2")
+        .unwrap();
+    assert_eq!(2, statements.len());
+    assert_eq!("CREATE PROCEDURE p AS SELECT 1", statements[0].to_string());
+
+    // TRY/CATCH blocks, at the top level of the body or nested in a block
+    let body = "BEGIN TRY SELECT 1/0; END TRY BEGIN CATCH SELECT ERROR_MESSAGE(); END CATCH";
+    let statements = ms()
+        .parse_sql_statements(&format!("CREATE PROCEDURE p AS {}; SELECT 2", body))
+        .unwrap();
+    assert_eq!(2, statements.len());
+    match &statements[0] {
+        SQLStatement::SQLCreateFunction { body: b, .. } => {
+            assert_eq!(&FunctionBody::Raw(body.to_string()), b)
+        }
+        _ => unreachable!(),
+    }
+    ms().verified_stmt(&format!(
+        "CREATE PROCEDURE p AS BEGIN SET NOCOUNT ON; {}; END",
+        body
+    ));
+
+    let res = ms().parse_sql_statements("CREATE PROCEDURE p AS BEGIN SELECT 1");
+    assert_eq!("Expected END, found: EOF", res.unwrap_err().message);
+}
+
+#[test]
+fn parse_mssql_transactions() {
+    ms().one_statement_parses_to("BEGIN TRAN", "BEGIN");
+    ms().one_statement_parses_to("COMMIT TRAN", "COMMIT");
+    ms().one_statement_parses_to("ROLLBACK TRAN", "ROLLBACK");
+    let statements = ms()
+        .parse_sql_statements("BEGIN TRANSACTION; SELECT 1; COMMIT TRANSACTION")
+        .unwrap();
+    assert_eq!(3, statements.len());
+}
+
 fn ms() -> TestedDialects {
     TestedDialects {
         dialects: vec![Box::new(MsSqlDialect {})],
@@ -0,0 +1,269 @@
+#![warn(clippy::all)]
+//! Test SQL syntax specific to MySQL. The parser based on the generic dialect
+//! is also tested (on the inputs it can handle).
+
+use sqlparser::dialect::{GenericSqlDialect, MySqlDialect};
+use sqlparser::sqlast::*;
+use sqlparser::sqlparser::Parser;
+use sqlparser::test_utils::*;
+
+#[test]
+fn parse_mysql_identifiers() {
+    let sql = "SELECT `a b`, $foo1 FROM `db`.`table`";
+    let select = mysql().verified_only_select(sql);
+    assert_eq!(
+        &ASTNode::SQLIdentifier("`a b`".to_string(), Span::empty()),
+        expr_from_projection(&select.projection[0]),
+    );
+    assert_eq!(
+        &ASTNode::SQLIdentifier("$foo1".to_string(), Span::empty()),
+        expr_from_projection(&select.projection[1]),
+    );
+    match select.relation {
+        Some(TableFactor::Table { name, .. }) => {
+            assert_eq!(vec!["`db`", "`table`"], name.0);
+        }
+        _ => unreachable!(),
+    };
+}
+
+#[test]
+fn parse_mysql_strings() {
+    // Double-quoted strings are printed back with single quotes, and escape
+    // sequences other than escaped quotes are kept as written
+    let select = match mysql().one_statement_parses_to(
+        r#"SELECT "It's", 'a\'b\\c', 'd\ne', 'f\%g'"#,
+        r"SELECT 'It''s', 'a''b\\c', 'd\ne', 'f\%g'",
+    ) {
+        SQLStatement::SQLQuery(query) => match query.body {
+            SQLSetExpr::Select(select) => select,
+            _ => unreachable!(),
+        },
+        _ => unreachable!(),
+    };
+    let values: Vec<_> = select
+        .projection
+        .iter()
+        .map(|item| match expr_from_projection(item) {
+            ASTNode::SQLValue(Value::SingleQuotedString(s), _) => s.as_str(),
+            _ => unreachable!(),
+        })
+        .collect();
+    assert_eq!(vec!["It's", r"a'b\\c", r"d\ne", r"f\%g"], values);
+    mysql().verified_stmt(r"SELECT * FROM t WHERE b = 'x\\y' AND c = 'it''s\\'");
+
+    // Without MySQL's escapes, a backslash stands for itself
+    let select = generic().verified_only_select(r"SELECT 'a\b'");
+    assert_eq!(
+        &ASTNode::SQLValue(Value::SingleQuotedString("a\\b".to_string()), Span::empty()),
+        expr_from_projection(&select.projection[0]),
+    );
+}
+
+#[test]
+fn parse_mysql_hash_comments() {
+    let sql = "SELECT 1 # the answer\nFROM t";
+    let (statements, comments) =
+        Parser::parse_sql_with_comments(&MySqlDialect {}, sql.to_string()).unwrap();
+    assert_eq!("SELECT 1 FROM t", statements[0].to_string());
+    assert_eq!(1, comments.len());
+    assert_eq!(" the answer", comments[0].text);
+}
+
+#[test]
+fn parse_mysql_limit() {
+    let query = mysql_and_generic().verified_query("SELECT * FROM t LIMIT 5 OFFSET 10");
+    assert_eq!(
+        Some(Offset {
+            value: ASTNode::SQLValue(Value::Long(10), Span::empty()),
+            rows: false,
+            span: Span::empty(),
+        }),
+        query.offset
+    );
+    mysql().one_statement_parses_to(
+        "SELECT * FROM t LIMIT 10, 5",
+        "SELECT * FROM t LIMIT 5 OFFSET 10",
+    );
+    // Other dialects only have LIMIT ... OFFSET
+    let res = all_dialects().parse_sql_statements("SELECT * FROM t LIMIT 10, 5");
+    assert_eq!(
+        "Expected end of statement, found: ,",
+        res.unwrap_err().message
+    );
+}
+
+#[test]
+fn parse_mysql_operators() {
+    let select = mysql().verified_only_select("SELECT a DIV 2, a <=> b, a XOR b");
+    let ops: Vec<_> = select
+        .projection
+        .iter()
+        .map(|item| match expr_from_projection(item) {
+            ASTNode::SQLBinaryExpr { op, .. } => op.clone(),
+            _ => unreachable!(),
+        })
+        .collect();
+    assert_eq!(
+        vec![
+            SQLOperator::IntegerDivide,
+            SQLOperator::Spaceship,
+            SQLOperator::Xor
+        ],
+        ops
+    );
+
+    // XOR binds tighter than OR but looser than AND
+    let select = mysql().verified_only_select("SELECT a OR b XOR c AND d");
+    match expr_from_projection(&select.projection[0]) {
+        ASTNode::SQLBinaryExpr { op, right, .. } => {
+            assert_eq!(&SQLOperator::Or, op);
+            assert_eq!("b XOR c AND d", right.to_string());
+            match right.as_ref() {
+                ASTNode::SQLBinaryExpr { op, .. } => assert_eq!(&SQLOperator::Xor, op),
+                _ => unreachable!(),
+            }
+        }
+        _ => unreachable!(),
+    }
+
+    // In other dialects, DIV and XOR are ordinary words and `<=>` isn't an operator
+    all_dialects().one_statement_parses_to("SELECT a div FROM t", "SELECT a AS div FROM t");
+    all_dialects().one_statement_parses_to("SELECT a xor FROM t", "SELECT a AS xor FROM t");
+    let res = all_dialects().parse_sql_statements("SELECT a <=> b");
+    assert_eq!(
+        "Expected end of statement, found: <=>",
+        res.unwrap_err().message
+    );
+}
+
+#[test]
+fn parse_mysql_create_table() {
+    let sql = "CREATE TABLE `users` (\
+               `id` int NOT NULL AUTO_INCREMENT, \
+               `name` text, \
+               PRIMARY KEY (`id`)) \
+               ENGINE=InnoDB AUTO_INCREMENT=10 DEFAULT CHARSET=utf8mb4 COMMENT='people'";
+    match mysql().verified_stmt(sql) {
+        SQLStatement::SQLCreateTable {
+            columns,
+            table_options,
+            ..
+        } => {
+            assert_eq!(
+                vec![ColumnOption::NotNull, ColumnOption::MySqlAutoIncrement],
+                column_options(&columns[0])
+            );
+            assert!(columns[1].options.is_empty());
+            let options: Vec<_> = table_options
+                .iter()
+                .map(|o| (o.default, o.name.as_str(), o.value.to_string()))
+                .collect();
+            assert_eq!(
+                vec![
+                    (false, "ENGINE", "InnoDB".to_string()),
+                    (false, "AUTO_INCREMENT", "10".to_string()),
+                    (true, "CHARSET", "utf8mb4".to_string()),
+                    (false, "COMMENT", "'people'".to_string()),
+                ],
+                options
+            );
+        }
+        _ => unreachable!(),
+    }
+
+    mysql().one_statement_parses_to(
+        "CREATE TABLE t (a int) ENGINE = InnoDB, DEFAULT CHARACTER SET utf8",
+        "CREATE TABLE t (a int) ENGINE=InnoDB DEFAULT CHARACTER SET=utf8",
+    );
+}
+
+#[test]
+fn parse_mysql_insert_on_duplicate_key_update() {
+    let sql = "INSERT INTO t (a, b) VALUES (1, 2) ON DUPLICATE KEY UPDATE b = b + 1, a = 0";
+    match mysql_and_generic().verified_stmt(sql) {
+        SQLStatement::SQLInsert {
+            replace_into, on, ..
+        } => {
+            assert!(!replace_into);
+            match on {
+                Some(OnInsert::DuplicateKeyUpdate(assignments, _)) => {
+                    assert_eq!(
+                        vec!["b = b + 1", "a = 0"],
+                        assignments
+                            .iter()
+                            .map(ToString::to_string)
+                            .collect::<Vec<_>>()
+                    );
+                }
+                _ => unreachable!(),
+            }
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn parse_mysql_replace_into() {
+    match mysql_and_generic().verified_stmt("REPLACE INTO t VALUES (1, 'a')") {
+        SQLStatement::SQLInsert {
+            replace_into,
+            table_name,
+            on,
+            ..
+        } => {
+            assert!(replace_into);
+            assert_eq!("t", table_name.to_string());
+            assert_eq!(None, on);
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn parse_mysql_unreserved_keywords() {
+    // OFFSET isn't reserved in MySQL, so it can be used as a table alias
+    mysql().one_statement_parses_to(
+        "SELECT offset.a FROM t offset LIMIT 1",
+        "SELECT offset.a FROM t AS offset LIMIT 1",
+    );
+    let res = generic().parse_sql_statements("SELECT offset.a FROM t offset");
+    assert_eq!("Expected literal int, found: EOF", res.unwrap_err().message);
+}
+
+#[test]
+fn parse_mysql_placeholders() {
+    let sql = "INSERT INTO t (a, b) VALUES (?, ?) ON DUPLICATE KEY UPDATE b = ?";
+    let stmt = mysql_and_generic().verified_stmt(sql);
+    assert_eq!(vec!["?", "?", "?"], stmt.placeholders());
+
+    let stmt = mysql().one_statement_parses_to(
+        "SELECT * FROM t WHERE a = ? LIMIT ?, ?",
+        "SELECT * FROM t WHERE a = ? LIMIT ? OFFSET ?",
+    );
+    assert_eq!(3, stmt.placeholders().len());
+
+    // `:name` and `$name` are not parameters in MySQL
+    let select = mysql().verified_only_select("SELECT $a FROM t");
+    assert_eq!(
+        &ASTNode::SQLIdentifier("$a".to_string(), Span::empty()),
+        expr_from_projection(only(&select.projection))
+    );
+    assert!(mysql().parse_sql_statements("SELECT :a").is_err());
+}
+
+fn mysql() -> TestedDialects {
+    TestedDialects {
+        dialects: vec![Box::new(MySqlDialect {})],
+    }
+}
+fn generic() -> TestedDialects {
+    TestedDialects {
+        dialects: vec![Box::new(GenericSqlDialect {})],
+    }
+}
+fn mysql_and_generic() -> TestedDialects {
+    TestedDialects {
+        dialects: vec![Box::new(MySqlDialect {}), Box::new(GenericSqlDialect {})],
+    }
+}
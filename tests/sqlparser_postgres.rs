@@ -29,6 +29,7 @@ fn parse_create_table_with_defaults() {
             external: false,
             file_format: None,
             location: None,
+            ..
         } => {
             assert_eq!("public.customer", name.to_string());
             assert_eq!(10, columns.len());
@@ -37,32 +38,35 @@ fn parse_create_table_with_defaults() {
             let c_name = &columns[0];
             assert_eq!("customer_id", c_name.name);
             assert_eq!(SQLType::Int, c_name.data_type);
-            assert_eq!(false, c_name.allow_null);
+            assert_eq!(ColumnOption::NotNull, column_options(c_name)[1]);
 
             let c_lat = &columns[1];
             assert_eq!("store_id", c_lat.name);
             assert_eq!(SQLType::SmallInt, c_lat.data_type);
-            assert_eq!(false, c_lat.allow_null);
+            assert_eq!(vec![ColumnOption::NotNull], column_options(c_lat));
 
             let c_lng = &columns[2];
             assert_eq!("first_name", c_lng.name);
             assert_eq!(SQLType::Varchar(Some(45)), c_lng.data_type);
-            assert_eq!(false, c_lng.allow_null);
+            assert_eq!(vec![ColumnOption::NotNull], column_options(c_lng));
 
             assert_eq!(
                 with_options,
                 vec![
                     SQLOption {
                         name: "fillfactor".into(),
-                        value: Value::Long(20)
+                        value: Value::Long(20),
+                        span: Span::empty()
                     },
                     SQLOption {
                         name: "user_catalog_table".into(),
-                        value: Value::Boolean(true)
+                        value: Value::Boolean(true),
+                        span: Span::empty()
                     },
                     SQLOption {
                         name: "autovacuum_vacuum_threshold".into(),
-                        value: Value::Long(100)
+                        value: Value::Long(100),
+                        span: Span::empty()
                     },
                 ]
             );
@@ -96,6 +100,7 @@ fn parse_create_table_from_pg_dump() {
             external: false,
             file_format: None,
             location: None,
+            ..
         } => {
             assert_eq!("public.customer", name.to_string());
             assert!(constraints.is_empty());
@@ -103,30 +108,36 @@ fn parse_create_table_from_pg_dump() {
             let c_customer_id = &columns[0];
             assert_eq!("customer_id", c_customer_id.name);
             assert_eq!(SQLType::Int, c_customer_id.data_type);
-            assert_eq!(false, c_customer_id.allow_null);
+            assert_eq!(ColumnOption::NotNull, column_options(c_customer_id)[1]);
 
             let c_store_id = &columns[1];
             assert_eq!("store_id", c_store_id.name);
             assert_eq!(SQLType::SmallInt, c_store_id.data_type);
-            assert_eq!(false, c_store_id.allow_null);
+            assert_eq!(vec![ColumnOption::NotNull], column_options(c_store_id));
 
             let c_first_name = &columns[2];
             assert_eq!("first_name", c_first_name.name);
             assert_eq!(SQLType::Varchar(Some(45)), c_first_name.data_type);
-            assert_eq!(false, c_first_name.allow_null);
+            assert_eq!(vec![ColumnOption::NotNull], column_options(c_first_name));
 
             let c_create_date1 = &columns[8];
             assert_eq!(
-                Some(ASTNode::SQLCast {
-                    expr: Box::new(ASTNode::SQLCast {
-                        expr: Box::new(ASTNode::SQLValue(Value::SingleQuotedString(
-                            "now".to_string()
-                        ))),
-                        data_type: SQLType::Text
+                vec![
+                    ColumnOption::Default(ASTNode::SQLCast {
+                        expr: Box::new(ASTNode::SQLCast {
+                            expr: Box::new(ASTNode::SQLValue(
+                                Value::SingleQuotedString("now".to_string()),
+                                Span::empty()
+                            )),
+                            data_type: SQLType::Text,
+                            span: Span::empty()
+                        }),
+                        data_type: SQLType::Date,
+                        span: Span::empty(),
                     }),
-                    data_type: SQLType::Date
-                }),
-                c_create_date1.default
+                    ColumnOption::NotNull,
+                ],
+                column_options(c_create_date1)
             );
 
             let c_release_year = &columns[10];
@@ -162,6 +173,7 @@ fn parse_create_table_with_inherit() {
             external: false,
             file_format: None,
             location: None,
+            ..
         } => {
             assert_eq!("bazaar.settings", name.to_string());
             assert!(constraints.is_empty());
@@ -169,16 +181,19 @@ fn parse_create_table_with_inherit() {
             let c_name = &columns[0];
             assert_eq!("settings_id", c_name.name);
             assert_eq!(SQLType::Uuid, c_name.data_type);
-            assert_eq!(false, c_name.allow_null);
-            assert_eq!(true, c_name.is_primary);
-            assert_eq!(false, c_name.is_unique);
+            assert_eq!(
+                ColumnOption::Unique { is_primary: true },
+                column_options(c_name)[0]
+            );
+            assert_eq!(ColumnOption::NotNull, column_options(c_name)[2]);
 
             let c_name = &columns[1];
             assert_eq!("user_id", c_name.name);
             assert_eq!(SQLType::Uuid, c_name.data_type);
-            assert_eq!(true, c_name.allow_null);
-            assert_eq!(false, c_name.is_primary);
-            assert_eq!(true, c_name.is_unique);
+            assert_eq!(
+                vec![ColumnOption::Unique { is_primary: false }],
+                column_options(c_name)
+            );
 
             assert_eq!(with_options, vec![]);
         }
@@ -213,6 +228,469 @@ PHP	₱ USD $
     //assert_eq!(sql, ast.to_string());
 }
 
+#[test]
+fn parse_numbered_placeholders() {
+    let sql = "SELECT a FROM t WHERE b = $1 AND c IN ($2, $1) ORDER BY a LIMIT $3 OFFSET $4";
+    let stmt = pg_and_generic().verified_stmt(sql);
+    assert_eq!(vec!["$1", "$2", "$1", "$3", "$4"], stmt.placeholders());
+    match stmt {
+        SQLStatement::SQLQuery(query) => {
+            assert_eq!(
+                Some(ASTNode::SQLPlaceholder("$3".to_string(), Span::empty())),
+                query.limit
+            );
+            assert_eq!(
+                ASTNode::SQLPlaceholder("$4".to_string(), Span::empty()),
+                query.offset.unwrap().value
+            );
+        }
+        _ => unreachable!(),
+    }
+
+    let stmt = pg().verified_stmt("UPDATE t SET a = $2 WHERE b = $1");
+    assert_eq!(vec!["$2", "$1"], stmt.placeholders());
+    // `$` can still be part of an identifier
+    pg().verified_stmt("SELECT a$1 FROM t");
+
+    let res = pg().parse_sql_statements("SELECT ?");
+    assert_eq!("Expected an expression, found: ?", res.unwrap_err().message);
+}
+
+#[test]
+fn parse_insert_on_conflict() {
+    let sql = "INSERT INTO t (a, b) VALUES (1, 2) \
+               ON CONFLICT (a) DO UPDATE SET b = excluded.b WHERE t.b < excluded.b";
+    match pg_and_generic().verified_stmt(sql) {
+        SQLStatement::SQLInsert { on, returning, .. } => {
+            assert_eq!(
+                Some(OnInsert::OnConflict(OnConflict {
+                    conflict_target: Some(ConflictTarget::Columns {
+                        columns: vec!["a".to_string()],
+                        selection: None,
+                    }),
+                    action: OnConflictAction::DoUpdate {
+                        assignments: vec![SQLAssignment {
+                            id: vec!["b".to_string()],
+                            value: ASTNode::SQLCompoundIdentifier(
+                                vec!["excluded".to_string(), "b".to_string()],
+                                Span::empty(),
+                            ),
+                            span: Span::empty(),
+                        }],
+                        selection: Some(pg().verified_expr("t.b < excluded.b")),
+                    },
+                    span: Span::empty(),
+                })),
+                on
+            );
+            assert!(returning.is_empty());
+        }
+        _ => unreachable!(),
+    }
+
+    match pg().verified_stmt("INSERT INTO t VALUES (1) ON CONFLICT ON CONSTRAINT t_pkey DO NOTHING")
+    {
+        SQLStatement::SQLInsert {
+            on: Some(OnInsert::OnConflict(on_conflict)),
+            ..
+        } => {
+            assert_eq!(
+                Some(ConflictTarget::OnConstraint(SQLObjectName(vec![
+                    "t_pkey".to_string()
+                ]))),
+                on_conflict.conflict_target
+            );
+            assert_eq!(OnConflictAction::DoNothing, on_conflict.action);
+        }
+        _ => unreachable!(),
+    }
+    pg().verified_stmt("INSERT INTO t SELECT * FROM u ON CONFLICT DO NOTHING");
+
+    // A conflict target can infer a partial unique index
+    match pg().verified_stmt(
+        "INSERT INTO t VALUES (1, 2) ON CONFLICT (a, b) WHERE NOT deleted DO NOTHING",
+    ) {
+        SQLStatement::SQLInsert {
+            on: Some(OnInsert::OnConflict(on_conflict)),
+            ..
+        } => {
+            assert_eq!(
+                Some(ConflictTarget::Columns {
+                    columns: vec!["a".to_string(), "b".to_string()],
+                    selection: Some(pg().verified_expr("NOT deleted")),
+                }),
+                on_conflict.conflict_target
+            );
+            assert_eq!(OnConflictAction::DoNothing, on_conflict.action);
+        }
+        _ => unreachable!(),
+    }
+    pg().verified_stmt(
+        "INSERT INTO t VALUES (1) ON CONFLICT (a) WHERE b > 0 DO UPDATE SET a = 2 WHERE t.c = 1",
+    );
+
+    let res =
+        pg().parse_sql_statements("INSERT INTO t VALUES (1) ON CONFLICT (a) UPDATE SET a = 1");
+    assert_eq!("Expected DO, found: UPDATE", res.unwrap_err().message);
+}
+
+#[test]
+fn parse_returning() {
+    let stmt = pg_and_generic().verified_stmt(
+        "INSERT INTO t (a) VALUES (1) ON CONFLICT DO NOTHING RETURNING id, a + 1 AS b",
+    );
+    match stmt {
+        SQLStatement::SQLInsert { returning, .. } => assert_eq!(
+            vec![
+                SQLSelectItem::UnnamedExpression(ASTNode::SQLIdentifier(
+                    "id".to_string(),
+                    Span::empty()
+                )),
+                SQLSelectItem::ExpressionWithAlias {
+                    expr: pg().verified_expr("a + 1"),
+                    alias: "b".to_string(),
+                    span: Span::empty(),
+                },
+            ],
+            returning
+        ),
+        _ => unreachable!(),
+    }
+
+    match pg().verified_stmt("UPDATE t SET a = 1 WHERE b = 2 RETURNING *") {
+        SQLStatement::SQLUpdate { returning, .. } => {
+            assert_eq!(vec![SQLSelectItem::Wildcard(Span::empty())], returning)
+        }
+        _ => unreachable!(),
+    }
+    match pg().verified_stmt("DELETE FROM t WHERE a = 1 RETURNING t.*") {
+        SQLStatement::SQLDelete { returning, .. } => assert_eq!(
+            vec![SQLSelectItem::QualifiedWildcard(
+                SQLObjectName(vec!["t".to_string()]),
+                Span::empty()
+            )],
+            returning
+        ),
+        _ => unreachable!(),
+    }
+    // RETURNING can't be taken for an alias
+    pg().one_statement_parses_to(
+        "DELETE FROM t RETURNING a id",
+        "DELETE FROM t RETURNING a AS id",
+    );
+}
+
+#[test]
+fn parse_delete_using() {
+    let sql = "DELETE FROM t AS a USING u AS b LEFT JOIN v ON b.id = v.id \
+               WHERE a.id = b.id AND v.id IS NULL RETURNING a.id";
+    match pg_and_generic().verified_stmt(sql) {
+        SQLStatement::SQLDelete {
+            table_name,
+            alias,
+            using,
+            joins,
+            selection,
+            returning,
+            ..
+        } => {
+            assert_eq!("t", table_name.to_string());
+            assert_eq!(Some("a".to_string()), alias);
+            assert_eq!("u AS b", using.unwrap().to_string());
+            assert_eq!(" LEFT JOIN v ON b.id = v.id", only(&joins).to_string());
+            assert_eq!(
+                pg().verified_expr("a.id = b.id AND v.id IS NULL"),
+                selection.unwrap()
+            );
+            assert_eq!(1, returning.len());
+        }
+        _ => unreachable!(),
+    }
+    pg().one_statement_parses_to(
+        "DELETE FROM t a USING u, v WHERE a.id = u.id",
+        "DELETE FROM t AS a USING u, v WHERE a.id = u.id",
+    );
+}
+
+#[test]
+fn parse_create_sequence() {
+    let sql = "CREATE SEQUENCE IF NOT EXISTS public.customer_id_seq AS bigint \
+               INCREMENT BY 1 MINVALUE 1 NO MAXVALUE START WITH 100 CACHE 20 NO CYCLE \
+               OWNED BY public.customer.customer_id";
+    match pg_and_generic().verified_stmt(sql) {
+        SQLStatement::SQLCreateSequence {
+            name,
+            if_not_exists,
+            options,
+            ..
+        } => {
+            assert_eq!("public.customer_id_seq", name.to_string());
+            assert!(if_not_exists);
+            let number = |n| ASTNode::SQLValue(Value::Long(n), Span::empty());
+            assert_eq!(
+                vec![
+                    SequenceOption::As {
+                        data_type: SQLType::BigInt
+                    },
+                    SequenceOption::IncrementBy { value: number(1) },
+                    SequenceOption::MinValue {
+                        value: Some(number(1))
+                    },
+                    SequenceOption::MaxValue { value: None },
+                    SequenceOption::StartWith { value: number(100) },
+                    SequenceOption::Cache { value: number(20) },
+                    SequenceOption::NoCycle,
+                    SequenceOption::OwnedBy {
+                        column: Some(SQLObjectName(vec![
+                            "public".to_string(),
+                            "customer".to_string(),
+                            "customer_id".to_string()
+                        ]))
+                    },
+                ],
+                options
+            );
+        }
+        _ => unreachable!(),
+    }
+
+    // `BY` and `WITH` are optional
+    pg().one_statement_parses_to(
+        "CREATE SEQUENCE s INCREMENT -1 START 10 CYCLE OWNED BY NONE",
+        "CREATE SEQUENCE s INCREMENT BY - 1 START WITH 10 CYCLE OWNED BY NONE",
+    );
+    pg().verified_stmt("CREATE SEQUENCE s");
+
+    // A column defaulting to the next value of the sequence
+    pg().one_statement_parses_to(
+        "CREATE TABLE t (id bigint DEFAULT nextval('s'::regclass) NOT NULL)",
+        "CREATE TABLE t (id bigint DEFAULT nextval(CAST('s' AS regclass)) NOT NULL)",
+    );
+    pg().verified_stmt("CREATE TABLE t (id bigint DEFAULT nextval('s') NOT NULL)");
+}
+
+#[test]
+fn parse_alter_sequence() {
+    match pg().verified_stmt("ALTER SEQUENCE IF EXISTS s RESTART WITH 1000 NO MINVALUE") {
+        SQLStatement::SQLAlterSequence {
+            name,
+            if_exists,
+            options,
+            ..
+        } => {
+            assert_eq!("s", name.to_string());
+            assert!(if_exists);
+            assert_eq!(
+                vec![
+                    SequenceOption::Restart {
+                        value: Some(ASTNode::SQLValue(Value::Long(1000), Span::empty()))
+                    },
+                    SequenceOption::MinValue { value: None },
+                ],
+                options
+            );
+        }
+        _ => unreachable!(),
+    }
+    pg().one_statement_parses_to(
+        "ALTER SEQUENCE s RESTART 5",
+        "ALTER SEQUENCE s RESTART WITH 5",
+    );
+    pg().verified_stmt("ALTER SEQUENCE s RESTART CACHE 1");
+
+    let res = pg().parse_sql_statements("ALTER SEQUENCE s");
+    assert_eq!(
+        "Expected a sequence option, found: EOF",
+        res.unwrap_err().message
+    );
+    let res = pg().parse_sql_statements("ALTER INDEX i");
+    assert_eq!(
+        "Expected one of TABLE or SEQUENCE, found: INDEX",
+        res.unwrap_err().message
+    );
+}
+
+#[test]
+fn parse_create_function() {
+    let sql = "CREATE OR REPLACE FUNCTION public.add(a int, INOUT b int = 1, OUT int) \
+               RETURNS int LANGUAGE plpgsql IMMUTABLE STRICT \
+               AS $$BEGIN RETURN a + b; END;$$";
+    match pg().verified_stmt(sql) {
+        SQLStatement::SQLCreateFunction {
+            or_replace,
+            procedure,
+            name,
+            args,
+            returns,
+            attributes,
+            body,
+            ..
+        } => {
+            assert!(or_replace);
+            assert!(!procedure);
+            assert_eq!("public.add", name.to_string());
+            assert_eq!(
+                Some(vec![
+                    FunctionArg {
+                        mode: None,
+                        name: Some("a".to_string()),
+                        data_type: SQLType::Int,
+                        default: None,
+                        output: false,
+                        span: Span::empty(),
+                    },
+                    FunctionArg {
+                        mode: Some(ArgMode::InOut),
+                        name: Some("b".to_string()),
+                        data_type: SQLType::Int,
+                        default: Some(ASTNode::SQLValue(Value::Long(1), Span::empty())),
+                        output: false,
+                        span: Span::empty(),
+                    },
+                    FunctionArg {
+                        mode: Some(ArgMode::Out),
+                        name: None,
+                        data_type: SQLType::Int,
+                        default: None,
+                        output: false,
+                        span: Span::empty(),
+                    },
+                ]),
+                args
+            );
+            assert_eq!(Some(FunctionReturns::Type(SQLType::Int)), returns);
+            assert_eq!(
+                vec![
+                    FunctionAttribute::Language("plpgsql".to_string()),
+                    FunctionAttribute::Immutable,
+                    FunctionAttribute::Strict,
+                ],
+                attributes
+            );
+            assert_eq!(
+                FunctionBody::DollarQuoted(DollarQuotedString {
+                    value: "BEGIN RETURN a + b; END;".to_string(),
+                    tag: None,
+                }),
+                body
+            );
+        }
+        _ => unreachable!(),
+    }
+
+    // pg_dump puts the body before the attributes
+    pg().one_statement_parses_to(
+        "CREATE FUNCTION f() RETURNS SETOF record AS $body$ SELECT $$a$$ $body$ LANGUAGE sql",
+        "CREATE FUNCTION f() RETURNS SETOF record LANGUAGE sql AS $body$ SELECT $$a$$ $body$",
+    );
+    pg().verified_stmt(
+        "CREATE FUNCTION f(double, text = 'x') RETURNS TABLE (a int, b text) \
+         RETURNS NULL ON NULL INPUT SECURITY DEFINER PARALLEL SAFE COST 100 ROWS 10 \
+         AS 'SELECT 1, ''x'''",
+    );
+    pg().verified_stmt("CREATE FUNCTION f RETURNS NULL ON NULL INPUT AS 'x'");
+    pg().one_statement_parses_to(
+        "CREATE FUNCTION f(a int DEFAULT 1) AS ''",
+        "CREATE FUNCTION f(a int = 1) AS ''",
+    );
+    pg().verified_stmt("CREATE PROCEDURE p(a int) LANGUAGE sql AS $$INSERT INTO t VALUES (a)$$");
+    match pg().verified_stmt(
+        "CREATE FUNCTION f(VARIADIC arr int[]) RETURNS int AS 'SELECT array_length($1, 1)'",
+    ) {
+        SQLStatement::SQLCreateFunction { args, .. } => assert_eq!(
+            Some(vec![FunctionArg {
+                mode: Some(ArgMode::Variadic),
+                name: Some("arr".to_string()),
+                data_type: SQLType::Array(Box::new(SQLType::Int)),
+                default: None,
+                output: false,
+                span: Span::empty(),
+            }]),
+            args
+        ),
+        _ => unreachable!(),
+    }
+    pg().verified_stmt("CREATE FUNCTION f(VARIADIC text[][]) AS ''");
+
+    let res = pg().parse_sql_statements("CREATE FUNCTION f() RETURNS int LANGUAGE sql");
+    assert_eq!(
+        "Expected AS or a function attribute, found: EOF",
+        res.unwrap_err().message
+    );
+    let res = pg().parse_sql_statements("CREATE FUNCTION f() PARALLEL UNKNOWN AS ''");
+    assert_eq!(
+        "Expected one of UNSAFE or RESTRICTED or SAFE, found: UNKNOWN",
+        res.unwrap_err().message
+    );
+}
+
+#[test]
+fn parse_dollar_quoted_strings() {
+    let select = pg().verified_only_select(
+        "SELECT $$it's$$, $tag$a $$ b$tag$, $$$$, $fn$\nline$fn$ AS b FROM t WHERE a = $1",
+    );
+    let dollar_quoted = |value: &str, tag: Option<&str>| {
+        ASTNode::SQLValue(
+            Value::DollarQuotedString(DollarQuotedString {
+                value: value.to_string(),
+                tag: tag.map(|t| t.to_string()),
+            }),
+            Span::empty(),
+        )
+    };
+    assert_eq!(
+        &dollar_quoted("it's", None),
+        expr_from_projection(&select.projection[0])
+    );
+    assert_eq!(
+        &dollar_quoted("a $$ b", Some("tag")),
+        expr_from_projection(&select.projection[1])
+    );
+    assert_eq!(
+        &dollar_quoted("", None),
+        expr_from_projection(&select.projection[2])
+    );
+    assert_eq!(
+        SQLSelectItem::ExpressionWithAlias {
+            expr: dollar_quoted("\nline", Some("fn")),
+            alias: "b".to_string(),
+            span: Span::empty(),
+        },
+        select.projection[3]
+    );
+    assert_eq!(
+        Some(ASTNode::SQLBinaryExpr {
+            left: Box::new(ASTNode::SQLIdentifier("a".to_string(), Span::empty())),
+            op: SQLOperator::Eq,
+            right: Box::new(ASTNode::SQLPlaceholder("$1".to_string(), Span::empty())),
+            span: Span::empty(),
+        }),
+        select.selection
+    );
+
+    // A `$` not followed by a tag and another `$` is still a parameter
+    pg().verified_stmt("SELECT $a, $1 FROM t");
+    pg().verified_stmt("INSERT INTO t VALUES ($1, $$a$$)");
+
+    let res = pg().parse_sql_statements("SELECT $tag$a$");
+    assert_eq!(
+        "Unexpected EOF while in a dollar-quoted string",
+        res.unwrap_err().message
+    );
+    // Other dialects don't have dollar-quoted strings, and `$` ends a
+    // parameter name
+    let generic = TestedDialects {
+        dialects: vec![Box::new(GenericSqlDialect {})],
+    };
+    let res = generic.parse_sql_statements("SELECT $$a$$");
+    assert_eq!("Expected an expression, found: $", res.unwrap_err().message);
+    let res = generic.parse_sql_statements("SELECT $a$");
+    assert_eq!(
+        "Expected end of statement, found: $",
+        res.unwrap_err().message
+    );
+}
+
 fn pg() -> TestedDialects {
     TestedDialects {
         dialects: vec![Box::new(PostgreSqlDialect {})],
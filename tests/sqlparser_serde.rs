@@ -0,0 +1,111 @@
+#![warn(clippy::all)]
+#![cfg(feature = "serde")]
+//! Test the `serde` feature: the AST round-trips through JSON, and the
+//! JSON shape documented in `sqlparser::sqlast` doesn't change by accident.
+
+use serde_json::json;
+use sqlparser::dialect::{Dialect, GenericSqlDialect, PostgreSqlDialect};
+use sqlparser::sqlast::*;
+use sqlparser::sqlparser::Parser;
+
+#[test]
+fn roundtrip_statements() {
+    let sqls = [
+        "SELECT DISTINCT a, b.* FROM t AS x (c) JOIN u USING(id) WHERE a BETWEEN 1 AND 1.5 ORDER BY a DESC LIMIT 5",
+        "WITH cte (x) AS (SELECT 1) SELECT * FROM cte UNION ALL SELECT 2 ORDER BY 1 OFFSET 1 ROWS FETCH FIRST 10 PERCENT ROWS WITH TIES",
+        "SELECT CASE WHEN a IS NULL THEN N'n' ELSE X'ff' END, CAST(b AS numeric(10,2)), count(*) OVER (PARTITION BY c ROWS BETWEEN 1 PRECEDING AND CURRENT ROW) FROM t",
+        "SELECT DATE '2019-01-01', TIMESTAMP '2019-01-01 00:00:00', true, NULL, 'it''s'",
+        "INSERT INTO t (a, b) VALUES (1, 2), (3, 4)",
+        "UPDATE t SET a = 1 WHERE b NOT IN (SELECT c FROM u)",
+        "DELETE FROM t WHERE EXISTS (SELECT 1)",
+        "CREATE TABLE t (a int NOT NULL, b character varying(10) DEFAULT 'x', CONSTRAINT fk FOREIGN KEY (b) REFERENCES u(c))",
+        "CREATE MATERIALIZED VIEW v WITH (foo = 'bar') AS SELECT 1",
+        "ALTER TABLE t ADD CONSTRAINT c CHECK (a > 0)",
+        "DROP TABLE IF EXISTS t, u CASCADE",
+        "MERGE INTO t AS tgt USING s ON tgt.id = s.id WHEN MATCHED AND s.deleted THEN DELETE \
+         WHEN MATCHED THEN UPDATE SET a = s.a WHEN NOT MATCHED THEN INSERT (id, a) VALUES (s.id, s.a)",
+        "CREATE SEQUENCE IF NOT EXISTS public.s AS bigint INCREMENT BY 1 MINVALUE 1 NO MAXVALUE \
+         START WITH 100 CACHE 20 NO CYCLE OWNED BY public.t.id",
+        "ALTER SEQUENCE s RESTART WITH 1",
+    ];
+    for sql in &sqls {
+        roundtrip(&GenericSqlDialect {}, sql);
+    }
+    let pg_sqls = [
+        "INSERT INTO t (a) VALUES (1) ON CONFLICT (a) DO UPDATE SET b = excluded.b WHERE t.b < excluded.b RETURNING id, a + 1 AS b",
+        "INSERT INTO t VALUES (1) ON CONFLICT ON CONSTRAINT t_pkey DO NOTHING",
+        "CREATE OR REPLACE FUNCTION public.add(a int, INOUT b int = 1, OUT int, VARIADIC c int[]) \
+         RETURNS int LANGUAGE plpgsql IMMUTABLE STRICT AS $$BEGIN RETURN a + b; END;$$",
+        "CREATE PROCEDURE p(a int) LANGUAGE sql AS $body$INSERT INTO t VALUES (a)$body$",
+        "SELECT $$it's$$, $tag$a$$b$tag$",
+    ];
+    for sql in &pg_sqls {
+        roundtrip(&PostgreSqlDialect {}, sql);
+    }
+}
+
+#[test]
+fn roundtrip_copy() {
+    let sql = "COPY t (a, b) FROM stdin;\n1\t\\N\n\\.";
+    let statements = Parser::parse_sql(&PostgreSqlDialect {}, sql.to_string()).unwrap();
+    let json = serde_json::to_string(&statements).unwrap();
+    let parsed: Vec<SQLStatement> = serde_json::from_str(&json).unwrap();
+    assert_eq!(statements, parsed);
+}
+
+#[test]
+fn json_shape() {
+    let expr = ASTNode::SQLBinaryExpr {
+        left: Box::new(ASTNode::SQLIdentifier("a".to_string(), Span::empty())),
+        op: SQLOperator::Plus,
+        right: Box::new(ASTNode::SQLValue(Value::Long(1), Span::empty())),
+        span: Span::empty(),
+    };
+    let span = json!({
+        "start": { "offset": 0, "line": 0, "column": 0 },
+        "end": { "offset": 0, "line": 0, "column": 0 }
+    });
+    assert_eq!(
+        json!({
+            "SQLBinaryExpr": {
+                "left": { "SQLIdentifier": ["a", span] },
+                "op": "Plus",
+                "right": { "SQLValue": [{ "Long": 1 }, span] },
+                "span": span
+            }
+        }),
+        serde_json::to_value(&expr).unwrap()
+    );
+    assert_eq!(
+        json!({ "Custom": ["public", "my_type"] }),
+        serde_json::to_value(SQLType::Custom(SQLObjectName(vec![
+            "public".to_string(),
+            "my_type".to_string()
+        ])))
+        .unwrap()
+    );
+    assert_eq!(
+        json!({ "Double": 1.5 }),
+        serde_json::to_value(Value::Double(1.5.into())).unwrap()
+    );
+    assert_eq!(
+        json!({ "DollarQuotedString": { "value": "a", "tag": "body" } }),
+        serde_json::to_value(Value::DollarQuotedString(DollarQuotedString {
+            value: "a".to_string(),
+            tag: Some("body".to_string())
+        }))
+        .unwrap()
+    );
+}
+
+/// Serialize the statement parsed from `sql` to JSON and back, checking that
+/// nothing, including the spans, is lost along the way.
+fn roundtrip(dialect: &dyn Dialect, sql: &str) {
+    let statements = Parser::parse_sql(dialect, sql.to_string()).unwrap();
+    let json = serde_json::to_string(&statements).unwrap();
+    let parsed: Vec<SQLStatement> = serde_json::from_str(&json).unwrap();
+    assert_eq!(statements, parsed);
+    // `Span`s always compare equal, so compare the serialized forms as well
+    assert_eq!(json, serde_json::to_string(&parsed).unwrap());
+    assert_eq!(sql, parsed[0].to_string());
+}
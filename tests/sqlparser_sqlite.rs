@@ -0,0 +1,233 @@
+#![warn(clippy::all)]
+//! Test SQL syntax specific to SQLite. The parser based on the generic
+//! dialect is also tested (on the inputs it can handle).
+
+use sqlparser::dialect::{GenericSqlDialect, SQLiteDialect};
+use sqlparser::sqlast::*;
+use sqlparser::test_utils::*;
+
+#[test]
+fn parse_sqlite_delimited_identifiers() {
+    let select = sqlite().verified_only_select("SELECT [a b], `c`, \"d\" FROM [t]");
+    assert_eq!(
+        vec!["[a b]", "`c`", "\"d\""],
+        select
+            .projection
+            .iter()
+            .map(|item| expr_from_projection(item).to_string())
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn parse_sqlite_create_table() {
+    let sql = "CREATE TABLE t (\
+               id integer PRIMARY KEY AUTOINCREMENT, \
+               a UNSIGNED BIG INT NOT NULL, \
+               b NVARCHAR(100), \
+               c DECIMAL(10,5), \
+               d VARYING CHARACTER(255) DEFAULT 'x', \
+               e DATETIME) \
+               WITHOUT ROWID";
+    let canonical = "CREATE TABLE t (\
+                     id int PRIMARY KEY AUTOINCREMENT, \
+                     a UNSIGNED BIG INT NOT NULL, \
+                     b NVARCHAR(100), \
+                     c numeric(10,5), \
+                     d VARYING CHARACTER(255) DEFAULT 'x', \
+                     e DATETIME) \
+                     WITHOUT ROWID";
+    match sqlite().one_statement_parses_to(sql, canonical) {
+        SQLStatement::SQLCreateTable {
+            columns,
+            without_rowid,
+            ..
+        } => {
+            assert!(without_rowid);
+            assert_eq!(
+                vec![
+                    ColumnOption::Unique { is_primary: true },
+                    ColumnOption::SqliteAutoincrement
+                ],
+                column_options(&columns[0])
+            );
+            assert_eq!(SQLType::Int, columns[0].data_type);
+            assert_eq!(
+                SQLType::FreeForm(
+                    vec!["UNSIGNED".to_string(), "BIG".to_string(), "INT".to_string()],
+                    vec![]
+                ),
+                columns[1].data_type
+            );
+            assert_eq!(vec![ColumnOption::NotNull], column_options(&columns[1]));
+            assert_eq!(
+                SQLType::FreeForm(vec!["NVARCHAR".to_string()], vec![100]),
+                columns[2].data_type
+            );
+            assert_eq!(SQLType::Decimal(Some(10), Some(5)), columns[3].data_type);
+            assert_eq!(
+                SQLType::FreeForm(
+                    vec!["VARYING".to_string(), "CHARACTER".to_string()],
+                    vec![255]
+                ),
+                columns[4].data_type
+            );
+            assert_eq!(
+                SQLType::Custom(SQLObjectName(vec!["DATETIME".to_string()])),
+                columns[5].data_type
+            );
+        }
+        _ => unreachable!(),
+    }
+    // Other dialects only accept the types they know
+    let generic = TestedDialects {
+        dialects: vec![Box::new(GenericSqlDialect {})],
+    };
+    let res = generic.parse_sql_statements("CREATE TABLE t (a INT foo)");
+    assert_eq!(
+        "Expected ',' or ')' after column definition, found: foo",
+        res.unwrap_err().message
+    );
+    let res = all_dialects().parse_sql_statements("CREATE TABLE t (a UNSIGNED BIG INT)");
+    assert_eq!(
+        "Expected ',' or ')' after column definition, found: BIG",
+        res.unwrap_err().message
+    );
+
+    // The type can be omitted
+    match sqlite().verified_stmt("CREATE TABLE t (id int PRIMARY KEY AUTOINCREMENT, x, y NOT NULL)")
+    {
+        SQLStatement::SQLCreateTable { columns, .. } => {
+            assert_eq!(SQLType::Unspecified, columns[1].data_type);
+            assert!(columns[1].options.is_empty());
+            assert_eq!(SQLType::Unspecified, columns[2].data_type);
+            assert_eq!(vec![ColumnOption::NotNull], column_options(&columns[2]));
+        }
+        _ => unreachable!(),
+    }
+    sqlite().verified_stmt("ALTER TABLE t ADD COLUMN z");
+    // Other dialects require it
+    let res = all_dialects().parse_sql_statements("CREATE TABLE t (x)");
+    assert_eq!(
+        "Expected a data type name, found: )",
+        res.unwrap_err().message
+    );
+}
+
+#[test]
+fn parse_sqlite_insert_or() {
+    for (sql, expected) in &[
+        (
+            "INSERT OR REPLACE INTO t VALUES (1)",
+            SQLiteOnConflict::Replace,
+        ),
+        (
+            "INSERT OR IGNORE INTO t VALUES (1)",
+            SQLiteOnConflict::Ignore,
+        ),
+        (
+            "INSERT OR ROLLBACK INTO t VALUES (1)",
+            SQLiteOnConflict::Rollback,
+        ),
+        ("INSERT OR ABORT INTO t VALUES (1)", SQLiteOnConflict::Abort),
+        ("INSERT OR FAIL INTO t VALUES (1)", SQLiteOnConflict::Fail),
+    ] {
+        match sqlite_and_generic().verified_stmt(sql) {
+            SQLStatement::SQLInsert { or, .. } => assert_eq!(Some(expected), or.as_ref()),
+            _ => unreachable!(),
+        }
+    }
+    let res = sqlite().parse_sql_statements("INSERT OR UPDATE INTO t VALUES (1)");
+    assert_eq!(
+        "Expected one of ROLLBACK or ABORT or FAIL or IGNORE or REPLACE, found: UPDATE",
+        res.unwrap_err().message
+    );
+}
+
+#[test]
+fn parse_sqlite_placeholders() {
+    let sql = "SELECT ?, ?2, :name, @name, $name, a$b FROM t";
+    assert_eq!(
+        vec!["?", "?2", ":name", "@name", "$name"],
+        sqlite().verified_stmt(sql).placeholders()
+    );
+    let select = sqlite().verified_only_select(sql);
+    assert_eq!(
+        vec![
+            ASTNode::SQLPlaceholder("?".to_string(), Span::empty()),
+            ASTNode::SQLPlaceholder("?2".to_string(), Span::empty()),
+            ASTNode::SQLPlaceholder(":name".to_string(), Span::empty()),
+            ASTNode::SQLPlaceholder("@name".to_string(), Span::empty()),
+            ASTNode::SQLPlaceholder("$name".to_string(), Span::empty()),
+            ASTNode::SQLIdentifier("a$b".to_string(), Span::empty()),
+        ],
+        select
+            .projection
+            .iter()
+            .map(|item| expr_from_projection(item).clone())
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn parse_sqlite_limit_comma() {
+    sqlite().one_statement_parses_to(
+        "SELECT * FROM t LIMIT 10, 5",
+        "SELECT * FROM t LIMIT 5 OFFSET 10",
+    );
+}
+
+#[test]
+fn parse_sqlite_pragma() {
+    match sqlite_and_generic().one_statement_parses_to(
+        "PRAGMA main.cache_size = -2000",
+        "PRAGMA main.cache_size = - 2000",
+    ) {
+        SQLStatement::SQLPragma {
+            name, value, is_eq, ..
+        } => {
+            assert_eq!("main.cache_size", name.to_string());
+            assert_eq!("- 2000", value.unwrap().to_string());
+            assert!(is_eq);
+        }
+        _ => unreachable!(),
+    }
+    sqlite_and_generic().verified_stmt("PRAGMA table_info(t)");
+    sqlite_and_generic().verified_stmt("PRAGMA journal_mode = WAL");
+    sqlite_and_generic().verified_stmt("PRAGMA user_version");
+}
+
+#[test]
+fn parse_sqlite_attach_database() {
+    match sqlite_and_generic().verified_stmt("ATTACH DATABASE 'other.db' AS other") {
+        SQLStatement::SQLAttachDatabase {
+            database,
+            database_file_name,
+            schema_name,
+            ..
+        } => {
+            assert!(database);
+            assert_eq!(
+                ASTNode::SQLValue(
+                    Value::SingleQuotedString("other.db".to_string()),
+                    Span::empty()
+                ),
+                database_file_name
+            );
+            assert_eq!("other", schema_name);
+        }
+        _ => unreachable!(),
+    }
+    sqlite_and_generic().verified_stmt("ATTACH 'other.db' AS other");
+}
+
+fn sqlite() -> TestedDialects {
+    TestedDialects {
+        dialects: vec![Box::new(SQLiteDialect {})],
+    }
+}
+fn sqlite_and_generic() -> TestedDialects {
+    TestedDialects {
+        dialects: vec![Box::new(SQLiteDialect {}), Box::new(GenericSqlDialect {})],
+    }
+}